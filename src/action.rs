@@ -1,13 +1,11 @@
 use crate::game::buildings::BuildingKind;
+use crate::game::market::{Order, TradeAsset, TradeSide};
 use crate::game::upgrades::UpgradeId;
 use crate::layout::PaneId;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Action {
-    Render,
-    GameTick,
     Quit,
-    Resize(u16, u16),
     NextPane,
     PrevPane,
     FocusPane(PaneId),
@@ -18,14 +16,32 @@ pub enum Action {
 
     // Upgrade actions
     PurchaseUpgrade(UpgradeId),
+    CancelResearch(UpgradeId),
 
-    // Task actions
-    TaskInput(char),
-    TaskSelect(usize),
-    TaskSubmit,
+    // Market actions
+    Trade {
+        side: TradeSide,
+        asset: TradeAsset,
+        crypto: f64,
+    },
+    PlaceOrder(Order),
+    CancelOrder(u32),
 
     // Prestige
     Prestige,
+    Ipo,
+
+    // Overlays
+    ToggleEventLog,
+    ToggleStats,
+    ToggleNotifications,
+    ToggleResearchTree,
+    ToggleMarket,
+    ToggleAchievements,
+    ToggleChallenges,
+    StartChallenge(String),
+    AbandonChallenge,
+    ExportLeaderboard,
 
     None,
 }
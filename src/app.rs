@@ -3,17 +3,28 @@ use crossterm::event::KeyCode;
 
 use crate::action::Action;
 use crate::components::dashboard::Dashboard;
+use crate::components::event_log::EventLogView;
 use crate::components::header::Header;
 use crate::components::log_stream::LogStream;
+use crate::components::market::MarketView;
 use crate::components::network_map::NetworkMap;
+use crate::components::notifications::NotificationHistoryView;
+use crate::components::research_tree::ResearchTreeView;
 use crate::components::server_rack::ServerRack;
+use crate::components::achievements::AchievementsView;
+use crate::components::challenges::ChallengesView;
+use crate::components::stats_view::StatsView;
 use crate::components::status_bar::StatusBar;
 use crate::components::task_terminal::TaskTerminal;
-use crate::components::Component;
+use crate::components::{self, Component};
 use crate::event::{Event, EventHandler};
-use crate::game::progression;
+use crate::game::daily;
+use crate::game::journal;
+use crate::game::notifications::{Notification, NotificationKind, NotificationManager};
 use crate::game::resources::format_si;
+use crate::game::content;
 use crate::game::save;
+use crate::game::scripting::ScriptHost;
 use crate::game::state::GameState;
 use crate::layout::{self, PaneId, FOCUSABLE_PANES};
 use crate::tui;
@@ -29,14 +40,27 @@ pub struct App {
     server_rack: ServerRack,
     network_map: NetworkMap,
     task_terminal: TaskTerminal,
+    scripts: ScriptHost,
     log_stream: LogStream,
     status_bar: StatusBar,
+    event_log_view: EventLogView,
+    show_event_log: bool,
+    stats_view: StatsView,
+    show_stats: bool,
+    achievements_view: AchievementsView,
+    show_achievements: bool,
+    challenges_view: ChallengesView,
+    show_challenges: bool,
     ticks_since_save: u64,
-    welcome_message: Option<String>,
-    welcome_display_ticks: u32,
+    notifications: NotificationManager,
+    notification_history: NotificationHistoryView,
+    show_notifications: bool,
+    research_tree: ResearchTreeView,
+    show_research_tree: bool,
+    market: MarketView,
+    show_market: bool,
     show_prestige_confirm: bool,
-    achievement_notification: Option<String>,
-    achievement_display_ticks: u32,
+    show_ipo_confirm: bool,
 }
 
 impl App {
@@ -67,6 +91,11 @@ impl App {
             }
         };
 
+        let mut notifications = NotificationManager::new();
+        if let Some(msg) = welcome {
+            notifications.push(Notification::new(NotificationKind::Welcome, msg));
+        }
+
         Self {
             should_quit: false,
             focused_pane: PaneId::Dashboard,
@@ -76,14 +105,27 @@ impl App {
             server_rack: ServerRack::new(),
             network_map: NetworkMap::new(),
             task_terminal: TaskTerminal::new(),
+            scripts: ScriptHost::new(&content::registry().scripts),
             log_stream: LogStream::new(),
             status_bar: StatusBar::new(),
+            event_log_view: EventLogView::new(),
+            show_event_log: false,
+            stats_view: StatsView::new(),
+            show_stats: false,
+            achievements_view: AchievementsView::new(),
+            show_achievements: false,
+            challenges_view: ChallengesView::new(),
+            show_challenges: false,
             ticks_since_save: 0,
-            welcome_message: welcome,
-            welcome_display_ticks: 40, // 10 seconds display
+            notifications,
+            notification_history: NotificationHistoryView::new(),
+            show_notifications: false,
+            research_tree: ResearchTreeView::new(),
+            show_research_tree: false,
+            market: MarketView::new(),
+            show_market: false,
             show_prestige_confirm: false,
-            achievement_notification: None,
-            achievement_display_ticks: 0,
+            show_ipo_confirm: false,
         }
     }
 
@@ -96,30 +138,12 @@ impl App {
 
             match event {
                 Event::Key(key) => {
-                    // Dismiss welcome message on any key
-                    if self.welcome_message.is_some() {
-                        self.welcome_message = None;
-                    }
-
-                    // Dismiss achievement notification on any key
-                    if self.achievement_notification.is_some() {
-                        self.achievement_notification = None;
-                    }
-
                     // Handle prestige confirmation mode
                     if self.show_prestige_confirm {
                         match key.code {
                             KeyCode::Char('y') => {
-                                let rep_earned = self.game_state.prestige();
+                                self.game_state.prestige();
                                 self.show_prestige_confirm = false;
-                                self.achievement_notification = Some(format!(
-                                    "PRESTIGE! +{:.0} Reputation (x{:.2} multiplier)",
-                                    rep_earned,
-                                    progression::reputation_multiplier(
-                                        self.game_state.resources.reputation
-                                    ),
-                                ));
-                                self.achievement_display_ticks = 40;
                             }
                             KeyCode::Char('n') | KeyCode::Esc => {
                                 self.show_prestige_confirm = false;
@@ -129,8 +153,85 @@ impl App {
                         continue;
                     }
 
+                    if self.show_ipo_confirm {
+                        match key.code {
+                            KeyCode::Char('y') => {
+                                self.game_state.ipo();
+                                self.show_ipo_confirm = false;
+                            }
+                            KeyCode::Char('n') | KeyCode::Esc => {
+                                self.show_ipo_confirm = false;
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    // The event log overlay captures all keys while open
+                    if self.show_event_log {
+                        if let Some(action) = self
+                            .event_log_view
+                            .handle_key_with_state(key, &self.game_state)?
+                        {
+                            self.dispatch_action(action);
+                        }
+                        continue;
+                    }
+
+                    if self.show_stats {
+                        if let Some(action) = self.stats_view.handle_key(key)? {
+                            self.dispatch_action(action);
+                        }
+                        continue;
+                    }
+
+                    if self.show_achievements {
+                        if let Some(action) = self.achievements_view.handle_key(key)? {
+                            self.dispatch_action(action);
+                        }
+                        continue;
+                    }
+
+                    if self.show_challenges {
+                        if let Some(action) = self.challenges_view.handle_key(key)? {
+                            self.dispatch_action(action);
+                        }
+                        continue;
+                    }
+
+                    if self.show_research_tree {
+                        if let Some(action) = self
+                            .research_tree
+                            .handle_key_with_state(key, &self.game_state)?
+                        {
+                            self.dispatch_action(action);
+                        }
+                        continue;
+                    }
+
+                    if self.show_market {
+                        if let Some(action) = self
+                            .market
+                            .handle_key_with_state(key, &self.game_state)?
+                        {
+                            self.dispatch_action(action);
+                        }
+                        continue;
+                    }
+
+                    if self.show_notifications {
+                        if let Some(action) = self.notification_history.handle_key(key)? {
+                            self.dispatch_action(action);
+                        }
+                        continue;
+                    }
+
                     // Let focused component handle the key first
                     let component_action = match self.focused_pane {
+                        PaneId::Dashboard => {
+                            self.dashboard
+                                .handle_key_with_state(key, &self.game_state)?
+                        }
                         PaneId::ServerRack => {
                             self.server_rack
                                 .handle_key_with_state(key, &self.game_state)?
@@ -154,6 +255,13 @@ impl App {
                             KeyCode::Char('3') => Action::FocusPane(PaneId::NetworkMap),
                             KeyCode::Char('4') => Action::FocusPane(PaneId::TaskTerminal),
                             KeyCode::Char('p') => Action::Prestige,
+                            KeyCode::Char('i') => Action::Ipo,
+                            KeyCode::Char('l') => Action::ToggleEventLog,
+                            KeyCode::Char('s') => Action::ToggleStats,
+                            KeyCode::Char('n') => Action::ToggleNotifications,
+                            KeyCode::Char('m') => Action::ToggleMarket,
+                            KeyCode::Char('a') => Action::ToggleAchievements,
+                            KeyCode::Char('h') => Action::ToggleChallenges,
                             _ => Action::None,
                         };
                         self.dispatch_action(action);
@@ -162,31 +270,18 @@ impl App {
                 Event::GameTick => {
                     self.game_state.tick();
                     self.task_terminal.game_tick(&mut self.game_state);
+                    self.scripts.tick(&mut self.game_state);
 
-                    // Check achievements
-                    let new_achievements = self.game_state.check_achievements();
-                    if !new_achievements.is_empty() {
-                        self.achievement_notification =
-                            Some(format!("* {} unlocked!", new_achievements.join(", ")));
-                        self.achievement_display_ticks = 32; // 8 seconds
-                    }
+                    self.game_state.check_achievements();
+                    self.notifications.tick();
 
-                    // Tick down achievement notification
-                    if self.achievement_notification.is_some() {
-                        if self.achievement_display_ticks > 0 {
-                            self.achievement_display_ticks -= 1;
-                        } else {
-                            self.achievement_notification = None;
-                        }
+                    // Append new events to the on-disk journal
+                    let entries = self.game_state.drain_journal();
+                    if let Err(e) = journal::append(&entries) {
+                        tracing::warn!("Failed to write journal: {e}");
                     }
-
-                    // Tick down welcome message
-                    if self.welcome_message.is_some() {
-                        if self.welcome_display_ticks > 0 {
-                            self.welcome_display_ticks -= 1;
-                        } else {
-                            self.welcome_message = None;
-                        }
+                    if let Err(e) = daily::record(self.game_state.drain_daily_results()) {
+                        tracing::warn!("Failed to write daily leaderboard: {e}");
                     }
 
                     // Auto-save
@@ -197,12 +292,21 @@ impl App {
                     }
                 }
                 Event::Render => {
+                    self.notifications
+                        .extend(self.game_state.drain_notifications());
                     self.status_bar.set_focused_pane(self.focused_pane);
                     let focused = self.focused_pane;
                     let game_state = &self.game_state;
-                    let welcome = self.welcome_message.as_deref();
                     let show_prestige = self.show_prestige_confirm;
-                    let achievement = self.achievement_notification.as_deref();
+                    let show_ipo = self.show_ipo_confirm;
+                    let show_event_log = self.show_event_log;
+                    let show_stats = self.show_stats;
+                    let show_achievements = self.show_achievements;
+                    let show_challenges = self.show_challenges;
+                    let show_notifications = self.show_notifications;
+                    let show_research_tree = self.show_research_tree;
+                    let show_market = self.show_market;
+                    let notifications = &self.notifications;
                     terminal.draw(|frame| {
                         let panes = layout::compute_layout(frame.area());
 
@@ -248,149 +352,62 @@ impl App {
                             .draw(frame, panes.status_bar, false)
                             .ok();
 
-                        // Achievement notification overlay
-                        if let Some(msg) = achievement {
-                            let popup_width =
-                                (msg.len() as u16 + 4).min(frame.area().width.saturating_sub(4));
-                            let popup_area = ratatui::layout::Rect {
-                                x: (frame.area().width.saturating_sub(popup_width)) / 2,
-                                y: 4,
-                                width: popup_width,
-                                height: 3,
-                            };
-                            let popup = ratatui::widgets::Paragraph::new(format!(" {msg}"))
-                                .style(
-                                    ratatui::style::Style::default()
-                                        .fg(crate::theme::ACCENT_MAGENTA),
-                                )
-                                .block(
-                                    ratatui::widgets::Block::default()
-                                        .borders(ratatui::widgets::Borders::ALL)
-                                        .border_type(ratatui::widgets::BorderType::Double)
-                                        .border_style(
-                                            ratatui::style::Style::default()
-                                                .fg(crate::theme::ACCENT_MAGENTA),
-                                        )
-                                        .title(" ACHIEVEMENT "),
-                                );
-                            frame.render_widget(ratatui::widgets::Clear, popup_area);
-                            frame.render_widget(popup, popup_area);
+                        if show_stats {
+                            self.stats_view
+                                .draw_with_state(frame, frame.area(), game_state)
+                                .ok();
+                        }
+
+                        if show_achievements {
+                            self.achievements_view
+                                .draw_with_state(frame, frame.area(), game_state)
+                                .ok();
+                        }
+
+                        if show_challenges {
+                            self.challenges_view
+                                .draw_with_state(frame, frame.area(), game_state)
+                                .ok();
+                        }
+
+                        if show_event_log {
+                            self.event_log_view
+                                .draw_with_state(frame, frame.area(), game_state)
+                                .ok();
+                        }
+
+                        if show_research_tree {
+                            self.research_tree
+                                .draw_with_state(frame, frame.area(), game_state)
+                                .ok();
+                        }
+
+                        if show_market {
+                            self.market
+                                .draw_with_state(frame, frame.area(), game_state)
+                                .ok();
                         }
 
-                        // Welcome back overlay
-                        if let Some(msg) = welcome {
-                            let popup_width =
-                                (msg.len() as u16 + 4).min(frame.area().width.saturating_sub(4));
-                            let popup_area = ratatui::layout::Rect {
-                                x: (frame.area().width.saturating_sub(popup_width)) / 2,
-                                y: frame.area().height / 2 - 1,
-                                width: popup_width,
-                                height: 3,
-                            };
-                            let popup = ratatui::widgets::Paragraph::new(format!(" {msg}"))
-                                .style(
-                                    ratatui::style::Style::default()
-                                        .fg(crate::theme::FG_PRIMARY),
-                                )
-                                .block(
-                                    ratatui::widgets::Block::default()
-                                        .borders(ratatui::widgets::Borders::ALL)
-                                        .border_type(ratatui::widgets::BorderType::Double)
-                                        .border_style(
-                                            ratatui::style::Style::default()
-                                                .fg(crate::theme::ACCENT_CYAN),
-                                        )
-                                        .title(" WELCOME BACK "),
-                                );
-                            frame.render_widget(ratatui::widgets::Clear, popup_area);
-                            frame.render_widget(popup, popup_area);
+                        if show_notifications {
+                            self.notification_history
+                                .draw(frame, frame.area(), notifications);
                         }
 
-                        // Prestige confirmation overlay
+                        components::notifications::draw_toasts(
+                            frame,
+                            frame.area(),
+                            notifications,
+                        );
+
                         if show_prestige {
-                            let rep_preview =
-                                progression::prestige_reputation(game_state.resources.compute);
-                            let new_mult = progression::reputation_multiplier(
-                                game_state.resources.reputation + rep_preview,
-                            );
-
-                            let lines = vec![
-                                ratatui::text::Line::from(""),
-                                ratatui::text::Line::from(vec![ratatui::text::Span::styled(
-                                    "  This will reset ALL resources and buildings.",
-                                    ratatui::style::Style::default()
-                                        .fg(crate::theme::ACCENT_YELLOW),
-                                )]),
-                                ratatui::text::Line::from(vec![
-                                    ratatui::text::Span::styled(
-                                        "  Reputation earned: +",
-                                        crate::theme::text_dim(),
-                                    ),
-                                    ratatui::text::Span::styled(
-                                        format!("{:.0}", rep_preview),
-                                        ratatui::style::Style::default()
-                                            .fg(crate::theme::ACCENT_MAGENTA),
-                                    ),
-                                ]),
-                                ratatui::text::Line::from(vec![
-                                    ratatui::text::Span::styled(
-                                        "  New multiplier: x",
-                                        crate::theme::text_dim(),
-                                    ),
-                                    ratatui::text::Span::styled(
-                                        format!("{:.2}", new_mult),
-                                        crate::theme::text_value(),
-                                    ),
-                                ]),
-                                ratatui::text::Line::from(""),
-                                ratatui::text::Line::from(vec![
-                                    ratatui::text::Span::styled(
-                                        "  [y] ",
-                                        crate::theme::text_value(),
-                                    ),
-                                    ratatui::text::Span::styled(
-                                        "Confirm  ",
-                                        crate::theme::text_dim(),
-                                    ),
-                                    ratatui::text::Span::styled(
-                                        "[n] ",
-                                        crate::theme::text_value(),
-                                    ),
-                                    ratatui::text::Span::styled(
-                                        "Cancel",
-                                        crate::theme::text_dim(),
-                                    ),
-                                ]),
-                            ];
-
-                            let popup_width = 50u16.min(frame.area().width.saturating_sub(4));
-                            let popup_height = 8u16;
-                            let popup_area = ratatui::layout::Rect {
-                                x: (frame.area().width.saturating_sub(popup_width)) / 2,
-                                y: frame
-                                    .area()
-                                    .height
-                                    .saturating_sub(popup_height)
-                                    / 2,
-                                width: popup_width,
-                                height: popup_height,
-                            };
-                            let popup = ratatui::widgets::Paragraph::new(lines).block(
-                                ratatui::widgets::Block::default()
-                                    .borders(ratatui::widgets::Borders::ALL)
-                                    .border_type(ratatui::widgets::BorderType::Double)
-                                    .border_style(
-                                        ratatui::style::Style::default()
-                                            .fg(crate::theme::ACCENT_MAGENTA),
-                                    )
-                                    .title(" * PRESTIGE RESET * "),
-                            );
-                            frame.render_widget(ratatui::widgets::Clear, popup_area);
-                            frame.render_widget(popup, popup_area);
+                            components::prestige::draw_confirm(frame, frame.area(), game_state);
+                        }
+
+                        if show_ipo {
+                            components::ipo::draw_confirm(frame, frame.area(), game_state);
                         }
                     })?;
                 }
-                Event::Resize(_, _) | Event::Mouse(_) => {}
             }
 
             if self.should_quit {
@@ -399,6 +416,7 @@ impl App {
         }
 
         // Save on quit
+        journal::append(&self.game_state.drain_journal()).ok();
         save::save_game(&self.game_state).ok();
         tui::restore()?;
         Ok(())
@@ -419,18 +437,78 @@ impl App {
                 self.focused_pane = pane;
             }
             Action::PurchaseBuilding(kind) => {
-                self.game_state.purchase_building(kind);
+                // Failures are logged and raised as toasts by GameState
+                self.game_state.purchase_building(kind).ok();
             }
             Action::UpgradeBuilding(kind) => {
-                self.game_state.upgrade_building(kind);
+                self.game_state.upgrade_building(kind).ok();
             }
             Action::PurchaseUpgrade(id) => {
-                self.game_state.purchase_upgrade(id);
+                self.game_state.purchase_upgrade(id).ok();
+            }
+            Action::CancelResearch(id) => {
+                self.game_state.cancel_research(id);
+            }
+            Action::Trade {
+                side,
+                asset,
+                crypto,
+            } => {
+                self.game_state.trade(side, asset, crypto).ok();
+            }
+            Action::PlaceOrder(order) => {
+                self.game_state.place_order(order).ok();
+            }
+            Action::CancelOrder(id) => {
+                self.game_state.cancel_order(id);
+            }
+            Action::Prestige if self.game_state.can_prestige() => {
+                self.show_prestige_confirm = true;
+            }
+            Action::Ipo if self.game_state.can_ipo() => {
+                self.show_ipo_confirm = true;
+            }
+            Action::ToggleEventLog => {
+                self.show_event_log = !self.show_event_log;
             }
-            Action::Prestige => {
-                if self.game_state.can_prestige() {
-                    self.show_prestige_confirm = true;
+            Action::ToggleStats => {
+                self.show_stats = !self.show_stats;
+            }
+            Action::ToggleAchievements => {
+                self.show_achievements = !self.show_achievements;
+            }
+            Action::ToggleChallenges => {
+                self.show_challenges = !self.show_challenges;
+                if self.show_challenges {
+                    self.challenges_view.refresh();
+                }
+            }
+            Action::StartChallenge(id) => {
+                if self.game_state.start_challenge(&id) {
+                    if let Some(date) = daily::date_of(&id) {
+                        self.task_terminal.restart(daily::daily_seed(date));
+                    }
                 }
+                self.show_challenges = false;
+            }
+            Action::ExportLeaderboard => match daily::export() {
+                Ok(path) => self.game_state.notify(
+                    NotificationKind::Info,
+                    format!("Leaderboard exported to {}", path.display()),
+                ),
+                Err(e) => tracing::warn!("Failed to export leaderboard: {e}"),
+            },
+            Action::AbandonChallenge => {
+                self.game_state.abandon_challenge();
+            }
+            Action::ToggleNotifications => {
+                self.show_notifications = !self.show_notifications;
+            }
+            Action::ToggleResearchTree => {
+                self.show_research_tree = !self.show_research_tree;
+            }
+            Action::ToggleMarket => {
+                self.show_market = !self.show_market;
             }
             _ => {}
         }
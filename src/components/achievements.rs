@@ -0,0 +1,164 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::action::Action;
+use crate::game::progression::{all_achievement_defs, AchievementDef};
+use crate::game::resources::format_si;
+use crate::game::state::GameState;
+use crate::theme;
+
+/// Width of the progress bar next to locked achievements.
+const BAR_WIDTH: usize = 12;
+
+/// Overlay listing every achievement, unlocked or not.
+pub struct AchievementsView {
+    scroll: usize,
+}
+
+impl AchievementsView {
+    pub fn new() -> Self {
+        Self { scroll: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('a') => return Ok(Some(Action::ToggleAchievements)),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll += 1,
+            _ => {}
+        }
+        Ok(Some(Action::None))
+    }
+
+    pub fn draw_with_state(
+        &self,
+        frame: &mut Frame<'_>,
+        area: Rect,
+        state: &GameState,
+    ) -> Result<()> {
+        let width = 76u16.min(area.width);
+        let height = area.height.saturating_sub(4).max(8).min(area.height);
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+
+        let defs = all_achievement_defs();
+        let block = Block::default()
+            .title(format!(
+                " ACHIEVEMENTS {}/{} ",
+                state.achievements.len(),
+                defs.len()
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(theme::border_focused());
+
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let lines: Vec<Line> = defs
+            .iter()
+            .flat_map(|def| achievement_lines(def, state))
+            .collect();
+
+        let visible = inner.height as usize;
+        let max_scroll = lines.len().saturating_sub(visible.saturating_sub(1));
+        let scroll = self.scroll.min(max_scroll);
+        let mut shown: Vec<Line> = lines
+            .into_iter()
+            .skip(scroll)
+            .take(visible.saturating_sub(1))
+            .collect();
+        shown.push(Line::from(vec![
+            Span::styled(" [↑/↓]", theme::text_value()),
+            Span::styled("Scroll ", theme::text_dim()),
+            Span::styled("[Esc]", theme::text_value()),
+            Span::styled("Close", theme::text_dim()),
+        ]));
+
+        frame.render_widget(Paragraph::new(shown), inner);
+        Ok(())
+    }
+}
+
+/// Two lines per achievement: name and status, then details.
+fn achievement_lines<'a>(def: &AchievementDef, state: &GameState) -> Vec<Line<'a>> {
+    let unlocked = state.unlocked_achievement(&def.id);
+    if def.hidden && unlocked.is_none() {
+        return vec![
+            Line::from(vec![
+                Span::styled(" ? ", theme::text_dim()),
+                Span::styled("???", theme::text_dim()),
+            ]),
+            Line::from(Span::styled("     Hidden achievement", theme::text_dim())),
+        ];
+    }
+
+    let (marker, name_style) = match unlocked {
+        Some(_) => (" ✓ ", Style::default().fg(theme::ACCENT_MAGENTA)),
+        None => (" ○ ", theme::text_value()),
+    };
+    let mut header = vec![
+        Span::styled(marker, name_style),
+        Span::styled(format!("{:<24}", def.name), name_style),
+    ];
+    match (unlocked, def.condition.as_ref()) {
+        (Some(record), _) => {
+            let when = record
+                .unlocked_at
+                .map(|t| {
+                    t.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_else(|| "unlocked".into());
+            header.push(Span::styled(when, theme::text_dim()));
+        }
+        (None, Some(condition)) => {
+            let target = condition.target();
+            let progress = state.achievement_progress(condition).min(target);
+            let filled = if target > 0.0 {
+                (progress / target * BAR_WIDTH as f64) as usize
+            } else {
+                BAR_WIDTH
+            };
+            header.push(Span::styled(
+                format!("{}{} ", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled)),
+                Style::default().fg(theme::FG_PRIMARY),
+            ));
+            header.push(Span::styled(
+                format!(
+                    "{}/{} {}",
+                    format_si(progress),
+                    format_si(target),
+                    condition.unit()
+                ),
+                theme::text_dim(),
+            ));
+        }
+        (None, None) => header.push(Span::styled("scripted", theme::text_dim())),
+    }
+
+    let mut details = vec![Span::styled(
+        format!("     {}", def.description),
+        theme::text_dim(),
+    )];
+    if let Some(reward) = &def.reward {
+        details.push(Span::styled(
+            format!("  Reward: {}", reward.description()),
+            Style::default().fg(theme::ACCENT_YELLOW),
+        ));
+    }
+    vec![Line::from(header), Line::from(details)]
+}
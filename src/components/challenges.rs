@@ -0,0 +1,199 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::action::Action;
+use crate::game::challenges::{all_challenges, ChallengeDef};
+use crate::game::daily::{self, Leaderboard};
+use crate::game::resources::format_si;
+use crate::game::state::GameState;
+use crate::theme;
+
+/// Daily results shown under the challenge list.
+const LEADERBOARD_ROWS: usize = 5;
+
+/// Overlay for picking, starting and abandoning challenge runs.
+pub struct ChallengesView {
+    selected: usize,
+    /// Enter was pressed once; a second press starts the challenge.
+    confirming: bool,
+    leaderboard: Leaderboard,
+}
+
+/// Today's daily challenge first, then the fixed ones.
+fn entries() -> Vec<ChallengeDef> {
+    let mut entries = vec![daily::daily_challenge(daily::today())];
+    entries.extend_from_slice(all_challenges());
+    entries
+}
+
+impl ChallengesView {
+    pub fn new() -> Self {
+        Self {
+            selected: 0,
+            confirming: false,
+            leaderboard: Leaderboard::default(),
+        }
+    }
+
+    /// Re-read the daily leaderboard from disk.
+    pub fn refresh(&mut self) {
+        self.leaderboard = daily::load();
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let count = entries().len();
+        let confirming = std::mem::take(&mut self.confirming);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('h') => return Ok(Some(Action::ToggleChallenges)),
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if self.selected + 1 < count => {
+                self.selected += 1;
+            }
+            KeyCode::Enter if confirming => {
+                let id = entries().swap_remove(self.selected).id;
+                return Ok(Some(Action::StartChallenge(id)));
+            }
+            KeyCode::Enter => self.confirming = true,
+            KeyCode::Char('x') => return Ok(Some(Action::AbandonChallenge)),
+            KeyCode::Char('e') => return Ok(Some(Action::ExportLeaderboard)),
+            _ => {}
+        }
+        Ok(Some(Action::None))
+    }
+
+    pub fn draw_with_state(
+        &self,
+        frame: &mut Frame<'_>,
+        area: Rect,
+        state: &GameState,
+    ) -> Result<()> {
+        let width = 72u16.min(area.width);
+        let height = area.height.saturating_sub(4).max(8).min(area.height);
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+
+        let block = Block::default()
+            .title(" CHALLENGES ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(theme::border_focused());
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let mut lines = Vec::new();
+        if let Some(active) = state.active_challenge() {
+            let mut status = vec![
+                Span::styled(" Playing: ", theme::text_dim()),
+                Span::styled(active.name.clone(), Style::default().fg(theme::ACCENT_MAGENTA)),
+                Span::styled(
+                    format!(
+                        "  {}/{}",
+                        format_si(active.progress(&state.resources)),
+                        format_si(active.goal_amount)
+                    ),
+                    theme::text_value(),
+                ),
+            ];
+            if let Some(ticks) = state.challenge_remaining_ticks() {
+                let secs = ticks / 4;
+                status.push(Span::styled(
+                    format!("  {}m {:02}s left", secs / 60, secs % 60),
+                    Style::default().fg(theme::ACCENT_YELLOW),
+                ));
+            }
+            lines.push(Line::from(status));
+            lines.push(Line::from(""));
+        }
+
+        let today = daily::today();
+        for (i, challenge) in entries().iter().enumerate() {
+            let selected = i == self.selected;
+            let done = state.completed_challenges.contains(&challenge.id)
+                || (i == 0 && self.leaderboard.best(today).is_some());
+            let marker = match (selected, done) {
+                (true, _) => " ▸ ",
+                (false, true) => " ✓ ",
+                (false, false) => "   ",
+            };
+            let mut name_style = theme::text_value();
+            if selected {
+                name_style = name_style.add_modifier(Modifier::BOLD);
+            }
+            lines.push(Line::from(vec![
+                Span::styled(marker, name_style),
+                Span::styled(format!("{:<20}", challenge.name), name_style),
+                Span::styled(
+                    format!("+{:.0} rep", challenge.reputation),
+                    Style::default().fg(theme::ACCENT_YELLOW),
+                ),
+                Span::styled(if done { "  completed" } else { "" }, theme::text_dim()),
+            ]));
+            lines.push(Line::from(Span::styled(
+                format!("     {}", challenge.description),
+                theme::text_dim(),
+            )));
+            lines.push(Line::from(Span::styled(
+                format!("     Rules: {}", challenge.modifiers.describe().join(", ")),
+                theme::text_dim(),
+            )));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            " Daily leaderboard",
+            Style::default().fg(theme::ACCENT_MAGENTA),
+        )));
+        let best = self.leaderboard.best_per_day();
+        if best.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "   No daily runs finished yet",
+                theme::text_dim(),
+            )));
+        }
+        for result in best.into_iter().take(LEADERBOARD_ROWS) {
+            lines.push(Line::from(vec![
+                Span::styled(format!("   {}  ", result.date), theme::text_dim()),
+                Span::styled(format!("{:<10}", result.time()), theme::text_value()),
+                Span::styled(
+                    format!("{} pts", result.score),
+                    Style::default().fg(theme::ACCENT_YELLOW),
+                ),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        if self.confirming {
+            lines.push(Line::from(Span::styled(
+                " Starting resets this run (reputation is kept). Press Enter again to start.",
+                Style::default().fg(theme::ACCENT_YELLOW),
+            )));
+        }
+        lines.push(Line::from(vec![
+            Span::styled(" [↑/↓]", theme::text_value()),
+            Span::styled("Select ", theme::text_dim()),
+            Span::styled("[Enter]", theme::text_value()),
+            Span::styled("Start ", theme::text_dim()),
+            Span::styled("[x]", theme::text_value()),
+            Span::styled("Abandon ", theme::text_dim()),
+            Span::styled("[e]", theme::text_value()),
+            Span::styled("Export ", theme::text_dim()),
+            Span::styled("[Esc]", theme::text_value()),
+            Span::styled("Close", theme::text_dim()),
+        ]));
+
+        frame.render_widget(Paragraph::new(lines), inner);
+        Ok(())
+    }
+}
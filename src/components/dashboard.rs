@@ -1,23 +1,83 @@
-use std::collections::VecDeque;
-
 use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::Rect,
+    style::{Color, Style},
+    symbols,
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Paragraph},
+    widgets::{Axis, Block, BorderType, Borders, Chart, Dataset, GraphType, Paragraph},
     Frame,
 };
 
+use crate::action::Action;
 use crate::components::Component;
-use crate::game::resources::format_si;
+use crate::game::history::{HistoryRange, Sample};
+use crate::game::incidents::IncidentState;
+use crate::game::progression;
+use crate::game::resources::{format_si, Resources};
 use crate::game::state::GameState;
 use crate::theme;
 
-pub struct Dashboard;
+#[derive(Clone, Copy, PartialEq)]
+enum View {
+    Summary,
+    Chart,
+}
+
+/// What the chart plots: balances or production rates.
+#[derive(Clone, Copy, PartialEq)]
+enum Metric {
+    Balance,
+    Rate,
+}
+
+/// Label, line color and accessor for one charted resource.
+type Series = (&'static str, Color, fn(&Resources) -> f64);
+
+pub struct Dashboard {
+    view: View,
+    range: HistoryRange,
+    metric: Metric,
+    log_scale: bool,
+}
 
 impl Dashboard {
     pub fn new() -> Self {
-        Self
+        Self {
+            view: View::Summary,
+            range: HistoryRange::Minute,
+            metric: Metric::Balance,
+            log_scale: true,
+        }
+    }
+
+    pub fn handle_key_with_state(
+        &mut self,
+        key: KeyEvent,
+        _state: &GameState,
+    ) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('c') => {
+                self.view = match self.view {
+                    View::Summary => View::Chart,
+                    View::Chart => View::Summary,
+                };
+            }
+            KeyCode::Char('t') if self.view == View::Chart => {
+                self.range = self.range.next();
+            }
+            KeyCode::Char('v') if self.view == View::Chart => {
+                self.metric = match self.metric {
+                    Metric::Balance => Metric::Rate,
+                    Metric::Rate => Metric::Balance,
+                };
+            }
+            KeyCode::Char('g') if self.view == View::Chart => {
+                self.log_scale = !self.log_scale;
+            }
+            _ => return Ok(None),
+        }
+        Ok(Some(Action::None))
     }
 
     pub fn draw_with_state(
@@ -47,6 +107,11 @@ impl Dashboard {
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
+        if self.view == View::Chart {
+            return self.draw_chart(frame, inner, focused, state);
+        }
+
+        let net = state.net_per_tick();
         let mut lines = vec![
             Line::from(""),
             resource_line(
@@ -54,6 +119,7 @@ impl Dashboard {
                 "Compute",
                 state.resources.compute,
                 state.production_per_tick.compute,
+                net.compute,
             ),
             Line::from(""),
             resource_line(
@@ -61,6 +127,7 @@ impl Dashboard {
                 "Bandwidth",
                 state.resources.bandwidth,
                 state.production_per_tick.bandwidth,
+                net.bandwidth,
             ),
             Line::from(""),
             resource_line(
@@ -68,6 +135,7 @@ impl Dashboard {
                 "Storage",
                 state.resources.storage,
                 state.production_per_tick.storage,
+                net.storage,
             ),
             Line::from(""),
             resource_line(
@@ -75,6 +143,7 @@ impl Dashboard {
                 "Reputation",
                 state.resources.reputation,
                 state.production_per_tick.reputation,
+                net.reputation,
             ),
             Line::from(""),
             resource_line(
@@ -82,13 +151,31 @@ impl Dashboard {
                 "Crypto",
                 state.resources.crypto,
                 state.production_per_tick.crypto,
+                net.crypto,
             ),
         ];
 
+        if state.upkeep_satisfaction < 1.0 {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  ! Upkeep short: servers at {:.0}% (need BW/SSD)",
+                    state.upkeep_satisfaction * 100.0
+                ),
+                ratatui::style::Style::default().fg(theme::ACCENT_RED),
+            )));
+        }
+
         // Sparkline for compute history
-        if !state.compute_history.is_empty() {
+        if !state.history.is_empty() {
             let width = (inner.width as usize).saturating_sub(4);
-            let spark = sparkline_text(&state.compute_history, width);
+            let compute: Vec<f64> = state
+                .history
+                .samples(HistoryRange::Minute)
+                .iter()
+                .map(|s| s.resources.compute)
+                .collect();
+            let spark = sparkline_text(&compute, width);
             lines.push(Line::from(""));
             lines.push(Line::from(vec![
                 Span::styled("  ", theme::text_dim()),
@@ -103,24 +190,121 @@ impl Dashboard {
         if inner.height as usize > lines.len() + 2 {
             lines.push(Line::from(""));
             if state.can_prestige() {
-                lines.push(Line::from(Span::styled(
-                    "  * PRESTIGE AVAILABLE [p]",
-                    ratatui::style::Style::default().fg(theme::ACCENT_MAGENTA),
-                )));
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        "  * PRESTIGE AVAILABLE [p] ",
+                        ratatui::style::Style::default().fg(theme::ACCENT_MAGENTA),
+                    ),
+                    Span::styled(
+                        format!(
+                            "+{:.0} rep, {:.2}/min",
+                            progression::prestige_reputation(state.run_compute),
+                            state.reputation_per_minute()
+                        ),
+                        theme::text_dim(),
+                    ),
+                ]));
             } else {
-                let progress = (state.resources.compute / 1_000_000.0 * 100.0).min(100.0);
+                let progress =
+                    (state.run_compute / progression::PRESTIGE_COMPUTE * 100.0).min(100.0);
                 lines.push(Line::from(vec![
                     Span::styled("  Prestige: ", theme::text_dim()),
-                    Span::styled(format!("{:.1}% to 1M CPU", progress), theme::text_dim()),
+                    Span::styled(
+                        format!("{:.1}% to 1M CPU this run", progress),
+                        theme::text_dim(),
+                    ),
                 ]));
             }
+            if state.can_ipo() && (inner.height as usize) > lines.len() + 1 {
+                lines.push(Line::from(Span::styled(
+                    "  $ IPO AVAILABLE [i]",
+                    ratatui::style::Style::default().fg(theme::ACCENT_YELLOW),
+                )));
+            }
         }
 
-        // Achievements count
+        // Open incidents
+        for incident in &state.incidents {
+            if incident.state == IncidentState::Undetected
+                || (inner.height as usize) <= lines.len() + 1
+            {
+                continue;
+            }
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  ! {} sev {} ", incident.name, incident.severity),
+                    ratatui::style::Style::default().fg(theme::ACCENT_RED),
+                ),
+                Span::styled(incident.state.label(), theme::text_dim()),
+            ]));
+        }
+
+        // Challenge goal and clock
+        if let Some(challenge) = state.active_challenge() {
+            if (inner.height as usize) > lines.len() + 1 {
+                let mut spans = vec![
+                    Span::styled("  Challenge: ", theme::text_dim()),
+                    Span::styled(
+                        format!("{} ", challenge.name),
+                        ratatui::style::Style::default().fg(theme::ACCENT_MAGENTA),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{}/{}",
+                            format_si(challenge.progress(&state.resources)),
+                            format_si(challenge.goal_amount)
+                        ),
+                        theme::text_value(),
+                    ),
+                ];
+                if let Some(ticks) = state.challenge_remaining_ticks() {
+                    spans.push(Span::styled(
+                        format!("  {}m left", ticks / (4 * 60)),
+                        theme::text_dim(),
+                    ));
+                }
+                lines.push(Line::from(spans));
+            }
+        }
+
+        // Achievements count and the closest one to unlocking
         if !state.achievements.is_empty() && (inner.height as usize) > lines.len() + 1 {
             lines.push(Line::from(vec![
                 Span::styled("  Achievements: ", theme::text_dim()),
-                Span::styled(format!("{}/10", state.achievements.len()), theme::text_value()),
+                Span::styled(
+                    format!(
+                        "{}/{}",
+                        state.achievements.len(),
+                        progression::all_achievement_defs().len()
+                    ),
+                    theme::text_value(),
+                ),
+            ]));
+        }
+        if let Some((def, progress)) = state.next_achievement() {
+            if let Some(condition) = def.condition.as_ref() {
+                if (inner.height as usize) > lines.len() + 1 {
+                    lines.push(Line::from(vec![
+                        Span::styled("  Next: ", theme::text_dim()),
+                        Span::styled(format!("{} ", def.name), theme::text_value()),
+                        Span::styled(
+                            format!(
+                                "{}/{} {}",
+                                format_si(progress.min(condition.target())),
+                                format_si(condition.target()),
+                                condition.unit()
+                            ),
+                            theme::text_dim(),
+                        ),
+                    ]));
+                }
+            }
+        }
+
+        if focused && (inner.height as usize) > lines.len() + 1 {
+            lines.push(Line::from(vec![
+                Span::styled("  [c]", theme::text_value()),
+                Span::styled("Charts", theme::text_dim()),
             ]));
         }
 
@@ -128,45 +312,201 @@ impl Dashboard {
         frame.render_widget(content, inner);
         Ok(())
     }
+
+    fn draw_chart(
+        &self,
+        frame: &mut Frame<'_>,
+        area: Rect,
+        focused: bool,
+        state: &GameState,
+    ) -> Result<()> {
+        let samples = state.history.samples(self.range);
+
+        let series: [Series; 4] = [
+            ("CPU", theme::FG_PRIMARY, |r| r.compute),
+            ("BW", theme::ACCENT_CYAN, |r| r.bandwidth),
+            ("SSD", theme::ACCENT_YELLOW, |r| r.storage),
+            ("BTC", theme::ACCENT_MAGENTA, |r| r.crypto),
+        ];
+
+        let points: Vec<Vec<(f64, f64)>> = series
+            .iter()
+            .map(|(_, _, get)| {
+                samples
+                    .iter()
+                    .enumerate()
+                    .map(|(i, s)| (i as f64, self.scale(get(self.metric_of(s)))))
+                    .collect()
+            })
+            .collect();
+
+        let y_max = points
+            .iter()
+            .flatten()
+            .map(|(_, y)| *y)
+            .fold(0.0_f64, f64::max)
+            .max(1.0)
+            * 1.1;
+        let x_max = (samples.len().max(2) - 1) as f64;
+
+        let datasets: Vec<Dataset> = series
+            .iter()
+            .zip(points.iter())
+            .filter(|(_, pts)| pts.iter().any(|(_, y)| *y > 0.0))
+            .map(|((name, color, _), pts)| {
+                Dataset::default()
+                    .name(*name)
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(*color))
+                    .data(pts)
+            })
+            .collect();
+
+        let metric_label = match self.metric {
+            Metric::Balance => "balance",
+            Metric::Rate => "per sec",
+        };
+        let scale_label = if self.log_scale { "log" } else { "lin" };
+
+        let chart_area = Rect {
+            height: area.height.saturating_sub(if focused { 1 } else { 0 }),
+            ..area
+        };
+
+        if samples.is_empty() {
+            let msg = Paragraph::new("  Collecting samples...").style(theme::text_dim());
+            frame.render_widget(msg, chart_area);
+        } else {
+            let chart = Chart::new(datasets)
+                .x_axis(
+                    Axis::default()
+                        .style(theme::text_dim())
+                        .bounds([0.0, x_max])
+                        .labels(vec![
+                            Span::styled(format!("-{}", self.range.label()), theme::text_dim()),
+                            Span::styled("now", theme::text_dim()),
+                        ]),
+                )
+                .y_axis(
+                    Axis::default()
+                        .title(Span::styled(
+                            format!("{metric_label} ({scale_label})"),
+                            theme::text_dim(),
+                        ))
+                        .style(theme::text_dim())
+                        .bounds([0.0, y_max])
+                        .labels(vec![
+                            Span::styled("0", theme::text_dim()),
+                            Span::styled(format_si(self.unscale(y_max / 2.0)), theme::text_dim()),
+                            Span::styled(format_si(self.unscale(y_max)), theme::text_dim()),
+                        ]),
+                );
+            frame.render_widget(chart, chart_area);
+        }
+
+        if focused {
+            let help = Line::from(vec![
+                Span::styled(" [t]", theme::text_value()),
+                Span::styled(format!("{} ", self.range.label()), theme::text_dim()),
+                Span::styled("[v]", theme::text_value()),
+                Span::styled(format!("{metric_label} "), theme::text_dim()),
+                Span::styled("[g]", theme::text_value()),
+                Span::styled(format!("{scale_label} "), theme::text_dim()),
+                Span::styled("[c]", theme::text_value()),
+                Span::styled("Summary", theme::text_dim()),
+            ]);
+            let help_area = Rect {
+                y: area.y + area.height.saturating_sub(1),
+                height: 1,
+                ..area
+            };
+            frame.render_widget(help, help_area);
+        }
+
+        Ok(())
+    }
+
+    fn metric_of<'a>(&self, sample: &'a Sample) -> &'a Resources {
+        match self.metric {
+            Metric::Balance => &sample.resources,
+            Metric::Rate => &sample.production,
+        }
+    }
+
+    /// Map a raw value onto the chart's y axis.
+    fn scale(&self, value: f64) -> f64 {
+        let value = match self.metric {
+            Metric::Balance => value,
+            Metric::Rate => value * 4.0,
+        }
+        .max(0.0);
+        if self.log_scale {
+            (value + 1.0).log10()
+        } else {
+            value
+        }
+    }
+
+    /// Inverse of `scale` for axis labels (already per second).
+    fn unscale(&self, y: f64) -> f64 {
+        if self.log_scale {
+            10f64.powf(y) - 1.0
+        } else {
+            y
+        }
+    }
 }
 
-fn resource_line<'a>(symbol: &'a str, name: &'a str, amount: f64, per_tick: f64) -> Line<'a> {
-    let per_sec = per_tick * 4.0;
-    Line::from(vec![
+fn resource_line<'a>(
+    symbol: &'a str,
+    name: &'a str,
+    amount: f64,
+    gross_per_tick: f64,
+    net_per_tick: f64,
+) -> Line<'a> {
+    let mut spans = vec![
         Span::styled(format!("  {symbol} "), theme::title()),
         Span::styled(format!("{:<10}", name), theme::text_dim()),
         Span::styled(format!("{:>8}", format_si(amount)), theme::text_value()),
         Span::styled("  +", theme::text_dim()),
         Span::styled(
-            format!("{}/s", format_si(per_sec)),
+            format!("{}/s", format_si(gross_per_tick * 4.0)),
             ratatui::style::Style::default().fg(theme::FG_PRIMARY),
         ),
-    ])
+    ];
+    // Only resources that pay upkeep show a separate net rate
+    if (gross_per_tick - net_per_tick).abs() > f64::EPSILON {
+        let net_per_sec = net_per_tick * 4.0;
+        let (sign, color) = if net_per_sec < 0.0 {
+            ("-", theme::ACCENT_RED)
+        } else {
+            ("+", theme::FG_PRIMARY)
+        };
+        spans.push(Span::styled("  net ", theme::text_dim()));
+        spans.push(Span::styled(
+            format!("{sign}{}/s", format_si(net_per_sec.abs())),
+            ratatui::style::Style::default().fg(color),
+        ));
+    }
+    Line::from(spans)
 }
 
 /// Render a sparkline as text using Unicode block characters.
-fn sparkline_text(data: &VecDeque<u64>, width: usize) -> String {
+fn sparkline_text(data: &[f64], width: usize) -> String {
     if data.is_empty() {
         return String::new();
     }
     let bars = [' ', '\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}', '\u{2588}'];
-    let recent: Vec<u64> = data
-        .iter()
-        .rev()
-        .take(width)
-        .copied()
-        .collect::<Vec<_>>()
-        .into_iter()
-        .rev()
-        .collect();
-    let max = recent.iter().copied().max().unwrap_or(1);
-    let min = recent.iter().copied().min().unwrap_or(0);
-    let range = (max - min).max(1) as f64;
+    let recent = &data[data.len().saturating_sub(width)..];
+    let max = recent.iter().copied().fold(f64::MIN, f64::max);
+    let min = recent.iter().copied().fold(f64::MAX, f64::min);
+    let range = (max - min).max(f64::EPSILON);
 
     recent
         .iter()
         .map(|&v| {
-            let idx = (((v - min) as f64 / range) * 8.0) as usize;
+            let idx = (((v - min) / range) * 8.0) as usize;
             bars[idx.min(8)]
         })
         .collect()
@@ -0,0 +1,251 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::action::Action;
+use crate::game::events::{EventCategory, EventSeverity, GameEvent};
+use crate::game::state::GameState;
+use crate::theme;
+
+const PAGE_SIZE: usize = 10;
+
+/// Full-screen scrollable view over the whole event log, with filtering
+/// by severity and category plus a free-text search.
+pub struct EventLogView {
+    /// Number of entries scrolled up from the newest one.
+    scroll: usize,
+    severity_filter: Option<EventSeverity>,
+    category_filter: Option<EventCategory>,
+    search: String,
+    searching: bool,
+}
+
+impl EventLogView {
+    pub fn new() -> Self {
+        Self {
+            scroll: 0,
+            severity_filter: None,
+            category_filter: None,
+            search: String::new(),
+            searching: false,
+        }
+    }
+
+    fn matches(&self, event: &GameEvent) -> bool {
+        if let Some(severity) = self.severity_filter {
+            if event.kind.severity_color() != severity {
+                return false;
+            }
+        }
+        if let Some(category) = self.category_filter {
+            if event.kind.category() != category {
+                return false;
+            }
+        }
+        if !self.search.is_empty() {
+            let needle = self.search.to_lowercase();
+            return event.kind.description().to_lowercase().contains(&needle)
+                || event.kind.category().label().contains(&needle);
+        }
+        true
+    }
+
+    fn filtered<'a>(&self, state: &'a GameState) -> Vec<&'a GameEvent> {
+        state.event_log.iter().filter(|e| self.matches(e)).collect()
+    }
+
+    pub fn handle_key_with_state(
+        &mut self,
+        key: KeyEvent,
+        state: &GameState,
+    ) -> Result<Option<Action>> {
+        if self.searching {
+            match key.code {
+                KeyCode::Char(c) => self.search.push(c),
+                KeyCode::Backspace => {
+                    self.search.pop();
+                }
+                KeyCode::Enter => self.searching = false,
+                KeyCode::Esc => {
+                    self.searching = false;
+                    self.search.clear();
+                }
+                _ => {}
+            }
+            self.scroll = 0;
+            return Ok(Some(Action::None));
+        }
+
+        let max_scroll = self.filtered(state).len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('l') => return Ok(Some(Action::ToggleEventLog)),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.scroll = (self.scroll + 1).min(max_scroll);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.scroll = self.scroll.saturating_sub(1);
+            }
+            KeyCode::PageUp => {
+                self.scroll = (self.scroll + PAGE_SIZE).min(max_scroll);
+            }
+            KeyCode::PageDown => {
+                self.scroll = self.scroll.saturating_sub(PAGE_SIZE);
+            }
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = max_scroll,
+            KeyCode::End | KeyCode::Char('G') => self.scroll = 0,
+            KeyCode::Char('f') => {
+                self.severity_filter = cycle(self.severity_filter, &EventSeverity::ALL);
+                self.scroll = 0;
+            }
+            KeyCode::Char('c') => {
+                self.category_filter = cycle(self.category_filter, &EventCategory::ALL);
+                self.scroll = 0;
+            }
+            KeyCode::Char('/') => {
+                self.searching = true;
+                self.search.clear();
+            }
+            KeyCode::Char('x') => {
+                self.severity_filter = None;
+                self.category_filter = None;
+                self.search.clear();
+                self.scroll = 0;
+            }
+            _ => {}
+        }
+        Ok(Some(Action::None))
+    }
+
+    pub fn draw_with_state(
+        &self,
+        frame: &mut Frame<'_>,
+        area: Rect,
+        state: &GameState,
+    ) -> Result<()> {
+        let width = area.width.saturating_sub(8).max(20).min(area.width);
+        let height = area.height.saturating_sub(6).max(8).min(area.height);
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+
+        let block = Block::default()
+            .title(" EVENT LOG ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(theme::border_focused());
+
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let events = self.filtered(state);
+
+        let mut lines: Vec<Line> = Vec::new();
+        let search_label = if self.searching {
+            format!("/{}_", self.search)
+        } else if self.search.is_empty() {
+            String::from("--")
+        } else {
+            format!("/{}", self.search)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(" Severity: ", theme::text_dim()),
+            Span::styled(
+                self.severity_filter.map(|s| s.label()).unwrap_or("all"),
+                theme::text_value(),
+            ),
+            Span::styled("  Kind: ", theme::text_dim()),
+            Span::styled(
+                self.category_filter.map(|c| c.label()).unwrap_or("all"),
+                theme::text_value(),
+            ),
+            Span::styled("  Search: ", theme::text_dim()),
+            Span::styled(search_label, theme::text_value()),
+            Span::styled(
+                format!("  ({}/{})", events.len(), state.event_log.len()),
+                theme::text_dim(),
+            ),
+        ]));
+        lines.push(Line::from(""));
+
+        // Header and footer take two lines each
+        let list_height = (inner.height as usize).saturating_sub(4);
+        let end = events.len().saturating_sub(self.scroll);
+        let start = end.saturating_sub(list_height);
+
+        if events.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "  No matching events",
+                theme::text_dim(),
+            )));
+        }
+
+        for event in &events[start..end] {
+            let timestamp = event
+                .timestamp
+                .with_timezone(&chrono::Local)
+                .format("%m-%d %H:%M:%S");
+            lines.push(Line::from(vec![
+                Span::styled(format!(" [{}] ", timestamp), theme::text_dim()),
+                Span::styled(
+                    format!("{:<5} ", event.kind.severity_color().label()),
+                    severity_style(event.kind.severity_color()),
+                ),
+                Span::styled(
+                    event.kind.description(),
+                    severity_style(event.kind.severity_color()),
+                ),
+            ]));
+        }
+
+        while lines.len() < (inner.height as usize).saturating_sub(1) {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(vec![
+            Span::styled(" [↑/↓]", theme::text_value()),
+            Span::styled("Scroll ", theme::text_dim()),
+            Span::styled("[f]", theme::text_value()),
+            Span::styled("Severity ", theme::text_dim()),
+            Span::styled("[c]", theme::text_value()),
+            Span::styled("Kind ", theme::text_dim()),
+            Span::styled("[/]", theme::text_value()),
+            Span::styled("Search ", theme::text_dim()),
+            Span::styled("[x]", theme::text_value()),
+            Span::styled("Clear ", theme::text_dim()),
+            Span::styled("[Esc]", theme::text_value()),
+            Span::styled("Close", theme::text_dim()),
+        ]));
+
+        frame.render_widget(Paragraph::new(lines), inner);
+        Ok(())
+    }
+}
+
+pub fn severity_style(severity: EventSeverity) -> Style {
+    match severity {
+        EventSeverity::Info => theme::text_value(),
+        EventSeverity::Good => Style::default().fg(theme::FG_PRIMARY),
+        EventSeverity::Warning => Style::default().fg(theme::ACCENT_YELLOW),
+        EventSeverity::Error => Style::default().fg(theme::ACCENT_RED),
+    }
+}
+
+/// Step a filter through `None -> values[0] -> ... -> values[n-1] -> None`.
+fn cycle<T: Copy + PartialEq>(current: Option<T>, values: &[T]) -> Option<T> {
+    match current {
+        None => values.first().copied(),
+        Some(v) => {
+            let idx = values.iter().position(|x| *x == v).unwrap_or(0);
+            values.get(idx + 1).copied()
+        }
+    }
+}
@@ -45,7 +45,7 @@ impl Header {
             theme::text_value()
         };
 
-        let line = Line::from(vec![
+        let mut line = Line::from(vec![
             Span::styled(" IDLE TERMINAL", theme::title()),
             Span::styled(" | ", theme::text_dim()),
             Span::styled("Tick:", theme::text_dim()),
@@ -57,6 +57,27 @@ impl Header {
             Span::styled(" | ", theme::text_dim()),
             Span::styled(clock, theme::text_value()),
         ]);
+        if state.equity > 0.0 {
+            line.spans.extend([
+                Span::styled(" | ", theme::text_dim()),
+                Span::styled("EQ:", theme::text_dim()),
+                Span::styled(
+                    format!("{:.0}", state.equity),
+                    Style::default().fg(theme::ACCENT_YELLOW),
+                ),
+                Span::styled(
+                    format!(" (x{:.2})", progression::equity_multiplier(state.equity)),
+                    theme::text_dim(),
+                ),
+            ]);
+        }
+        if let Some(title) = state.title() {
+            line.spans.push(Span::styled(" | ", theme::text_dim()));
+            line.spans.push(Span::styled(
+                format!("~{title}~"),
+                Style::default().fg(theme::ACCENT_MAGENTA),
+            ));
+        }
 
         let content = Paragraph::new(vec![line]);
         frame.render_widget(content, inner);
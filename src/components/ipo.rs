@@ -0,0 +1,81 @@
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::game::progression::{self, EquityPerk};
+use crate::game::state::GameState;
+use crate::theme;
+
+/// Confirmation popup for an IPO: what it wipes, what it pays and which
+/// perks the new Equity unlocks.
+pub fn draw_confirm(frame: &mut Frame<'_>, area: Rect, state: &GameState) {
+    let earned = progression::ipo_equity(state.resources.reputation);
+    let equity = state.equity + earned;
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Resets the run, ALL reputation and prestiges.",
+            Style::default().fg(theme::ACCENT_YELLOW),
+        )),
+        Line::from(vec![
+            Span::styled("  Equity earned: +", theme::text_dim()),
+            Span::styled(
+                format!("{earned:.0}"),
+                Style::default().fg(theme::ACCENT_YELLOW),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  Equity multiplier: x", theme::text_dim()),
+            Span::styled(
+                format!("{:.2}", progression::equity_multiplier(equity)),
+                theme::text_value(),
+            ),
+        ]),
+    ];
+    for perk in EquityPerk::ALL {
+        let (marker, style) = if state.has_perk(perk) {
+            ("  ✓ ", theme::text_dim())
+        } else if equity >= perk.equity_required() {
+            ("  + ", Style::default().fg(theme::ACCENT_MAGENTA))
+        } else {
+            ("    ", theme::text_dim())
+        };
+        lines.push(Line::from(vec![
+            Span::styled(marker, style),
+            Span::styled(
+                format!("{} ({:.0} EQ): {}", perk.name(), perk.equity_required(), perk.description()),
+                style,
+            ),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("  [y] ", theme::text_value()),
+        Span::styled("Confirm  ", theme::text_dim()),
+        Span::styled("[n] ", theme::text_value()),
+        Span::styled("Cancel", theme::text_dim()),
+    ]));
+
+    let width = 60u16.min(area.width.saturating_sub(4));
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup_area = Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(Style::default().fg(theme::ACCENT_YELLOW))
+            .title(" $ IPO $ "),
+    );
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(popup, popup_area);
+}
@@ -7,8 +7,8 @@ use ratatui::{
     Frame,
 };
 
+use crate::components::event_log::severity_style;
 use crate::components::Component;
-use crate::game::events::EventSeverity;
 use crate::game::state::GameState;
 use crate::theme;
 
@@ -27,7 +27,7 @@ impl LogStream {
         state: &GameState,
     ) -> Result<()> {
         let block = Block::default()
-            .title(" LOG ")
+            .title(" LOG [l] ")
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(theme::FG_DIM));
@@ -59,23 +59,16 @@ impl LogStream {
                 spans.push(Span::styled(" │ ", theme::text_dim()));
             }
 
-            // Timestamp from tick (HH:MM:SS approximation)
-            let secs = event.tick / 4;
-            let h = (secs / 3600) % 24;
-            let m = (secs / 60) % 60;
-            let s = secs % 60;
-
-            let severity_style = match event.kind.severity_color() {
-                EventSeverity::Good => Style::default().fg(theme::FG_PRIMARY),
-                EventSeverity::Warning => Style::default().fg(theme::ACCENT_YELLOW),
-                EventSeverity::Error => Style::default().fg(theme::ACCENT_RED),
-            };
+            let timestamp = event
+                .timestamp
+                .with_timezone(&chrono::Local)
+                .format("%H:%M:%S");
 
+            spans.push(Span::styled(format!(" [{}] ", timestamp), theme::text_dim()));
             spans.push(Span::styled(
-                format!(" [{:02}:{:02}:{:02}] ", h, m, s),
-                theme::text_dim(),
+                event.kind.description(),
+                severity_style(event.kind.severity_color()),
             ));
-            spans.push(Span::styled(event.kind.description(), severity_style));
         }
 
         let line = Line::from(spans);
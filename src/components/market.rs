@@ -0,0 +1,337 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Block, BorderType, Borders, Chart, Clear, Dataset, GraphType, Paragraph},
+    Frame,
+};
+
+use crate::action::Action;
+use crate::game::market::{Order, TradeAsset, TradeSide};
+use crate::game::resources::format_si;
+use crate::game::state::GameState;
+use crate::theme;
+
+/// Share of the balance each trade uses.
+const AMOUNTS: [f64; 4] = [0.10, 0.25, 0.50, 1.00];
+/// How far from the market price limit orders are placed.
+const MIN_OFFSET: f64 = 0.05;
+const MAX_OFFSET: f64 = 0.50;
+
+/// Overlay for the crypto exchange: price chart, trades and limit orders.
+pub struct MarketView {
+    asset: usize,
+    amount: usize,
+    offset: f64,
+}
+
+impl MarketView {
+    pub fn new() -> Self {
+        Self {
+            asset: 0,
+            amount: 1,
+            offset: 0.10,
+        }
+    }
+
+    fn asset(&self) -> TradeAsset {
+        TradeAsset::ALL[self.asset]
+    }
+
+    /// Crypto moved by a trade of the selected size at `price`.
+    fn crypto_for(&self, side: TradeSide, state: &GameState, price: f64) -> f64 {
+        let share = AMOUNTS[self.amount];
+        match side {
+            TradeSide::Buy if price > 0.0 => {
+                self.asset().amount(&state.resources) * share / price
+            }
+            TradeSide::Buy => 0.0,
+            TradeSide::Sell => state.resources.crypto * share,
+        }
+    }
+
+    fn limit_price(&self, side: TradeSide, state: &GameState) -> f64 {
+        let mid = state.market.quote(side, self.asset());
+        match side {
+            TradeSide::Buy => mid * (1.0 - self.offset),
+            TradeSide::Sell => mid * (1.0 + self.offset),
+        }
+    }
+
+    fn limit_order(&self, side: TradeSide, state: &GameState) -> Order {
+        let limit = self.limit_price(side, state);
+        Order::new(side, self.asset(), self.crypto_for(side, state, limit), limit)
+    }
+
+    pub fn handle_key_with_state(
+        &mut self,
+        key: KeyEvent,
+        state: &GameState,
+    ) -> Result<Option<Action>> {
+        let asset = self.asset();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('m') => return Ok(Some(Action::ToggleMarket)),
+            KeyCode::Left | KeyCode::Char('h') => self.asset = self.asset.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') if self.asset + 1 < TradeAsset::ALL.len() => {
+                self.asset += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.amount + 1 < AMOUNTS.len() => {
+                self.amount += 1;
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.amount = self.amount.saturating_sub(1),
+            KeyCode::Char('[') => self.offset = (self.offset - 0.05).max(MIN_OFFSET),
+            KeyCode::Char(']') => self.offset = (self.offset + 0.05).min(MAX_OFFSET),
+            KeyCode::Char('b') => {
+                let price = state.market.quote(TradeSide::Buy, asset);
+                return Ok(Some(Action::Trade {
+                    side: TradeSide::Buy,
+                    asset,
+                    crypto: self.crypto_for(TradeSide::Buy, state, price),
+                }));
+            }
+            KeyCode::Char('s') => {
+                return Ok(Some(Action::Trade {
+                    side: TradeSide::Sell,
+                    asset,
+                    crypto: self.crypto_for(TradeSide::Sell, state, 0.0),
+                }));
+            }
+            KeyCode::Char('B') => {
+                return Ok(Some(Action::PlaceOrder(
+                    self.limit_order(TradeSide::Buy, state),
+                )));
+            }
+            KeyCode::Char('S') => {
+                return Ok(Some(Action::PlaceOrder(
+                    self.limit_order(TradeSide::Sell, state),
+                )));
+            }
+            KeyCode::Char('x') => {
+                if let Some(order) = state.market.orders.last() {
+                    return Ok(Some(Action::CancelOrder(order.id)));
+                }
+            }
+            _ => {}
+        }
+        Ok(Some(Action::None))
+    }
+
+    pub fn draw_with_state(
+        &self,
+        frame: &mut Frame<'_>,
+        area: Rect,
+        state: &GameState,
+    ) -> Result<()> {
+        let width = 90u16.min(area.width);
+        let height = area.height.saturating_sub(2).max(12).min(area.height);
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+
+        let block = Block::default()
+            .title(" CRYPTO MARKET ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(theme::border_focused());
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(6), Constraint::Length(16)])
+            .split(inner);
+
+        draw_price_chart(frame, chunks[0], state);
+        self.draw_trading(frame, chunks[1], state);
+        Ok(())
+    }
+
+    fn draw_trading(&self, frame: &mut Frame<'_>, area: Rect, state: &GameState) {
+        let market = &state.market;
+        let change = market.change();
+        let (arrow, change_color) = if change >= 0.0 {
+            ("▲", theme::FG_PRIMARY)
+        } else {
+            ("▼", theme::ACCENT_RED)
+        };
+
+        let mut lines = vec![Line::from(vec![
+            Span::styled(" BTC ", theme::title()),
+            Span::styled(
+                format!("{} CPU  ", format_si(market.price)),
+                theme::text_value(),
+            ),
+            Span::styled(
+                format!("{arrow} {:+.1}% (4m)  ", change * 100.0),
+                Style::default().fg(change_color),
+            ),
+            Span::styled(
+                format!("You hold {} BTC", format_si(state.resources.crypto)),
+                theme::text_dim(),
+            ),
+        ])];
+
+        for (i, asset) in TradeAsset::ALL.iter().enumerate() {
+            let mut style = theme::text_dim();
+            if i == self.asset {
+                style = theme::text_value().add_modifier(Modifier::BOLD);
+            }
+            let marker = if i == self.asset { "▸" } else { " " };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {marker} {:<4}", asset.label()), style),
+                Span::styled(
+                    format!(
+                        "buy {:>8}  sell {:>8}  ",
+                        format_si(market.quote(TradeSide::Buy, *asset)),
+                        format_si(market.quote(TradeSide::Sell, *asset)),
+                    ),
+                    style,
+                ),
+                Span::styled(
+                    format!("have {}", format_si(asset.amount(&state.resources))),
+                    theme::text_dim(),
+                ),
+            ]));
+        }
+
+        let asset = self.asset();
+        let buy_price = market.quote(TradeSide::Buy, asset);
+        let buy = self.crypto_for(TradeSide::Buy, state, buy_price);
+        let sell = self.crypto_for(TradeSide::Sell, state, 0.0);
+        let sell_price = market.quote(TradeSide::Sell, asset);
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" Size {:.0}%: ", AMOUNTS[self.amount] * 100.0),
+                theme::text_dim(),
+            ),
+            Span::styled(
+                format!(
+                    "buy {} BTC for {} {}  |  sell {} BTC for {} {}",
+                    format_si(buy),
+                    format_si(buy * buy_price),
+                    asset.label(),
+                    format_si(sell),
+                    format_si(sell * sell_price),
+                    asset.label(),
+                ),
+                theme::text_value(),
+            ),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" Limit offset {:.0}%: ", self.offset * 100.0),
+                theme::text_dim(),
+            ),
+            Span::styled(
+                format!(
+                    "buy at ≤{}  sell at ≥{} {}",
+                    format_si(self.limit_price(TradeSide::Buy, state)),
+                    format_si(self.limit_price(TradeSide::Sell, state)),
+                    asset.label(),
+                ),
+                theme::text_value(),
+            ),
+        ]));
+
+        lines.push(Line::from(Span::styled(" Open orders", theme::title())));
+        if market.orders.is_empty() {
+            lines.push(Line::from(Span::styled("   none", theme::text_dim())));
+        }
+        for order in &market.orders {
+            let (label, color) = match order.side {
+                TradeSide::Buy => ("BUY ", theme::FG_PRIMARY),
+                TradeSide::Sell => ("SELL", theme::ACCENT_YELLOW),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("   #{:<3}", order.id), theme::text_dim()),
+                Span::styled(label, Style::default().fg(color)),
+                Span::styled(
+                    format!(
+                        " {} BTC @ {} {}",
+                        format_si(order.crypto),
+                        format_si(order.limit),
+                        order.asset.label()
+                    ),
+                    theme::text_value(),
+                ),
+            ]));
+        }
+
+        lines.push(Line::from(vec![
+            Span::styled(" [←/→]", theme::text_value()),
+            Span::styled("Asset ", theme::text_dim()),
+            Span::styled("[↑/↓]", theme::text_value()),
+            Span::styled("Size ", theme::text_dim()),
+            Span::styled("[b/s]", theme::text_value()),
+            Span::styled("Buy/Sell ", theme::text_dim()),
+            Span::styled("[B/S]", theme::text_value()),
+            Span::styled("Limit ", theme::text_dim()),
+            Span::styled("[ [/] ]", theme::text_value()),
+            Span::styled("Offset ", theme::text_dim()),
+            Span::styled("[x]", theme::text_value()),
+            Span::styled("Cancel ", theme::text_dim()),
+            Span::styled("[Esc]", theme::text_value()),
+            Span::styled("Close", theme::text_dim()),
+        ]));
+
+        let block = Block::default()
+            .borders(Borders::TOP)
+            .border_style(theme::border_unfocused());
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+fn draw_price_chart(frame: &mut Frame<'_>, area: Rect, state: &GameState) {
+    let history = &state.market.history;
+    if history.len() < 2 {
+        let msg = Paragraph::new("  Waiting for price data...").style(theme::text_dim());
+        frame.render_widget(msg, area);
+        return;
+    }
+
+    let points: Vec<(f64, f64)> = history
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i as f64, *p))
+        .collect();
+    let low = history.iter().copied().fold(f64::MAX, f64::min) * 0.95;
+    let high = history.iter().copied().fold(f64::MIN, f64::max) * 1.05;
+    let x_max = (points.len() - 1) as f64;
+
+    let dataset = Dataset::default()
+        .name("BTC/CPU")
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(theme::ACCENT_MAGENTA))
+        .data(&points);
+
+    let chart = Chart::new(vec![dataset])
+        .x_axis(
+            Axis::default()
+                .style(theme::text_dim())
+                .bounds([0.0, x_max])
+                .labels(vec![
+                    Span::styled(format!("-{}s", points.len()), theme::text_dim()),
+                    Span::styled("now", theme::text_dim()),
+                ]),
+        )
+        .y_axis(
+            Axis::default()
+                .title(Span::styled("CPU per BTC", theme::text_dim()))
+                .style(theme::text_dim())
+                .bounds([low, high])
+                .labels(vec![
+                    Span::styled(format_si(low), theme::text_dim()),
+                    Span::styled(format_si((low + high) / 2.0), theme::text_dim()),
+                    Span::styled(format_si(high), theme::text_dim()),
+                ]),
+        );
+    frame.render_widget(chart, area);
+}
@@ -1,26 +1,24 @@
+pub mod achievements;
+pub mod challenges;
 pub mod dashboard;
+pub mod event_log;
 pub mod header;
+pub mod ipo;
 pub mod log_stream;
+pub mod market;
 pub mod network_map;
+pub mod notifications;
+pub mod prestige;
+pub mod research_tree;
 pub mod server_rack;
+pub mod stats_view;
 pub mod status_bar;
 pub mod task_terminal;
 
 use color_eyre::eyre::Result;
-use crossterm::event::KeyEvent;
 use ratatui::Frame;
 use ratatui::layout::Rect;
 
-use crate::action::Action;
-
 pub trait Component {
-    fn handle_key_event(&mut self, _key: KeyEvent) -> Result<Option<Action>> {
-        Ok(None)
-    }
-
-    fn update(&mut self, _action: &Action) -> Result<Option<Action>> {
-        Ok(None)
-    }
-
     fn draw(&self, frame: &mut Frame<'_>, area: Rect, focused: bool) -> Result<()>;
 }
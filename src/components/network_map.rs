@@ -9,6 +9,7 @@ use ratatui::{
 use crate::components::Component;
 use crate::game::buildings::all_building_defs;
 use crate::game::network_info::LocalNetworkInfo;
+use crate::game::resources::format_si;
 use crate::game::state::GameState;
 use crate::theme;
 
@@ -127,6 +128,7 @@ impl NetworkMap {
             // Group by resource type for visual clarity
             let compute_nodes: Vec<_> = owned
                 .iter()
+                .filter(|d| !d.is_facility())
                 .filter(|d| matches!(d.resource_type, crate::game::buildings::ResourceType::Compute))
                 .collect();
             let network_nodes: Vec<_> = owned
@@ -137,6 +139,7 @@ impl NetworkMap {
                 .iter()
                 .filter(|d| matches!(d.resource_type, crate::game::buildings::ResourceType::Storage))
                 .collect();
+            let facility_nodes: Vec<_> = owned.iter().filter(|d| d.is_facility()).collect();
 
             let max_height = inner.height as usize;
 
@@ -145,6 +148,7 @@ impl NetworkMap {
                 ("CPU", &compute_nodes),
                 ("NET", &network_nodes),
                 ("SSD", &storage_nodes),
+                ("PWR", &facility_nodes),
             ];
 
             for (i, (label, nodes)) in groups.iter().enumerate() {
@@ -174,25 +178,42 @@ impl NetworkMap {
 
                     lines.push(Line::from(vec![
                         Span::styled(format!("   {pipe}   {sub_connector}─ "), theme::text_dim()),
-                        Span::styled(node.name, theme::text_dim()),
+                        Span::styled(node.name.as_str(), theme::text_dim()),
                         Span::styled(format!(" x{count}"), theme::text_value()),
                     ]));
                 }
             }
 
-            // Traffic spike indicator
-            if state.traffic_spike_remaining > 0 {
-                if lines.len() < max_height {
-                    lines.push(Line::from(""));
-                    lines.push(Line::from(Span::styled(
-                        format!(
-                            "  ⚡ TRAFFIC SPIKE x{:.1} ({}s)",
-                            state.traffic_spike_multiplier,
-                            state.traffic_spike_remaining / 4
-                        ),
-                        ratatui::style::Style::default().fg(theme::ACCENT_YELLOW),
-                    )));
+            // Power and cooling headroom
+            let facility = &state.production_breakdown.facility;
+            let meters = [
+                ("pwr", facility.demand.power, facility.capacity.power),
+                ("cool", facility.demand.cooling, facility.capacity.cooling),
+            ];
+            for (label, demand, capacity) in meters {
+                if lines.len() >= max_height {
+                    break;
                 }
+                lines.push(capacity_line(label, demand, capacity));
+            }
+            if facility.is_throttled() && lines.len() < max_height {
+                lines.push(Line::from(Span::styled(
+                    format!("  ! THROTTLED to {:.0}%", facility.throttle() * 100.0),
+                    ratatui::style::Style::default().fg(theme::ACCENT_RED),
+                )));
+            }
+
+            // Traffic spike indicator
+            if state.traffic_spike_remaining > 0 && lines.len() < max_height {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "  ⚡ TRAFFIC SPIKE x{:.1} ({}s)",
+                        state.traffic_spike_multiplier,
+                        state.traffic_spike_remaining / 4
+                    ),
+                    ratatui::style::Style::default().fg(theme::ACCENT_YELLOW),
+                )));
             }
         }
 
@@ -202,6 +223,31 @@ impl NetworkMap {
     }
 }
 
+/// A "used/capacity kW" bar, red once demand exceeds capacity.
+fn capacity_line<'a>(label: &str, demand: f64, capacity: f64) -> Line<'a> {
+    const WIDTH: usize = 10;
+    let ratio = if capacity > 0.0 { demand / capacity } else { 1.0 };
+    let filled = ((ratio.min(1.0) * WIDTH as f64).round() as usize).min(WIDTH);
+    let color = if ratio > 1.0 {
+        theme::ACCENT_RED
+    } else if ratio > 0.8 {
+        theme::ACCENT_YELLOW
+    } else {
+        theme::FG_PRIMARY
+    };
+    Line::from(vec![
+        Span::styled(format!("  {label:<5}"), theme::text_dim()),
+        Span::styled(
+            format!("{}{}", "█".repeat(filled), "░".repeat(WIDTH - filled)),
+            ratatui::style::Style::default().fg(color),
+        ),
+        Span::styled(
+            format!(" {}/{} kW", format_si(demand), format_si(capacity)),
+            theme::text_value(),
+        ),
+    ])
+}
+
 fn whoami() -> String {
     std::env::var("USER")
         .or_else(|_| std::env::var("USERNAME"))
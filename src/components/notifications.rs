@@ -0,0 +1,137 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::action::Action;
+use crate::game::notifications::{NotificationKind, NotificationManager};
+use crate::theme;
+
+fn kind_color(kind: NotificationKind) -> Color {
+    match kind {
+        NotificationKind::Info => theme::FG_PRIMARY,
+        NotificationKind::Warning => theme::ACCENT_YELLOW,
+        NotificationKind::Welcome => theme::ACCENT_CYAN,
+        NotificationKind::Achievement | NotificationKind::Prestige => theme::ACCENT_MAGENTA,
+    }
+}
+
+/// Draw the visible toasts stacked down from the top of `area`.
+pub fn draw_toasts(frame: &mut Frame<'_>, area: Rect, manager: &NotificationManager) {
+    let visible = manager.visible();
+    let queued = manager.queued();
+
+    for (i, notification) in visible.iter().enumerate() {
+        let y = area.y + 4 + i as u16 * 3;
+        if y + 3 > area.y + area.height {
+            break;
+        }
+        // Display width, not bytes: pack and script names may be non-ASCII
+        let text = Line::from(format!(" {}", notification.message));
+        let width = u16::try_from(text.width() + 3)
+            .unwrap_or(u16::MAX)
+            .min(area.width.saturating_sub(4));
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y,
+            width,
+            height: 3,
+        };
+
+        let color = kind_color(notification.kind);
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(Style::default().fg(color))
+            .title(format!(" {} ", notification.kind.title()));
+        if queued > 0 && i == visible.len() - 1 {
+            block = block.title_bottom(format!(" +{queued} more "));
+        }
+
+        let popup = Paragraph::new(text)
+            .style(Style::default().fg(color))
+            .block(block);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(popup, popup_area);
+    }
+}
+
+/// Overlay listing past notifications, newest first.
+pub struct NotificationHistoryView {
+    scroll: usize,
+}
+
+impl NotificationHistoryView {
+    pub fn new() -> Self {
+        Self { scroll: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('n') => return Ok(Some(Action::ToggleNotifications)),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll += 1,
+            _ => {}
+        }
+        Ok(Some(Action::None))
+    }
+
+    pub fn draw(&self, frame: &mut Frame<'_>, area: Rect, manager: &NotificationManager) {
+        let width = 72u16.min(area.width);
+        let height = area.height.saturating_sub(4).max(8).min(area.height);
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+
+        let block = Block::default()
+            .title(" NOTIFICATIONS ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(theme::border_focused());
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let mut lines: Vec<Line> = manager
+            .history()
+            .map(|n| {
+                let time = n.created.with_timezone(&chrono::Local).format("%H:%M:%S");
+                Line::from(vec![
+                    Span::styled(format!(" {time} "), theme::text_dim()),
+                    Span::styled(
+                        format!("{:<13}", n.kind.title()),
+                        Style::default().fg(kind_color(n.kind)),
+                    ),
+                    Span::styled(n.message.clone(), theme::text_value()),
+                ])
+            })
+            .collect();
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled(
+                " No notifications yet",
+                theme::text_dim(),
+            )));
+        }
+
+        let visible = inner.height.saturating_sub(1) as usize;
+        let max_scroll = lines.len().saturating_sub(visible);
+        let scroll = self.scroll.min(max_scroll);
+        let mut shown: Vec<Line> = lines.into_iter().skip(scroll).take(visible).collect();
+        shown.push(Line::from(vec![
+            Span::styled(" [↑/↓]", theme::text_value()),
+            Span::styled("Scroll ", theme::text_dim()),
+            Span::styled("[Esc]", theme::text_value()),
+            Span::styled("Close", theme::text_dim()),
+        ]));
+
+        frame.render_widget(Paragraph::new(shown), inner);
+    }
+}
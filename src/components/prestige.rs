@@ -0,0 +1,82 @@
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::game::progression;
+use crate::game::resources::format_si;
+use crate::game::state::GameState;
+use crate::theme;
+
+/// Confirmation popup for a prestige: the reputation it pays, the new
+/// multiplier and how far off the next reputation point is.
+pub fn draw_confirm(frame: &mut Frame<'_>, area: Rect, state: &GameState) {
+    let earned = progression::prestige_reputation(state.run_compute);
+    let next_point = progression::next_reputation_at(state.run_compute);
+    let multiplier = progression::reputation_multiplier(state.resources.reputation + earned);
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "  This will reset ALL resources and buildings.",
+            Style::default().fg(theme::ACCENT_YELLOW),
+        )),
+        Line::from(vec![
+            Span::styled("  Reputation earned: +", theme::text_dim()),
+            Span::styled(
+                format!("{earned:.0}"),
+                Style::default().fg(theme::ACCENT_MAGENTA),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  New multiplier: x", theme::text_dim()),
+            Span::styled(format!("{multiplier:.2}"), theme::text_value()),
+        ]),
+        Line::from(vec![
+            Span::styled("  Next reputation point at ", theme::text_dim()),
+            Span::styled(
+                format!(
+                    "{} CPU ({} now)",
+                    format_si(next_point),
+                    format_si(state.run_compute)
+                ),
+                theme::text_value(),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  Reputation / min: ", theme::text_dim()),
+            Span::styled(
+                format!("{:.2}", state.reputation_per_minute()),
+                theme::text_value(),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  [y] ", theme::text_value()),
+            Span::styled("Confirm  ", theme::text_dim()),
+            Span::styled("[n] ", theme::text_value()),
+            Span::styled("Cancel", theme::text_dim()),
+        ]),
+    ];
+
+    let width = 56u16.min(area.width.saturating_sub(4));
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup_area = Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(Style::default().fg(theme::ACCENT_MAGENTA))
+            .title(" * PRESTIGE RESET * "),
+    );
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(popup, popup_area);
+}
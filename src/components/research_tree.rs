@@ -0,0 +1,298 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::action::Action;
+use crate::game::resources::format_cost;
+use crate::game::state::GameState;
+use crate::game::upgrades::{dependents, research_depths, ResearchStatus, UpgradeId};
+use crate::theme;
+
+/// Overlay drawing the whole upgrade DAG, one column per depth.
+pub struct ResearchTreeView {
+    column: usize,
+    row: usize,
+}
+
+impl ResearchTreeView {
+    pub fn new() -> Self {
+        Self { column: 0, row: 0 }
+    }
+
+    /// Upgrade ids grouped by depth, each column sorted by id.
+    fn columns(state: &GameState) -> Vec<Vec<UpgradeId>> {
+        let depths = research_depths(&state.upgrades);
+        let max_depth = depths.values().copied().max().unwrap_or(0);
+        let mut columns = vec![Vec::new(); max_depth + 1];
+        for upgrade in &state.upgrades {
+            columns[depths[&upgrade.id]].push(upgrade.id);
+        }
+        for column in &mut columns {
+            column.sort();
+        }
+        columns
+    }
+
+    fn selected(&self, columns: &[Vec<UpgradeId>]) -> Option<UpgradeId> {
+        let column = columns.get(self.column)?;
+        column.get(self.row.min(column.len().saturating_sub(1))).copied()
+    }
+
+    pub fn handle_key_with_state(
+        &mut self,
+        key: KeyEvent,
+        state: &GameState,
+    ) -> Result<Option<Action>> {
+        let columns = Self::columns(state);
+        let column_len = |c: usize| columns.get(c).map(|c| c.len()).unwrap_or(0);
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('t') => return Ok(Some(Action::ToggleResearchTree)),
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.column = self.column.saturating_sub(1);
+                self.row = self.row.min(column_len(self.column).saturating_sub(1));
+            }
+            KeyCode::Right | KeyCode::Char('l') if self.column + 1 < columns.len() => {
+                self.column += 1;
+                self.row = self.row.min(column_len(self.column).saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.row = self.row.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if self.row + 1 < column_len(self.column) => {
+                self.row += 1;
+            }
+            KeyCode::Enter => {
+                if let Some(id) = self.selected(&columns) {
+                    return Ok(Some(Action::PurchaseUpgrade(id)));
+                }
+            }
+            KeyCode::Char('x') => {
+                if let Some(id) = self.selected(&columns) {
+                    return Ok(Some(Action::CancelResearch(id)));
+                }
+            }
+            _ => {}
+        }
+        Ok(Some(Action::None))
+    }
+
+    pub fn draw_with_state(
+        &self,
+        frame: &mut Frame<'_>,
+        area: Rect,
+        state: &GameState,
+    ) -> Result<()> {
+        let width = 100u16.min(area.width);
+        let height = area.height.saturating_sub(2).max(12).min(area.height);
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+
+        let block = Block::default()
+            .title(" RESEARCH TREE ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(theme::border_focused());
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(4), Constraint::Length(9)])
+            .split(inner);
+
+        let columns = Self::columns(state);
+        let selected = self.selected(&columns);
+        self.draw_graph(frame, chunks[0], state, &columns, selected);
+        if let Some(id) = selected {
+            draw_details(frame, chunks[1], state, id);
+        }
+        Ok(())
+    }
+
+    fn draw_graph(
+        &self,
+        frame: &mut Frame<'_>,
+        area: Rect,
+        state: &GameState,
+        columns: &[Vec<UpgradeId>],
+        selected: Option<UpgradeId>,
+    ) {
+        let (prereqs, unlocks) = match selected {
+            Some(id) => (
+                state
+                    .upgrades
+                    .iter()
+                    .find(|u| u.id == id)
+                    .map(|u| u.prerequisites.clone())
+                    .unwrap_or_default(),
+                dependents(&state.upgrades, id),
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+
+        let constraints: Vec<Constraint> = columns
+            .iter()
+            .map(|_| Constraint::Ratio(1, columns.len() as u32))
+            .collect();
+        let areas = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(constraints)
+            .split(area);
+
+        for (depth, (ids, col_area)) in columns.iter().zip(areas.iter()).enumerate() {
+            let name_width = (col_area.width as usize).saturating_sub(5);
+            let mut lines = vec![Line::from(Span::styled(
+                format!(" Tier {}", depth + 1),
+                theme::title(),
+            ))];
+            for id in ids {
+                let upgrade = match state.upgrades.iter().find(|u| u.id == *id) {
+                    Some(u) => u,
+                    None => continue,
+                };
+                let (icon, mut style) = match state.research_status(*id) {
+                    Some(ResearchStatus::Purchased) => ("✓", Style::default().fg(theme::FG_PRIMARY)),
+                    Some(ResearchStatus::Available) => ("○", theme::text_value()),
+                    Some(ResearchStatus::Researching) => {
+                        ("…", Style::default().fg(theme::ACCENT_CYAN))
+                    }
+                    _ => ("✗", theme::text_dim()),
+                };
+                // Edges of the selected node
+                let link = if prereqs.contains(id) {
+                    style = style.fg(theme::ACCENT_CYAN);
+                    "◂"
+                } else if unlocks.contains(id) {
+                    style = style.fg(theme::ACCENT_YELLOW);
+                    "▸"
+                } else {
+                    " "
+                };
+                if Some(*id) == selected {
+                    style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+                }
+                let name: String = upgrade.name.chars().take(name_width).collect();
+                lines.push(Line::from(vec![
+                    Span::styled(link, style),
+                    Span::styled(format!("{icon} {name}"), style),
+                ]));
+            }
+            frame.render_widget(Paragraph::new(lines), *col_area);
+        }
+    }
+}
+
+fn draw_details(frame: &mut Frame<'_>, area: Rect, state: &GameState, id: UpgradeId) {
+    let upgrade = match state.upgrades.iter().find(|u| u.id == id) {
+        Some(u) => u,
+        None => return,
+    };
+    let name_of = |id: &UpgradeId| {
+        state
+            .upgrades
+            .iter()
+            .find(|u| u.id == *id)
+            .map(|u| u.name.clone())
+            .unwrap_or_default()
+    };
+
+    let (status, status_style) = match state.research_status(id) {
+        Some(ResearchStatus::Purchased) => {
+            ("Researched".to_string(), Style::default().fg(theme::FG_PRIMARY))
+        }
+        Some(ResearchStatus::Available) => ("Available".to_string(), theme::text_value()),
+        Some(ResearchStatus::Researching) => {
+            let progress = state
+                .research
+                .jobs
+                .iter()
+                .find(|j| j.id == id)
+                .map(|j| j.progress() * 100.0)
+                .unwrap_or(0.0);
+            (
+                format!("Researching {:.0}%", progress),
+                Style::default().fg(theme::ACCENT_CYAN),
+            )
+        }
+        _ => ("Locked".to_string(), Style::default().fg(theme::ACCENT_RED)),
+    };
+    let cost_style = if state.resources.can_afford(&upgrade.cost) {
+        Style::default().fg(theme::FG_PRIMARY)
+    } else {
+        Style::default().fg(theme::ACCENT_RED)
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(format!(" {} ", upgrade.name), theme::title()),
+            Span::styled(format!("[{status}]"), status_style),
+        ]),
+        Line::from(vec![
+            Span::styled(" Effect: ", theme::text_dim()),
+            Span::styled(upgrade.description.clone(), theme::text_value()),
+        ]),
+        Line::from(vec![
+            Span::styled(" Cost:   ", theme::text_dim()),
+            Span::styled(format_cost(&upgrade.cost), cost_style),
+        ]),
+    ];
+
+    if !upgrade.prerequisites.is_empty() {
+        let mut spans = vec![Span::styled(" Needs:  ", theme::text_dim())];
+        for prereq in &upgrade.prerequisites {
+            let done = state.research_status(*prereq) == Some(ResearchStatus::Purchased);
+            let style = if done {
+                Style::default().fg(theme::FG_PRIMARY)
+            } else {
+                Style::default().fg(theme::ACCENT_RED)
+            };
+            let mark = if done { "✓" } else { "✗" };
+            spans.push(Span::styled(format!("{mark} {}  ", name_of(prereq)), style));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let path = state.research_path(id);
+    if !path.is_empty() {
+        let names: Vec<String> = path.iter().map(name_of).collect();
+        lines.push(Line::from(vec![
+            Span::styled(" Unlock: ", theme::text_dim()),
+            Span::styled(names.join(" → "), Style::default().fg(theme::ACCENT_CYAN)),
+        ]));
+    }
+
+    let unlocks = dependents(&state.upgrades, id);
+    if !unlocks.is_empty() {
+        let names: Vec<String> = unlocks.iter().map(name_of).collect();
+        lines.push(Line::from(vec![
+            Span::styled(" Leads to: ", theme::text_dim()),
+            Span::styled(names.join(", "), Style::default().fg(theme::ACCENT_YELLOW)),
+        ]));
+    }
+
+    lines.push(Line::from(vec![
+        Span::styled(" [←↑↓→]", theme::text_value()),
+        Span::styled("Navigate ", theme::text_dim()),
+        Span::styled("[Enter]", theme::text_value()),
+        Span::styled("Research ", theme::text_dim()),
+        Span::styled("[x]", theme::text_value()),
+        Span::styled("Cancel ", theme::text_dim()),
+        Span::styled("[Esc]", theme::text_value()),
+        Span::styled("Close", theme::text_dim()),
+    ]));
+
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(theme::border_unfocused());
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
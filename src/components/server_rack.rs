@@ -10,14 +10,19 @@ use ratatui::{
 use crate::action::Action;
 use crate::components::Component;
 use crate::game::buildings::{all_building_defs, BuildingKind};
-use crate::game::resources::format_si;
+use crate::game::formulas;
+use crate::game::production::{best_value, Purchase, PurchaseAdvice};
+use crate::game::research::ResearchJob;
+use crate::game::resources::{format_cost, format_si};
 use crate::game::state::GameState;
+use crate::game::synergies::{all_synergies, Synergy, SynergyEffect};
 use crate::theme;
 
 #[derive(Clone, Copy, PartialEq)]
 enum View {
     Buildings,
     Upgrades,
+    Breakdown,
 }
 
 pub struct ServerRack {
@@ -56,6 +61,7 @@ impl ServerRack {
         let title = match self.view {
             View::Buildings => " SERVER RACK ",
             View::Upgrades => " UPGRADES ",
+            View::Breakdown => " PRODUCTION BREAKDOWN ",
         };
 
         let block = Block::default()
@@ -70,6 +76,7 @@ impl ServerRack {
         match self.view {
             View::Buildings => self.draw_buildings(frame, inner, focused, state),
             View::Upgrades => self.draw_upgrades(frame, inner, focused, state),
+            View::Breakdown => self.draw_breakdown(frame, inner, focused, state),
         }
     }
 
@@ -88,6 +95,8 @@ impl ServerRack {
         }
 
         let defs = all_building_defs();
+        let advice = state.all_purchase_advice();
+        let best = best_value(&advice);
         let visible_height = area.height as usize;
         let lines_per_building = 3;
         let max_visible = visible_height.saturating_sub(2) / lines_per_building;
@@ -115,15 +124,11 @@ impl ServerRack {
             let can_afford = state.resources.can_afford(&def.cost_as_resources(instance.count));
             let next_cost = def.next_cost(instance.count);
 
-            let cicd_count = state
-                .buildings
-                .get(&BuildingKind::CICDPipeline)
-                .map(|b| b.count)
-                .unwrap_or(0);
-            let cicd_mult = 1.0 + (cicd_count as f64 * 0.10);
-            let prod_per_sec =
-                def.production_per_tick(instance.count, instance.level, state.global_multiplier * cicd_mult)
-                    * 4.0;
+            let prod_per_sec = state
+                .production_breakdown
+                .get(*kind)
+                .map(|b| b.total() * 4.0)
+                .unwrap_or(0.0);
 
             let marker = if is_selected { "▸ " } else { "  " };
             let name_style = if is_selected {
@@ -142,12 +147,38 @@ impl ServerRack {
                 String::new()
             };
 
-            lines.push(Line::from(vec![
+            let purchase = Purchase::Building(*kind);
+            let mut name_line = vec![
                 Span::styled(marker, name_style),
                 Span::styled(format!("{:<20}", def.name), name_style),
                 Span::styled(count_str, theme::text_value()),
                 Span::styled(level_str, theme::text_value()),
-            ]));
+            ];
+            let synergy = state
+                .production_breakdown
+                .get(*kind)
+                .map(|b| b.synergy_multiplier)
+                .unwrap_or(1.0);
+            if is_selected && synergy > 1.0 {
+                name_line.push(Span::styled(
+                    format!(" ⇄x{synergy:.2}"),
+                    ratatui::style::Style::default().fg(theme::ACCENT_CYAN),
+                ));
+            }
+            if best == Some(purchase) {
+                name_line.push(best_value_tag());
+            } else if best == Some(Purchase::BuildingLevel(*kind)) {
+                let level_cost = advice
+                    .iter()
+                    .find(|a| a.purchase == Purchase::BuildingLevel(*kind))
+                    .map(|a| format_si(a.cost.sum()))
+                    .unwrap_or_default();
+                name_line.push(Span::styled(
+                    format!("  $ BEST: [u] level up ({level_cost})"),
+                    ratatui::style::Style::default().fg(theme::ACCENT_MAGENTA),
+                ));
+            }
+            lines.push(Line::from(name_line));
 
             let cost_style = if can_afford {
                 ratatui::style::Style::default().fg(theme::FG_PRIMARY)
@@ -159,18 +190,32 @@ impl ServerRack {
                 format!("+{}/s", format_si(prod_per_sec))
             } else if def.kind == BuildingKind::CICDPipeline && instance.count > 0 {
                 format!("+{}% global", instance.count * 10)
+            } else if def.is_facility() {
+                let supply = if def.supply.power > 0.0 {
+                    def.supply.power
+                } else {
+                    def.supply.cooling
+                };
+                let unit = if def.supply.power > 0.0 { "kW" } else { "kW cool" };
+                format!("+{}{unit}", format_si(supply))
             } else {
                 String::from("--")
             };
 
-            lines.push(Line::from(vec![
+            let mut cost_line = vec![
                 Span::styled("    ", theme::text_dim()),
                 Span::styled(format!("{:<14}", prod_str), ratatui::style::Style::default().fg(theme::FG_PRIMARY)),
                 Span::styled("Cost: ", theme::text_dim()),
-                Span::styled(format_si(next_cost), cost_style),
-            ]));
+                Span::styled(format!("{:<8}", format_si(next_cost)), cost_style),
+            ];
+            cost_line.extend(advice_spans(advice.iter().find(|a| a.purchase == purchase)));
+            lines.push(Line::from(cost_line));
 
-            lines.push(Line::from(""));
+            if is_selected {
+                lines.push(level_preview_line(state, *kind, instance.level));
+            } else {
+                lines.push(Line::from(""));
+            }
         }
 
         if focused {
@@ -179,6 +224,8 @@ impl ServerRack {
                 Span::styled("Buy ", theme::text_dim()),
                 Span::styled("[u]", theme::text_value()),
                 Span::styled("Upgrade ", theme::text_dim()),
+                Span::styled("[d]", theme::text_value()),
+                Span::styled("Details ", theme::text_dim()),
                 Span::styled("[r]", theme::text_value()),
                 Span::styled("Research", theme::text_dim()),
             ]));
@@ -189,6 +236,127 @@ impl ServerRack {
         Ok(())
     }
 
+    fn draw_breakdown(
+        &self,
+        frame: &mut Frame<'_>,
+        area: Rect,
+        focused: bool,
+        state: &GameState,
+    ) -> Result<()> {
+        let unlocked = state.unlocked_buildings();
+        let kind = match unlocked.get(self.selected_index) {
+            Some(kind) => *kind,
+            None => return Ok(()),
+        };
+        let defs = all_building_defs();
+        let name = defs
+            .iter()
+            .find(|d| d.kind == kind)
+            .map(|d| d.name.as_str())
+            .unwrap_or("?");
+
+        let mut lines: Vec<Line> = vec![
+            Line::from(Span::styled(format!("  {name}"), theme::title())),
+            Line::from(""),
+        ];
+
+        match state.production_breakdown.get(kind) {
+            None => {
+                let is_facility = defs.iter().any(|d| d.kind == kind && d.is_facility());
+                let msg = if kind == BuildingKind::CICDPipeline {
+                    "  Boosts every other building (see CI/CD factor)"
+                } else if is_facility {
+                    "  Supplies power/cooling (see Facility factor)"
+                } else {
+                    "  Not producing anything yet"
+                };
+                lines.push(Line::from(Span::styled(msg, theme::text_dim())));
+            }
+            Some(b) => {
+                let factors = [
+                    ("Base per unit", format!("{}/s", format_si(b.base * 4.0))),
+                    ("x Count", format!("{}", b.count)),
+                    (
+                        "x Level",
+                        format!("{:.2}  (Lv.{})", b.level_multiplier(), b.level),
+                    ),
+                    ("x Upgrades", format!("{:.2}", b.upgrade_multiplier)),
+                    ("x CI/CD", format!("{:.2}", b.cicd_multiplier)),
+                    ("x Global/Rep", format!("{:.2}", b.global_multiplier)),
+                    ("x Effects", format!("{:.2}", b.effect_multiplier)),
+                    ("x Traffic spike", format!("{:.2}", b.spike_multiplier)),
+                    ("x Synergies", format!("{:.2}", b.synergy_multiplier)),
+                    ("x Facility", format!("{:.2}", b.facility_multiplier)),
+                    ("x Upkeep", format!("{:.2}", b.upkeep_multiplier)),
+                ];
+                for (label, value) in factors {
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("    {:<16}", label), theme::text_dim()),
+                        Span::styled(value, theme::text_value()),
+                    ]));
+                }
+                lines.push(Line::from(vec![
+                    Span::styled(format!("    {:<16}", "= Total"), theme::title()),
+                    Span::styled(
+                        format!("{}/s", format_si(b.total() * 4.0)),
+                        ratatui::style::Style::default().fg(theme::FG_PRIMARY),
+                    ),
+                ]));
+            }
+        }
+
+        let synergies: Vec<&Synergy> = all_synergies()
+            .iter()
+            .filter(|s| s.involves(kind))
+            .collect();
+        if !synergies.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("  Synergies", theme::title())));
+            for synergy in &synergies {
+                lines.push(synergy_line(state, synergy));
+            }
+        }
+
+        for effect in &state.active_effects {
+            if effect.production_multiplier(kind) == 1.0 {
+                continue;
+            }
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  ! {} ({}s left)",
+                    effect.description(),
+                    effect.remaining_ticks / 4
+                ),
+                ratatui::style::Style::default().fg(theme::ACCENT_RED),
+            )));
+        }
+
+        if state.traffic_spike_remaining > 0 {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  Traffic spike: x{:.1} on everything for {}s",
+                    state.traffic_spike_multiplier,
+                    state.traffic_spike_remaining / 4
+                ),
+                ratatui::style::Style::default().fg(theme::ACCENT_YELLOW),
+            )));
+        }
+
+        if focused {
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled(" [↑/↓]", theme::text_value()),
+                Span::styled("Building ", theme::text_dim()),
+                Span::styled("[d]", theme::text_value()),
+                Span::styled("Back", theme::text_dim()),
+            ]));
+        }
+
+        frame.render_widget(Paragraph::new(lines), area);
+        Ok(())
+    }
+
     fn draw_upgrades(
         &self,
         frame: &mut Frame<'_>,
@@ -198,9 +366,29 @@ impl ServerRack {
     ) -> Result<()> {
         let available = state.available_upgrades();
         let purchased: Vec<_> = state.upgrades.iter().filter(|u| u.purchased).collect();
+        let advice = state.all_purchase_advice();
+        let best = best_value(&advice);
 
         let mut lines: Vec<Line> = Vec::new();
 
+        // Research in progress
+        if !state.research.jobs.is_empty() {
+            let active = state.active_research();
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  Research Queue ({}/{} slots):",
+                    active.len(),
+                    state.research_slots()
+                ),
+                theme::title(),
+            )));
+            for job in &state.research.jobs {
+                lines.push(research_job_line(state, job, active.contains(&job.id)));
+            }
+            lines.push(Line::from(""));
+        }
+        let queue_lines = lines.len();
+
         // Available upgrades
         lines.push(Line::from(Span::styled(
             "  Available Research:",
@@ -215,7 +403,7 @@ impl ServerRack {
             )));
         } else {
             let visible_height = area.height as usize;
-            let max_visible = visible_height.saturating_sub(6) / 3;
+            let max_visible = visible_height.saturating_sub(6 + queue_lines) / 3;
 
             for (i, upgrade) in available.iter().enumerate() {
                 if i >= max_visible {
@@ -237,28 +425,25 @@ impl ServerRack {
                     ratatui::style::Style::default().fg(theme::ACCENT_RED)
                 };
 
-                lines.push(Line::from(vec![
+                let purchase = Purchase::Upgrade(upgrade.id);
+                let mut name_line = vec![
                     Span::styled(marker, name_style),
                     Span::styled(&upgrade.name, name_style),
-                ]));
+                ];
+                if best == Some(purchase) {
+                    name_line.push(best_value_tag());
+                }
+                lines.push(Line::from(name_line));
 
                 // Cost line
-                let cost_parts: Vec<String> = [
-                    (upgrade.cost.compute, "CPU"),
-                    (upgrade.cost.bandwidth, "BW"),
-                    (upgrade.cost.storage, "SSD"),
-                ]
-                .iter()
-                .filter(|(v, _)| *v > 0.0)
-                .map(|(v, label)| format!("{} {}", format_si(*v), label))
-                .collect();
-
-                lines.push(Line::from(vec![
+                let mut cost_line = vec![
                     Span::styled("    ", theme::text_dim()),
                     Span::styled(&upgrade.description, theme::text_dim()),
                     Span::styled("  Cost: ", theme::text_dim()),
-                    Span::styled(cost_parts.join(" + "), cost_style),
-                ]));
+                    Span::styled(format_cost(&upgrade.cost), cost_style),
+                ];
+                cost_line.extend(advice_spans(advice.iter().find(|a| a.purchase == purchase)));
+                lines.push(Line::from(cost_line));
 
                 lines.push(Line::from(""));
             }
@@ -278,7 +463,11 @@ impl ServerRack {
             lines.push(Line::from(""));
             lines.push(Line::from(vec![
                 Span::styled(" [Enter]", theme::text_value()),
-                Span::styled("Buy ", theme::text_dim()),
+                Span::styled("Research ", theme::text_dim()),
+                Span::styled("[x]", theme::text_value()),
+                Span::styled("Cancel last ", theme::text_dim()),
+                Span::styled("[t]", theme::text_value()),
+                Span::styled("Tree ", theme::text_dim()),
                 Span::styled("[r]", theme::text_value()),
                 Span::styled("Buildings", theme::text_dim()),
             ]));
@@ -294,22 +483,29 @@ impl ServerRack {
         key: KeyEvent,
         state: &GameState,
     ) -> Result<Option<Action>> {
-        match key.code {
-            KeyCode::Char('r') => {
-                self.view = match self.view {
-                    View::Buildings => View::Upgrades,
-                    View::Upgrades => View::Buildings,
-                };
-                self.selected_index = 0;
-                self.scroll_offset = 0;
-                return Ok(Some(Action::None));
-            }
-            _ => {}
+        if key.code == KeyCode::Char('t') {
+            return Ok(Some(Action::ToggleResearchTree));
+        }
+        if key.code == KeyCode::Char('r') {
+            self.view = match self.view {
+                View::Buildings | View::Breakdown => View::Upgrades,
+                View::Upgrades => View::Buildings,
+            };
+            self.selected_index = 0;
+            self.scroll_offset = 0;
+            return Ok(Some(Action::None));
         }
 
         match self.view {
             View::Buildings => self.handle_building_keys(key, state),
             View::Upgrades => self.handle_upgrade_keys(key, state),
+            View::Breakdown => {
+                if matches!(key.code, KeyCode::Char('d') | KeyCode::Esc) {
+                    self.view = View::Buildings;
+                    return Ok(Some(Action::None));
+                }
+                self.handle_building_keys(key, state)
+            }
         }
     }
 
@@ -346,6 +542,10 @@ impl ServerRack {
                     Ok(None)
                 }
             }
+            KeyCode::Char('d') => {
+                self.view = View::Breakdown;
+                Ok(Some(Action::None))
+            }
             KeyCode::Char('u') => {
                 if self.selected_index < unlocked.len() {
                     Ok(Some(Action::UpgradeBuilding(
@@ -364,6 +564,14 @@ impl ServerRack {
         key: KeyEvent,
         state: &GameState,
     ) -> Result<Option<Action>> {
+        if key.code == KeyCode::Char('x') {
+            return Ok(state
+                .research
+                .jobs
+                .last()
+                .map(|job| Action::CancelResearch(job.id)));
+        }
+
         let available = state.available_upgrades();
         if available.is_empty() {
             return Ok(None);
@@ -396,6 +604,159 @@ impl ServerRack {
     }
 }
 
+/// "  Name  [######----]  60%  12s" for a queued upgrade.
+fn research_job_line<'a>(state: &GameState, job: &ResearchJob, active: bool) -> Line<'a> {
+    let name = state
+        .upgrades
+        .iter()
+        .find(|u| u.id == job.id)
+        .map(|u| u.name.clone())
+        .unwrap_or_default();
+    let width = 12;
+    let filled = (job.progress() * width as f64).round() as usize;
+    let bar = format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled.min(width)));
+    let status = if active {
+        format!(
+            " {:>3.0}%  {}",
+            job.progress() * 100.0,
+            short_duration(job.remaining_ticks() as f64 / 4.0)
+        )
+    } else {
+        String::from(" waiting")
+    };
+    let style = if active {
+        theme::text_value()
+    } else {
+        theme::text_dim()
+    };
+    Line::from(vec![
+        Span::styled(format!("    {:<22}", name), style),
+        Span::styled(bar, ratatui::style::Style::default().fg(theme::ACCENT_CYAN)),
+        Span::styled(status, style),
+    ])
+}
+
+/// Next level's cost and the production before/after buying it.
+fn level_preview_line<'a>(state: &GameState, kind: BuildingKind, level: u32) -> Line<'a> {
+    let cost = match state.building_level_cost(kind) {
+        Some(cost) => cost,
+        None => return Line::from(""),
+    };
+    let cost_style = if state.resources.can_afford(&cost) {
+        ratatui::style::Style::default().fg(theme::FG_PRIMARY)
+    } else {
+        ratatui::style::Style::default().fg(theme::ACCENT_RED)
+    };
+    let before = state
+        .production_breakdown
+        .get(kind)
+        .map(|b| b.total() * 4.0)
+        .unwrap_or(0.0);
+    let after = before + state.marginal_production(Purchase::BuildingLevel(kind)).sum() * 4.0;
+
+    let mut spans = vec![
+        Span::styled(format!("    Lv.{}→{}: ", level, level + 1), theme::text_dim()),
+        Span::styled(format_cost(&cost), cost_style),
+        Span::styled(
+            format!("  {}/s → {}/s", format_si(before), format_si(after)),
+            theme::text_value(),
+        ),
+    ];
+    if let Some((at, mult)) = formulas::next_milestone(level) {
+        let style = if at == level + 1 {
+            ratatui::style::Style::default().fg(theme::ACCENT_MAGENTA)
+        } else {
+            theme::text_dim()
+        };
+        spans.push(Span::styled(format!("  Lv.{at}: x{mult}"), style));
+    }
+    Line::from(spans)
+}
+
+/// "Load Balancer → Dedicated Server x1.35", dimmed until both ends are owned.
+fn synergy_line<'a>(state: &GameState, synergy: &Synergy) -> Line<'a> {
+    let defs = all_building_defs();
+    let name_of = |kind: BuildingKind| {
+        defs.iter()
+            .find(|d| d.kind == kind)
+            .map(|d| d.name.as_str())
+            .unwrap_or("?")
+    };
+    let count = |kind: BuildingKind| state.buildings.get(&kind).map(|b| b.count).unwrap_or(0);
+
+    let sources = count(synergy.source);
+    let (target, target_owned) = match synergy.effect {
+        SynergyEffect::Production(kind) => (name_of(kind).to_string(), count(kind) > 0),
+        SynergyEffect::FailureOdds { tier } => (format!("tier {tier} failure odds"), true),
+    };
+    let active = sources > 0 && target_owned;
+    let style = if active {
+        theme::text_value()
+    } else {
+        theme::text_dim()
+    };
+
+    Line::from(vec![
+        Span::styled(
+            format!("    ⇄ {} → {target} ", name_of(synergy.source)),
+            style,
+        ),
+        Span::styled(
+            format!("x{:.2}", synergy.value(sources)),
+            if active {
+                ratatui::style::Style::default().fg(theme::ACCENT_CYAN)
+            } else {
+                theme::text_dim()
+            },
+        ),
+        Span::styled(format!("  {}", synergy.description), theme::text_dim()),
+    ])
+}
+
+fn best_value_tag<'a>() -> Span<'a> {
+    Span::styled(
+        "  $ BEST VALUE",
+        ratatui::style::Style::default().fg(theme::ACCENT_MAGENTA),
+    )
+}
+
+/// "+1.2K/s  ETA 12s  ROI 3.4m" for a purchase.
+fn advice_spans<'a>(advice: Option<&PurchaseAdvice>) -> Vec<Span<'a>> {
+    let advice = match advice {
+        Some(a) => a,
+        None => return Vec::new(),
+    };
+    let eta = match advice.time_to_afford {
+        Some(t) if t <= 0.0 => String::from("now"),
+        Some(t) => short_duration(t),
+        None => String::from("never"),
+    };
+    let roi = advice
+        .payback
+        .map(short_duration)
+        .unwrap_or_else(|| String::from("--"));
+    vec![
+        Span::styled("  +", theme::text_dim()),
+        Span::styled(format!("{}/s", format_si(advice.gain.sum() * 4.0)), theme::text_value()),
+        Span::styled("  ETA ", theme::text_dim()),
+        Span::styled(eta, theme::text_value()),
+        Span::styled("  ROI ", theme::text_dim()),
+        Span::styled(roi, theme::text_value()),
+    ]
+}
+
+fn short_duration(secs: f64) -> String {
+    if secs < 60.0 {
+        format!("{:.0}s", secs)
+    } else if secs < 3600.0 {
+        format!("{:.1}m", secs / 60.0)
+    } else if secs < 86_400.0 {
+        format!("{:.1}h", secs / 3600.0)
+    } else {
+        format!("{:.1}d", secs / 86_400.0)
+    }
+}
+
 impl Component for ServerRack {
     fn draw(&self, frame: &mut Frame<'_>, area: Rect, focused: bool) -> Result<()> {
         let border_style = if focused {
@@ -0,0 +1,247 @@
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::action::Action;
+use crate::game::buildings::all_building_defs;
+use crate::game::content;
+use crate::game::resources::format_si;
+use crate::game::state::GameState;
+use crate::game::stats::RunStats;
+use crate::theme;
+
+/// Overlay listing per-run and all-time statistics side by side.
+pub struct StatsView {
+    scroll: usize,
+}
+
+impl StatsView {
+    pub fn new() -> Self {
+        Self { scroll: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('s') => return Ok(Some(Action::ToggleStats)),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll += 1,
+            _ => {}
+        }
+        Ok(Some(Action::None))
+    }
+
+    pub fn draw_with_state(
+        &self,
+        frame: &mut Frame<'_>,
+        area: Rect,
+        state: &GameState,
+    ) -> Result<()> {
+        let width = 64u16.min(area.width);
+        let height = area.height.saturating_sub(4).max(8).min(area.height);
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(width)) / 2,
+            y: area.y + (area.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        };
+
+        let block = Block::default()
+            .title(" STATISTICS ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(theme::border_focused());
+
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let run = &state.stats.run;
+        let all = &state.stats.lifetime;
+
+        let mut lines: Vec<Line> = vec![
+            Line::from(vec![
+                Span::styled(format!("  {:<24}", ""), theme::text_dim()),
+                Span::styled(format!("{:>14}", "This run"), theme::title()),
+                Span::styled(format!("{:>14}", "All time"), theme::title()),
+            ]),
+            section("General"),
+            row("Time played", duration(run.seconds_played()), duration(all.seconds_played())),
+            row(
+                "Reputation earned",
+                format_si(run.reputation_earned),
+                format_si(all.reputation_earned),
+            ),
+            row(
+                "Reputation / hour",
+                format_si(run.reputation_per_hour()),
+                format_si(all.reputation_per_hour()),
+            ),
+            row("Prestiges", String::new(), state.prestige_count.to_string()),
+            row("IPOs", String::new(), state.ipo_count.to_string()),
+            row("Equity", String::new(), format_si(state.equity)),
+            row(
+                "Fastest prestige",
+                String::new(),
+                state
+                    .stats
+                    .fastest_prestige_ticks
+                    .map(|t| duration(t as f64 / 4.0))
+                    .unwrap_or_else(|| "--".into()),
+            ),
+            section("Peak production /s"),
+        ];
+
+        for (label, get) in resource_rows() {
+            lines.push(row(
+                label,
+                format_si(get(&run.peak_production) * 4.0),
+                format_si(get(&all.peak_production) * 4.0),
+            ));
+        }
+
+        lines.push(section("Total spent"));
+        for (label, get) in resource_rows() {
+            lines.push(row(
+                label,
+                format_si(get(&run.total_spent)),
+                format_si(get(&all.total_spent)),
+            ));
+        }
+
+        lines.push(section("Tasks"));
+        lines.push(row(
+            "Succeeded",
+            run.tasks_succeeded.to_string(),
+            all.tasks_succeeded.to_string(),
+        ));
+        lines.push(row(
+            "Failed",
+            run.tasks_failed.to_string(),
+            all.tasks_failed.to_string(),
+        ));
+        lines.push(row(
+            "Expired",
+            run.tasks_expired.to_string(),
+            all.tasks_expired.to_string(),
+        ));
+
+        lines.push(section("Incidents"));
+        lines.push(row(
+            "Resolved",
+            run.incidents_resolved.to_string(),
+            all.incidents_resolved.to_string(),
+        ));
+        lines.push(row(
+            "Burned out",
+            run.incidents_burned_out.to_string(),
+            all.incidents_burned_out.to_string(),
+        ));
+        let mean = |seconds: Option<f64>| seconds.map(duration).unwrap_or_else(|| "--".into());
+        lines.push(row("MTTD", mean(run.mttd_seconds()), mean(all.mttd_seconds())));
+        lines.push(row("MTTA", mean(run.mtta_seconds()), mean(all.mtta_seconds())));
+        lines.push(row("MTTR", mean(run.mttr_seconds()), mean(all.mttr_seconds())));
+
+        lines.push(section("Buildings bought"));
+        lines.push(row(
+            "Total",
+            run.total_buildings_bought().to_string(),
+            all.total_buildings_bought().to_string(),
+        ));
+        for def in all_building_defs() {
+            let lifetime = all.buildings_bought.get(&def.kind).copied().unwrap_or(0);
+            if lifetime == 0 {
+                continue;
+            }
+            let this_run = run.buildings_bought.get(&def.kind).copied().unwrap_or(0);
+            lines.push(row(&def.name, this_run.to_string(), lifetime.to_string()));
+        }
+
+        lines.push(section("Events"));
+        lines.extend(event_rows(run, all));
+
+        lines.push(section("Content packs"));
+        lines.extend(pack_rows());
+
+        let visible = inner.height as usize;
+        let max_scroll = lines.len().saturating_sub(visible.saturating_sub(1));
+        let scroll = self.scroll.min(max_scroll);
+        let mut shown: Vec<Line> = lines
+            .into_iter()
+            .skip(scroll)
+            .take(visible.saturating_sub(1))
+            .collect();
+        shown.push(Line::from(vec![
+            Span::styled(" [↑/↓]", theme::text_value()),
+            Span::styled("Scroll ", theme::text_dim()),
+            Span::styled("[Esc]", theme::text_value()),
+            Span::styled("Close", theme::text_dim()),
+        ]));
+
+        frame.render_widget(Paragraph::new(shown), inner);
+        Ok(())
+    }
+}
+
+type ResourceGetter = fn(&crate::game::resources::Resources) -> f64;
+
+fn resource_rows() -> [(&'static str, ResourceGetter); 4] {
+    [
+        ("Compute", |r| r.compute),
+        ("Bandwidth", |r| r.bandwidth),
+        ("Storage", |r| r.storage),
+        ("Crypto", |r| r.crypto),
+    ]
+}
+
+fn event_rows<'a>(run: &RunStats, all: &RunStats) -> Vec<Line<'a>> {
+    if all.events_by_type.is_empty() {
+        return vec![Line::from(Span::styled("    None yet", theme::text_dim()))];
+    }
+    let mut kinds: Vec<_> = all.events_by_type.iter().collect();
+    kinds.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    kinds
+        .into_iter()
+        .map(|(slug, count)| {
+            let this_run = run.events_by_type.get(slug).copied().unwrap_or(0);
+            row(slug, this_run.to_string(), count.to_string())
+        })
+        .collect()
+}
+
+fn pack_rows<'a>() -> Vec<Line<'a>> {
+    let packs = &content::registry().packs;
+    if packs.is_empty() {
+        return vec![Line::from(Span::styled("    None installed", theme::text_dim()))];
+    }
+    packs
+        .iter()
+        .map(|pack| {
+            Line::from(vec![
+                Span::styled(format!("    {:<22}", pack.name), theme::text_value()),
+                Span::styled(format!("{} {}", pack.id, pack.version), theme::text_dim()),
+            ])
+        })
+        .collect()
+}
+
+fn section(title: &str) -> Line<'_> {
+    Line::from(Span::styled(format!(" {title}"), theme::title()))
+}
+
+fn row<'a>(label: &str, run: String, all: String) -> Line<'a> {
+    Line::from(vec![
+        Span::styled(format!("    {:<22}", label), theme::text_dim()),
+        Span::styled(format!("{:>14}", run), theme::text_value()),
+        Span::styled(format!("{:>14}", all), theme::text_value()),
+    ])
+}
+
+fn duration(seconds: f64) -> String {
+    let secs = seconds as u64;
+    format!("{}h {:02}m {:02}s", secs / 3600, (secs / 60) % 60, secs % 60)
+}
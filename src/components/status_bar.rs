@@ -41,6 +41,20 @@ impl Component for StatusBar {
             Span::styled("Jump ", theme::text_dim()),
             Span::styled("[p]", theme::text_value()),
             Span::styled("Prestige ", theme::text_dim()),
+            Span::styled("[i]", theme::text_value()),
+            Span::styled("IPO ", theme::text_dim()),
+            Span::styled("[l]", theme::text_value()),
+            Span::styled("Log ", theme::text_dim()),
+            Span::styled("[s]", theme::text_value()),
+            Span::styled("Stats ", theme::text_dim()),
+            Span::styled("[n]", theme::text_value()),
+            Span::styled("Alerts ", theme::text_dim()),
+            Span::styled("[m]", theme::text_value()),
+            Span::styled("Market ", theme::text_dim()),
+            Span::styled("[a]", theme::text_value()),
+            Span::styled("Achievements ", theme::text_dim()),
+            Span::styled("[h]", theme::text_value()),
+            Span::styled("Challenges ", theme::text_dim()),
             Span::styled("[q]", theme::text_value()),
             Span::styled("Quit ", theme::text_dim()),
             Span::styled("| ", theme::text_dim()),
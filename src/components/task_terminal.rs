@@ -10,6 +10,7 @@ use ratatui::{
 
 use crate::action::Action;
 use crate::components::Component;
+use crate::game::events::GameEventKind;
 use crate::game::state::GameState;
 use crate::game::tasks::{generate_random_task, ActiveTask, TaskKind, TASK_COOLDOWN_TICKS};
 use crate::theme;
@@ -20,6 +21,11 @@ pub struct TaskTerminal {
     rng: rand::rngs::StdRng,
     last_result: Option<TaskResult>,
     pending_reward: Option<crate::game::resources::Resources>,
+    pending_log: Option<GameEventKind>,
+    /// Incident whose prompt the player has started answering.
+    pending_ack: Option<u32>,
+    /// Incident whose prompt was answered correctly.
+    pending_mitigation: Option<u32>,
 }
 
 enum TaskResult {
@@ -36,9 +42,21 @@ impl TaskTerminal {
             rng: rand::rngs::StdRng::from_entropy(),
             last_result: None,
             pending_reward: None,
+            pending_log: None,
+            pending_ack: None,
+            pending_mitigation: None,
         }
     }
 
+    /// Drop the current task and start a task sequence seeded from
+    /// `seed`, so the same seed hands out the same tasks.
+    pub fn restart(&mut self, seed: u64) {
+        self.active_task = None;
+        self.last_result = None;
+        self.cooldown_ticks = TASK_COOLDOWN_TICKS / 2;
+        self.rng = rand::rngs::StdRng::seed_from_u64(seed);
+    }
+
     pub fn game_tick(&mut self, game_state: &mut GameState) {
         // Grant any pending reward from completed task
         if let Some(mut reward) = self.pending_reward.take() {
@@ -46,12 +64,49 @@ impl TaskTerminal {
             reward.bandwidth *= game_state.task_reward_multiplier;
             reward.storage *= game_state.task_reward_multiplier;
             game_state.resources.add(&reward);
+            game_state.stats.record_reputation(reward.reputation);
             game_state.tasks_completed += 1;
         }
 
+        // Log the outcome of a task finished since the last tick
+        if let Some(kind) = self.pending_log.take() {
+            game_state.log_event(kind);
+        }
+        if let Some(id) = self.pending_ack.take() {
+            game_state.acknowledge_incident(id);
+        }
+        if let Some(id) = self.pending_mitigation.take() {
+            game_state.mitigate_incident(id);
+        }
+
+        // Incident prompts jump the cooldown and replace routine tasks,
+        // which go back in the queue to be handed out again afterwards
+        let prompt_waiting = game_state
+            .task_queue
+            .front()
+            .is_some_and(|task| task.incident.is_some());
+        let routine_active = self
+            .active_task
+            .as_ref()
+            .is_some_and(|task| task.definition.incident.is_none());
+        if prompt_waiting && (self.active_task.is_none() || routine_active) {
+            if let Some(def) = game_state.task_queue.pop_front() {
+                if let Some(task) = self.active_task.take() {
+                    game_state.task_queue.push_back(task.definition);
+                }
+                self.active_task = Some(ActiveTask::new(def));
+                self.last_result = None;
+                self.cooldown_ticks = 0;
+                return;
+            }
+        }
+
         if let Some(ref mut task) = self.active_task {
             task.tick();
             if task.is_expired() {
+                game_state.log_event(GameEventKind::TaskExpired {
+                    name: task.definition.name.clone(),
+                });
                 self.last_result = Some(TaskResult::Expired);
                 self.active_task = None;
                 self.cooldown_ticks = TASK_COOLDOWN_TICKS;
@@ -61,7 +116,10 @@ impl TaskTerminal {
             if self.cooldown_ticks > 0 {
                 self.cooldown_ticks -= 1;
             } else {
-                let def = generate_random_task(&mut self.rng);
+                let def = game_state
+                    .task_queue
+                    .pop_front()
+                    .unwrap_or_else(|| generate_random_task(&mut self.rng));
                 self.active_task = Some(ActiveTask::new(def));
                 self.last_result = None;
             }
@@ -78,6 +136,10 @@ impl TaskTerminal {
             None => return Ok(None),
         };
 
+        if let Some(id) = task.definition.incident {
+            self.pending_ack = Some(id);
+        }
+
         match &task.definition.kind {
             TaskKind::TypeCommand { .. } => match key.code {
                 KeyCode::Char(c) => {
@@ -85,6 +147,9 @@ impl TaskTerminal {
                     if task.check_completion() {
                         let reward = task.definition.reward.clone();
                         self.pending_reward = Some(reward);
+                        self.pending_log = Some(GameEventKind::TaskCompleted {
+                            name: task.definition.name.clone(),
+                        });
                         self.last_result = Some(TaskResult::Completed);
                         self.active_task = None;
                         self.cooldown_ticks = TASK_COOLDOWN_TICKS;
@@ -111,11 +176,15 @@ impl TaskTerminal {
                     Ok(Some(Action::None))
                 }
                 KeyCode::Enter => {
+                    let name = task.definition.name.clone();
                     if task.check_completion() {
                         let reward = task.definition.reward.clone();
                         self.pending_reward = Some(reward);
+                        self.pending_log = Some(GameEventKind::TaskCompleted { name });
+                        self.pending_mitigation = task.definition.incident;
                         self.last_result = Some(TaskResult::Completed);
                     } else {
+                        self.pending_log = Some(GameEventKind::TaskFailed { name });
                         self.last_result = Some(TaskResult::Failed);
                     }
                     self.active_task = None;
@@ -230,7 +299,7 @@ impl TaskTerminal {
                         lines.push(Line::from(vec![
                             Span::styled("  > ", theme::title()),
                             Span::styled(&task.input, ratatui::style::Style::default().fg(theme::FG_PRIMARY)),
-                            Span::styled("_", if (state.total_ticks / 2) % 2 == 0 {
+                            Span::styled("_", if (state.total_ticks / 2).is_multiple_of(2) {
                                 ratatui::style::Style::default().fg(theme::FG_PRIMARY)
                             } else {
                                 ratatui::style::Style::default().fg(theme::BG)
@@ -1,4 +1,4 @@
-use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
+use crossterm::event::{self, Event as CrosstermEvent, KeyEvent};
 use futures::StreamExt;
 use std::time::Duration;
 use tokio::sync::mpsc;
@@ -6,8 +6,6 @@ use tokio::sync::mpsc;
 #[derive(Clone, Debug)]
 pub enum Event {
     Key(KeyEvent),
-    Mouse(MouseEvent),
-    Resize(u16, u16),
     Render,
     GameTick,
 }
@@ -32,8 +30,6 @@ impl EventHandler {
                         if let Some(Ok(evt)) = maybe_event {
                             let mapped = match evt {
                                 CrosstermEvent::Key(key) => Some(Event::Key(key)),
-                                CrosstermEvent::Mouse(mouse) => Some(Event::Mouse(mouse)),
-                                CrosstermEvent::Resize(w, h) => Some(Event::Resize(w, h)),
                                 _ => None,
                             };
                             if let Some(e) = mapped {
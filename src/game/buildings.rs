@@ -1,38 +1,105 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
 
+use super::content;
 use super::formulas;
 use super::resources::Resources;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub enum BuildingKind {
+/// Interned building id. Built-in buildings are the associated constants;
+/// content packs add namespaced ids such as `quantum:QubitRack`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BuildingKind(&'static str);
+
+// Not every built-in is named in code; the rest only appear in content.
+#[allow(dead_code, non_upper_case_globals)]
+impl BuildingKind {
     // Tier 1
-    RaspberryPi,
-    HomeRouter,
-    USBDrive,
+    pub const RaspberryPi: BuildingKind = BuildingKind("RaspberryPi");
+    pub const HomeRouter: BuildingKind = BuildingKind("HomeRouter");
+    pub const USBDrive: BuildingKind = BuildingKind("USBDrive");
     // Tier 2
-    VPS,
-    FiberConnection,
-    NASBox,
+    pub const VPS: BuildingKind = BuildingKind("VPS");
+    pub const FiberConnection: BuildingKind = BuildingKind("FiberConnection");
+    pub const NASBox: BuildingKind = BuildingKind("NASBox");
     // Tier 3
-    DedicatedServer,
-    LoadBalancer,
-    SANArray,
+    pub const DedicatedServer: BuildingKind = BuildingKind("DedicatedServer");
+    pub const LoadBalancer: BuildingKind = BuildingKind("LoadBalancer");
+    pub const SANArray: BuildingKind = BuildingKind("SANArray");
     // Tier 4
-    ServerCluster,
-    CDN,
-    DataWarehouse,
+    pub const ServerCluster: BuildingKind = BuildingKind("ServerCluster");
+    pub const CDN: BuildingKind = BuildingKind("CDN");
+    pub const DataWarehouse: BuildingKind = BuildingKind("DataWarehouse");
     // Tier 5
-    Datacenter,
-    BackboneLink,
-    ObjectStorage,
+    pub const Datacenter: BuildingKind = BuildingKind("Datacenter");
+    pub const BackboneLink: BuildingKind = BuildingKind("BackboneLink");
+    pub const ObjectStorage: BuildingKind = BuildingKind("ObjectStorage");
     // Tier 6
-    CloudRegion,
-    SubmarineCable,
-    DistributedFS,
+    pub const CloudRegion: BuildingKind = BuildingKind("CloudRegion");
+    pub const SubmarineCable: BuildingKind = BuildingKind("SubmarineCable");
+    pub const DistributedFS: BuildingKind = BuildingKind("DistributedFS");
+    // Tier 7 (needs Equity)
+    pub const HyperscaleCampus: BuildingKind = BuildingKind("HyperscaleCampus");
+    pub const PrivateBackbone: BuildingKind = BuildingKind("PrivateBackbone");
+    pub const ColdArchive: BuildingKind = BuildingKind("ColdArchive");
+    // Tier 8 (needs Equity)
+    pub const OrbitalDatacenter: BuildingKind = BuildingKind("OrbitalDatacenter");
+    pub const LaserMesh: BuildingKind = BuildingKind("LaserMesh");
+    pub const DNAStorage: BuildingKind = BuildingKind("DNAStorage");
     // Special
-    CICDPipeline,
-    MonitoringStack,
-    CryptoMiner,
+    pub const CICDPipeline: BuildingKind = BuildingKind("CICDPipeline");
+    pub const MonitoringStack: BuildingKind = BuildingKind("MonitoringStack");
+    pub const CryptoMiner: BuildingKind = BuildingKind("CryptoMiner");
+    // Facilities
+    pub const PDU: BuildingKind = BuildingKind("PDU");
+    pub const Chiller: BuildingKind = BuildingKind("Chiller");
+    pub const SolarArray: BuildingKind = BuildingKind("SolarArray");
+
+    /// The kind for `id`, interning it if it hasn't been seen before.
+    pub fn intern(id: &str) -> Self {
+        static INTERNED: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+        let mut interned = INTERNED
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        match interned.get(id) {
+            Some(existing) => BuildingKind(existing),
+            None => {
+                let leaked: &'static str = Box::leak(id.to_string().into_boxed_str());
+                interned.insert(leaked);
+                BuildingKind(leaked)
+            }
+        }
+    }
+
+    pub fn id(self) -> &'static str {
+        self.0
+    }
+
+    /// Pack a namespaced id belongs to; `None` for built-in buildings.
+    pub fn namespace(self) -> Option<&'static str> {
+        self.0.split_once(':').map(|(namespace, _)| namespace)
+    }
+}
+
+impl fmt::Debug for BuildingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl Serialize for BuildingKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for BuildingKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Ok(BuildingKind::intern(&id))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,21 +119,90 @@ impl BuildingInstance {
     }
 }
 
-/// Static definition of a building type.
+/// Definition of a building type, loaded from content data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuildingDef {
     pub kind: BuildingKind,
-    pub name: &'static str,
-    pub description: &'static str,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
     pub base_cost: f64,
     pub cost_multiplier: f64,
     pub base_production: f64,
     pub level_bonus: f64,
+    /// Growth of the level cost per level bought.
+    pub level_cost_growth: f64,
+    /// Extra resource needed for higher levels, if any.
+    #[serde(default)]
+    pub level_secondary: Option<SecondaryLevelCost>,
+    /// Power and cooling each unit needs.
+    #[serde(default)]
+    pub draw: FacilityStats,
+    /// Power and cooling each unit provides (facilities only).
+    #[serde(default)]
+    pub supply: FacilityStats,
+    /// Resources each unit consumes per tick to keep running.
+    #[serde(default)]
+    pub upkeep: Upkeep,
     pub resource_type: ResourceType,
     pub unlock_threshold: f64, // Compute threshold to unlock
     pub tier: u8,
+    /// Equity needed before the compute threshold counts.
+    #[serde(default)]
+    pub equity_required: f64,
+}
+
+/// Power (kW) and cooling (kW of heat removed).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct FacilityStats {
+    pub power: f64,
+    pub cooling: f64,
+}
+
+impl FacilityStats {
+    pub const NONE: FacilityStats = FacilityStats {
+        power: 0.0,
+        cooling: 0.0,
+    };
+
+    pub const fn new(power: f64, cooling: f64) -> Self {
+        Self { power, cooling }
+    }
+}
+
+/// Bandwidth and storage a building consumes per tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Upkeep {
+    pub bandwidth: f64,
+    pub storage: f64,
 }
 
-#[derive(Debug, Clone, Copy)]
+impl Upkeep {
+    pub const NONE: Upkeep = Upkeep {
+        bandwidth: 0.0,
+        storage: 0.0,
+    };
+
+    pub fn as_resources(&self) -> Resources {
+        Resources {
+            bandwidth: self.bandwidth,
+            storage: self.storage,
+            ..Default::default()
+        }
+    }
+}
+
+/// A second resource required on top of the primary level cost.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SecondaryLevelCost {
+    pub resource: ResourceType,
+    /// First level (the level being bought) that requires it.
+    pub from_level: u32,
+    /// Amount as a fraction of the primary level cost.
+    pub ratio: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ResourceType {
     Compute,
     Bandwidth,
@@ -75,297 +211,54 @@ pub enum ResourceType {
 }
 
 impl BuildingDef {
+    /// Facilities supply power or cooling instead of producing resources.
+    pub fn is_facility(&self) -> bool {
+        self.supply != FacilityStats::NONE
+    }
+
     pub fn next_cost(&self, count: u32) -> f64 {
         formulas::building_cost(self.base_cost, self.cost_multiplier, count)
     }
 
-    pub fn production_per_tick(&self, count: u32, level: u32, global_multiplier: f64) -> f64 {
-        formulas::building_production(count, self.base_production, level, self.level_bonus, global_multiplier)
+    pub fn cost_as_resources(&self, count: u32) -> Resources {
+        resource_amount(self.resource_type, self.next_cost(count))
     }
 
-    pub fn cost_as_resources(&self, count: u32) -> Resources {
-        let cost = self.next_cost(count);
-        match self.resource_type {
-            ResourceType::Compute => Resources {
-                compute: cost,
-                ..Default::default()
-            },
-            ResourceType::Bandwidth => Resources {
-                bandwidth: cost,
-                ..Default::default()
-            },
-            ResourceType::Storage => Resources {
-                storage: cost,
-                ..Default::default()
-            },
-            ResourceType::Crypto => Resources {
-                crypto: cost,
-                ..Default::default()
-            },
+    /// Cost of going from `level` to `level + 1`.
+    pub fn level_cost(&self, level: u32) -> Resources {
+        let primary = formulas::level_cost(self.base_cost, self.level_cost_growth, level);
+        let mut cost = resource_amount(self.resource_type, primary);
+        if let Some(secondary) = self.level_secondary {
+            if level + 1 >= secondary.from_level {
+                cost.add(&resource_amount(secondary.resource, primary * secondary.ratio));
+            }
         }
+        cost
     }
 }
 
-pub fn all_building_defs() -> Vec<BuildingDef> {
-    vec![
-        // Tier 1
-        BuildingDef {
-            kind: BuildingKind::RaspberryPi,
-            name: "Raspberry Pi",
-            description: "A tiny single-board computer",
-            base_cost: 10.0,
-            cost_multiplier: 1.15,
-            base_production: 0.5,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Compute,
-            unlock_threshold: 0.0,
-            tier: 1,
-        },
-        BuildingDef {
-            kind: BuildingKind::HomeRouter,
-            name: "Home Router",
-            description: "Basic network connectivity",
-            base_cost: 15.0,
-            cost_multiplier: 1.15,
-            base_production: 0.3,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Bandwidth,
-            unlock_threshold: 0.0,
-            tier: 1,
-        },
-        BuildingDef {
-            kind: BuildingKind::USBDrive,
-            name: "USB Drive",
-            description: "Portable storage",
-            base_cost: 20.0,
-            cost_multiplier: 1.15,
-            base_production: 0.2,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Storage,
-            unlock_threshold: 0.0,
-            tier: 1,
-        },
-        // Tier 2
-        BuildingDef {
-            kind: BuildingKind::VPS,
-            name: "VPS",
-            description: "Virtual private server",
-            base_cost: 100.0,
-            cost_multiplier: 1.15,
-            base_production: 4.0,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Compute,
-            unlock_threshold: 1_000.0,
-            tier: 2,
-        },
-        BuildingDef {
-            kind: BuildingKind::FiberConnection,
-            name: "Fiber Connection",
-            description: "High-speed fiber optic link",
-            base_cost: 150.0,
-            cost_multiplier: 1.15,
-            base_production: 2.5,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Bandwidth,
-            unlock_threshold: 1_000.0,
-            tier: 2,
-        },
-        BuildingDef {
-            kind: BuildingKind::NASBox,
-            name: "NAS Box",
-            description: "Network-attached storage",
-            base_cost: 200.0,
-            cost_multiplier: 1.15,
-            base_production: 1.5,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Storage,
-            unlock_threshold: 1_000.0,
-            tier: 2,
-        },
-        // Tier 3
-        BuildingDef {
-            kind: BuildingKind::DedicatedServer,
-            name: "Dedicated Server",
-            description: "Full rack-mounted server",
-            base_cost: 1_000.0,
-            cost_multiplier: 1.15,
-            base_production: 30.0,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Compute,
-            unlock_threshold: 100_000.0,
-            tier: 3,
+fn resource_amount(resource_type: ResourceType, amount: f64) -> Resources {
+    match resource_type {
+        ResourceType::Compute => Resources {
+            compute: amount,
+            ..Default::default()
         },
-        BuildingDef {
-            kind: BuildingKind::LoadBalancer,
-            name: "Load Balancer",
-            description: "Distributes network traffic",
-            base_cost: 1_500.0,
-            cost_multiplier: 1.15,
-            base_production: 20.0,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Bandwidth,
-            unlock_threshold: 100_000.0,
-            tier: 3,
+        ResourceType::Bandwidth => Resources {
+            bandwidth: amount,
+            ..Default::default()
         },
-        BuildingDef {
-            kind: BuildingKind::SANArray,
-            name: "SAN Array",
-            description: "Storage area network",
-            base_cost: 2_000.0,
-            cost_multiplier: 1.15,
-            base_production: 12.0,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Storage,
-            unlock_threshold: 100_000.0,
-            tier: 3,
+        ResourceType::Storage => Resources {
+            storage: amount,
+            ..Default::default()
         },
-        // Tier 4
-        BuildingDef {
-            kind: BuildingKind::ServerCluster,
-            name: "Server Cluster",
-            description: "Clustered compute nodes",
-            base_cost: 10_000.0,
-            cost_multiplier: 1.15,
-            base_production: 200.0,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Compute,
-            unlock_threshold: 10_000_000.0,
-            tier: 4,
+        ResourceType::Crypto => Resources {
+            crypto: amount,
+            ..Default::default()
         },
-        BuildingDef {
-            kind: BuildingKind::CDN,
-            name: "CDN",
-            description: "Content delivery network",
-            base_cost: 15_000.0,
-            cost_multiplier: 1.15,
-            base_production: 130.0,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Bandwidth,
-            unlock_threshold: 10_000_000.0,
-            tier: 4,
-        },
-        BuildingDef {
-            kind: BuildingKind::DataWarehouse,
-            name: "Data Warehouse",
-            description: "Enterprise data storage",
-            base_cost: 20_000.0,
-            cost_multiplier: 1.15,
-            base_production: 80.0,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Storage,
-            unlock_threshold: 10_000_000.0,
-            tier: 4,
-        },
-        // Tier 5
-        BuildingDef {
-            kind: BuildingKind::Datacenter,
-            name: "Datacenter",
-            description: "Full-scale data center",
-            base_cost: 100_000.0,
-            cost_multiplier: 1.15,
-            base_production: 1_500.0,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Compute,
-            unlock_threshold: 1_000_000_000.0,
-            tier: 5,
-        },
-        BuildingDef {
-            kind: BuildingKind::BackboneLink,
-            name: "Backbone Link",
-            description: "Internet backbone connection",
-            base_cost: 150_000.0,
-            cost_multiplier: 1.15,
-            base_production: 1_000.0,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Bandwidth,
-            unlock_threshold: 1_000_000_000.0,
-            tier: 5,
-        },
-        BuildingDef {
-            kind: BuildingKind::ObjectStorage,
-            name: "Object Storage",
-            description: "Cloud object store (S3-like)",
-            base_cost: 200_000.0,
-            cost_multiplier: 1.15,
-            base_production: 600.0,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Storage,
-            unlock_threshold: 1_000_000_000.0,
-            tier: 5,
-        },
-        // Tier 6
-        BuildingDef {
-            kind: BuildingKind::CloudRegion,
-            name: "Cloud Region",
-            description: "Entire cloud availability zone",
-            base_cost: 1_000_000.0,
-            cost_multiplier: 1.15,
-            base_production: 10_000.0,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Compute,
-            unlock_threshold: 1_000_000_000_000.0,
-            tier: 6,
-        },
-        BuildingDef {
-            kind: BuildingKind::SubmarineCable,
-            name: "Submarine Cable",
-            description: "Undersea fiber optic cable",
-            base_cost: 1_500_000.0,
-            cost_multiplier: 1.15,
-            base_production: 7_000.0,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Bandwidth,
-            unlock_threshold: 1_000_000_000_000.0,
-            tier: 6,
-        },
-        BuildingDef {
-            kind: BuildingKind::DistributedFS,
-            name: "Distributed FS",
-            description: "Planet-scale filesystem",
-            base_cost: 2_000_000.0,
-            cost_multiplier: 1.15,
-            base_production: 4_500.0,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Storage,
-            unlock_threshold: 1_000_000_000_000.0,
-            tier: 6,
-        },
-        // Special buildings
-        BuildingDef {
-            kind: BuildingKind::CICDPipeline,
-            name: "CI/CD Pipeline",
-            description: "Automates all production (+10% global)",
-            base_cost: 5_000.0,
-            cost_multiplier: 1.20,
-            base_production: 0.0, // Effect is global multiplier
-            level_bonus: 0.0,
-            resource_type: ResourceType::Compute,
-            unlock_threshold: 50_000.0,
-            tier: 3,
-        },
-        BuildingDef {
-            kind: BuildingKind::MonitoringStack,
-            name: "Monitoring Stack",
-            description: "Generates bonus events",
-            base_cost: 3_000.0,
-            cost_multiplier: 1.20,
-            base_production: 5.0,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Compute,
-            unlock_threshold: 25_000.0,
-            tier: 2,
-        },
-        BuildingDef {
-            kind: BuildingKind::CryptoMiner,
-            name: "Crypto Miner",
-            description: "Mines cryptocurrency",
-            base_cost: 50_000.0,
-            cost_multiplier: 1.20,
-            base_production: 0.1,
-            level_bonus: 0.5,
-            resource_type: ResourceType::Crypto,
-            unlock_threshold: 1_000_000_000.0,
-            tier: 5,
-        },
-    ]
+    }
+}
+
+/// Every building type, from the content registry.
+pub fn all_building_defs() -> &'static [BuildingDef] {
+    &content::registry().buildings
 }
@@ -0,0 +1,229 @@
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use super::buildings::ResourceType;
+use super::daily;
+use super::resources::Resources;
+
+/// Rules a run is played under. The default is a normal run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunModifiers {
+    pub no_upgrades: bool,
+    /// Only buildings producing this resource can be bought.
+    pub only_resource: Option<ResourceType>,
+    /// Multiplier on the chance of a random event each tick.
+    pub event_rate: f64,
+    pub no_offline: bool,
+    /// Ticks allowed to reach the goal.
+    pub time_limit_ticks: Option<u64>,
+    /// Start without reputation and achievement multipliers.
+    pub no_bonuses: bool,
+}
+
+impl Default for RunModifiers {
+    fn default() -> Self {
+        Self {
+            no_upgrades: false,
+            only_resource: None,
+            event_rate: 1.0,
+            no_offline: false,
+            time_limit_ticks: None,
+            no_bonuses: false,
+        }
+    }
+}
+
+impl RunModifiers {
+    pub fn allows_building(&self, resource_type: ResourceType) -> bool {
+        self.only_resource.is_none_or(|only| only == resource_type)
+    }
+
+    /// Short summary of every rule in effect.
+    pub fn describe(&self) -> Vec<String> {
+        let mut rules = Vec::new();
+        if self.no_upgrades {
+            rules.push("no research".to_string());
+        }
+        if let Some(only) = self.only_resource {
+            rules.push(format!("{only:?} buildings only"));
+        }
+        if self.event_rate != 1.0 {
+            rules.push(format!("events x{:.0}", self.event_rate));
+        }
+        if self.no_offline {
+            rules.push("no offline progress".to_string());
+        }
+        if self.no_bonuses {
+            rules.push("no prestige bonuses".to_string());
+        }
+        if let Some(limit) = self.time_limit_ticks {
+            rules.push(format!("{} minute limit", limit / (4 * 60)));
+        }
+        rules
+    }
+}
+
+/// An opt-in run with extra rules and a goal.
+#[derive(Debug, Clone)]
+pub struct ChallengeDef {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub modifiers: RunModifiers,
+    /// What the run starts with instead of the usual 50 compute.
+    pub start: Resources,
+    pub goal: ResourceType,
+    pub goal_amount: f64,
+    /// Reputation paid on completion.
+    pub reputation: f64,
+}
+
+impl ChallengeDef {
+    pub fn goal_met(&self, resources: &Resources) -> bool {
+        self.progress(resources) >= self.goal_amount
+    }
+
+    pub fn progress(&self, resources: &Resources) -> f64 {
+        match self.goal {
+            ResourceType::Compute => resources.compute,
+            ResourceType::Bandwidth => resources.bandwidth,
+            ResourceType::Storage => resources.storage,
+            ResourceType::Crypto => resources.crypto,
+        }
+    }
+}
+
+/// The challenge being played, if any.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveChallenge {
+    pub id: String,
+    pub started_tick: u64,
+    /// The RNGs were seeded when this run started. The RNG state isn't
+    /// saved, so a run resumed from a save is no longer seeded.
+    #[serde(skip, default)]
+    pub seeded: bool,
+}
+
+pub(super) const MINUTE_TICKS: u64 = 4 * 60;
+
+fn compute(amount: f64) -> Resources {
+    Resources {
+        compute: amount,
+        ..Default::default()
+    }
+}
+
+/// The fixed challenges, built on first use.
+pub fn all_challenges() -> &'static [ChallengeDef] {
+    static CHALLENGES: OnceLock<Vec<ChallengeDef>> = OnceLock::new();
+    CHALLENGES.get_or_init(builtin_challenges)
+}
+
+fn builtin_challenges() -> Vec<ChallengeDef> {
+    vec![
+        ChallengeDef {
+            id: "no_upgrades".into(),
+            name: "Bare Metal".into(),
+            description: "Reach 1M compute without any research".into(),
+            modifiers: RunModifiers {
+                no_upgrades: true,
+                ..Default::default()
+            },
+            start: compute(50.0),
+            goal: ResourceType::Compute,
+            goal_amount: 1_000_000.0,
+            reputation: 3.0,
+        },
+        ChallengeDef {
+            id: "storage_only".into(),
+            name: "Cold Storage".into(),
+            description: "Hoard 50K storage using storage buildings only".into(),
+            modifiers: RunModifiers {
+                only_resource: Some(ResourceType::Storage),
+                ..Default::default()
+            },
+            start: Resources {
+                storage: 50.0,
+                ..Default::default()
+            },
+            goal: ResourceType::Storage,
+            goal_amount: 50_000.0,
+            reputation: 3.0,
+        },
+        ChallengeDef {
+            id: "event_storm".into(),
+            name: "Chaos Engineering".into(),
+            description: "Reach 1M compute with events 5x as frequent".into(),
+            modifiers: RunModifiers {
+                event_rate: 5.0,
+                ..Default::default()
+            },
+            start: compute(50.0),
+            goal: ResourceType::Compute,
+            goal_amount: 1_000_000.0,
+            reputation: 2.0,
+        },
+        ChallengeDef {
+            id: "always_on".into(),
+            name: "Always On".into(),
+            description: "Reach 10M compute with no offline progress".into(),
+            modifiers: RunModifiers {
+                no_offline: true,
+                ..Default::default()
+            },
+            start: compute(50.0),
+            goal: ResourceType::Compute,
+            goal_amount: 10_000_000.0,
+            reputation: 4.0,
+        },
+        ChallengeDef {
+            id: "speedrun".into(),
+            name: "Speedrun".into(),
+            description: "Reach 1M compute in under 30 minutes".into(),
+            modifiers: RunModifiers {
+                time_limit_ticks: Some(30 * MINUTE_TICKS),
+                ..Default::default()
+            },
+            start: compute(50.0),
+            goal: ResourceType::Compute,
+            goal_amount: 1_000_000.0,
+            reputation: 5.0,
+        },
+    ]
+}
+
+/// A challenge by id; `daily:<date>` gives that day's daily challenge.
+pub fn challenge(id: &str) -> Option<ChallengeDef> {
+    if id.starts_with(daily::ID_PREFIX) {
+        return daily::date_of(id).map(daily::daily_challenge);
+    }
+    all_challenges().iter().find(|c| c.id == id).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_challenge_ids_unique() {
+        let challenges = all_challenges();
+        let mut ids: Vec<_> = challenges.iter().map(|c| c.id.as_str()).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), challenges.len());
+    }
+
+    #[test]
+    fn test_modifiers() {
+        let normal = RunModifiers::default();
+        assert!(normal.allows_building(ResourceType::Compute));
+        assert!(normal.describe().is_empty());
+
+        let storage = challenge("storage_only").unwrap();
+        assert!(!storage.modifiers.allows_building(ResourceType::Compute));
+        assert!(storage.modifiers.allows_building(ResourceType::Storage));
+        assert!(!storage.goal_met(&storage.start));
+    }
+}
@@ -0,0 +1,464 @@
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use super::buildings::{BuildingDef, BuildingKind};
+use super::events::CustomEventDef;
+use super::packs::{self, Pack, PackManifest};
+use super::progression::{AchievementDef, AchievementReward};
+use super::save;
+use super::scripting::ScriptSource;
+use super::synergies::{Synergy, SynergyEffect};
+use super::tasks::{TaskDefinition, TaskKind};
+use super::upgrades::{Upgrade, UpgradeEffect, UpgradeId};
+
+/// Content shipped with the game.
+const BUILDINGS: &str = include_str!("../../assets/content/buildings.json");
+const UPGRADES: &str = include_str!("../../assets/content/upgrades.json");
+const TASKS: &str = include_str!("../../assets/content/tasks.json");
+const ACHIEVEMENTS: &str = include_str!("../../assets/content/achievements.json");
+const SYNERGIES: &str = include_str!("../../assets/content/synergies.json");
+
+/// Sub-directory of the data dir where players can override or extend content.
+const CONTENT_DIR: &str = "content";
+
+/// All game content: buildings, upgrades, tasks, events, achievements,
+/// synergies and scripts, plus the packs that contributed to it.
+#[derive(Debug, Clone)]
+pub struct ContentRegistry {
+    pub buildings: Vec<BuildingDef>,
+    pub upgrades: Vec<Upgrade>,
+    pub tasks: Vec<TaskDefinition>,
+    pub events: Vec<CustomEventDef>,
+    pub achievements: Vec<AchievementDef>,
+    pub synergies: Vec<Synergy>,
+    pub scripts: Vec<ScriptSource>,
+    pub packs: Vec<PackManifest>,
+}
+
+/// Why content failed to load.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContentError {
+    Parse { file: String, message: String },
+    DuplicateId { file: &'static str, id: String },
+    UnknownPrerequisite { upgrade: UpgradeId, prerequisite: UpgradeId },
+    PrerequisiteCycle(UpgradeId),
+    UnknownBuilding { upgrade: UpgradeId, kind: BuildingKind },
+    Invalid { item: String, reason: &'static str },
+    NotNamespaced { pack: String, id: String },
+}
+
+impl fmt::Display for ContentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContentError::Parse { file, message } => write!(f, "{file}: {message}"),
+            ContentError::DuplicateId { file, id } => write!(f, "{file}: duplicate id {id}"),
+            ContentError::UnknownPrerequisite {
+                upgrade,
+                prerequisite,
+            } => write!(f, "upgrade {upgrade} requires unknown upgrade {prerequisite}"),
+            ContentError::PrerequisiteCycle(id) => {
+                write!(f, "upgrade {id} is part of a prerequisite cycle")
+            }
+            ContentError::UnknownBuilding { upgrade, kind } => {
+                write!(f, "upgrade {upgrade} targets {kind:?}, which has no definition")
+            }
+            ContentError::Invalid { item, reason } => write!(f, "{item}: {reason}"),
+            ContentError::NotNamespaced { pack, id } => {
+                write!(f, "{pack}: {id} is not under the pack's namespace")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ContentError {}
+
+/// The registry, loaded on first use. Built-in content is always valid (see
+/// the tests); broken user overrides and packs are logged and ignored.
+pub fn registry() -> &'static ContentRegistry {
+    static REGISTRY: OnceLock<ContentRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let builtin = ContentRegistry::builtin().expect("built-in content is invalid");
+        let content = match builtin.clone().with_overrides(&user_content_dir()) {
+            Ok(content) => content,
+            Err(e) => {
+                tracing::warn!("Ignoring user content: {e}");
+                builtin
+            }
+        };
+        content.with_packs(&packs::packs_dir())
+    })
+}
+
+pub fn user_content_dir() -> PathBuf {
+    save::data_dir().join(CONTENT_DIR)
+}
+
+impl ContentRegistry {
+    pub fn builtin() -> Result<Self, ContentError> {
+        let content = Self {
+            buildings: parse("buildings.json", BUILDINGS)?,
+            upgrades: parse("upgrades.json", UPGRADES)?,
+            tasks: parse("tasks.json", TASKS)?,
+            events: Vec::new(),
+            achievements: parse("achievements.json", ACHIEVEMENTS)?,
+            synergies: parse("synergies.json", SYNERGIES)?,
+            scripts: Vec::new(),
+            packs: Vec::new(),
+        };
+        content.validate()?;
+        Ok(content)
+    }
+
+    /// Merge any content files found in `dir`: entries with a known id
+    /// replace the built-in one, new ids are added.
+    pub fn with_overrides(mut self, dir: &Path) -> Result<Self, ContentError> {
+        if let Some(buildings) = read_override::<BuildingDef>(dir, "buildings.json")? {
+            merge(&mut self.buildings, buildings, |b| b.kind);
+        }
+        if let Some(upgrades) = read_override::<Upgrade>(dir, "upgrades.json")? {
+            merge(&mut self.upgrades, upgrades, |u| u.id);
+        }
+        if let Some(tasks) = read_override::<TaskDefinition>(dir, "tasks.json")? {
+            merge(&mut self.tasks, tasks, |t| t.name.clone());
+        }
+        if let Some(achievements) = read_override::<AchievementDef>(dir, "achievements.json")? {
+            merge(&mut self.achievements, achievements, |a| a.id.clone());
+        }
+        if let Some(synergies) = read_override::<Synergy>(dir, "synergies.json")? {
+            merge(&mut self.synergies, synergies, |s| (s.source, s.effect));
+        }
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Add every pack found in `dir`. Packs that fail to load, or that
+    /// would leave the content invalid, are skipped with a warning.
+    pub fn with_packs(mut self, dir: &Path) -> Self {
+        for pack in packs::load_packs(dir) {
+            match pack.and_then(|pack| self.clone().with_pack(pack)) {
+                Ok(content) => self = content,
+                Err(e) => tracing::warn!("Skipping content pack: {e}"),
+            }
+        }
+        self
+    }
+
+    pub fn with_pack(mut self, pack: Pack) -> Result<Self, ContentError> {
+        if self.packs.iter().any(|p| p.id == pack.manifest.id) {
+            return Err(ContentError::DuplicateId {
+                file: "pack.json",
+                id: pack.manifest.id,
+            });
+        }
+        self.buildings.extend(pack.buildings);
+        self.upgrades.extend(pack.upgrades);
+        self.tasks.extend(pack.tasks);
+        self.events.extend(pack.events);
+        self.achievements.extend(pack.achievements);
+        self.scripts.extend(pack.scripts);
+        self.packs.push(pack.manifest);
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Ids of the active packs, in load order.
+    pub fn pack_ids(&self) -> Vec<String> {
+        self.packs.iter().map(|p| p.id.clone()).collect()
+    }
+
+    /// Check ids are unique, references resolve and numbers make sense.
+    pub fn validate(&self) -> Result<(), ContentError> {
+        unique("buildings.json", self.buildings.iter().map(|b| format!("{:?}", b.kind)))?;
+        unique("upgrades.json", self.upgrades.iter().map(|u| u.id.to_string()))?;
+        unique("tasks.json", self.tasks.iter().map(|t| t.name.clone()))?;
+        unique("events.json", self.events.iter().map(|e| e.id.clone()))?;
+        unique("achievements.json", self.achievements.iter().map(|a| a.id.clone()))?;
+
+        for def in &self.buildings {
+            let item = || format!("building {:?}", def.kind);
+            check(def.base_cost > 0.0, item, "base_cost must be positive")?;
+            check(def.cost_multiplier >= 1.0, item, "cost_multiplier must be at least 1")?;
+            check(def.level_cost_growth >= 1.0, item, "level_cost_growth must be at least 1")?;
+            check(def.base_production >= 0.0, item, "base_production can't be negative")?;
+            check(def.unlock_threshold >= 0.0, item, "unlock_threshold can't be negative")?;
+            check(def.equity_required >= 0.0, item, "equity_required can't be negative")?;
+        }
+
+        let kinds: HashSet<BuildingKind> = self.buildings.iter().map(|b| b.kind).collect();
+        let ids: HashSet<UpgradeId> = self.upgrades.iter().map(|u| u.id).collect();
+        for upgrade in &self.upgrades {
+            for prerequisite in &upgrade.prerequisites {
+                if !ids.contains(prerequisite) {
+                    return Err(ContentError::UnknownPrerequisite {
+                        upgrade: upgrade.id,
+                        prerequisite: *prerequisite,
+                    });
+                }
+            }
+            let target = match upgrade.effect {
+                UpgradeEffect::MultiplyProduction(kind, _)
+                | UpgradeEffect::ReduceCost(kind, _)
+                | UpgradeEffect::UnlockBuilding(kind) => Some(kind),
+                _ => None,
+            };
+            if let Some(kind) = target.filter(|k| !kinds.contains(k)) {
+                return Err(ContentError::UnknownBuilding {
+                    upgrade: upgrade.id,
+                    kind,
+                });
+            }
+        }
+        check_acyclic(&self.upgrades)?;
+
+        for task in &self.tasks {
+            let item = || format!("task {}", task.name);
+            check(task.time_limit_ticks > 0, item, "time_limit_ticks must be positive")?;
+            if let TaskKind::IncidentResponse {
+                options, correct, ..
+            } = &task.kind
+            {
+                check(*correct < options.len(), item, "correct option out of range")?;
+            }
+        }
+        check(!self.tasks.is_empty(), || "tasks.json".into(), "needs at least one task")?;
+
+        for synergy in &self.synergies {
+            let item = || format!("synergy {:?} -> {:?}", synergy.source, synergy.effect);
+            check(kinds.contains(&synergy.source), item, "unknown source building")?;
+            if let SynergyEffect::Production(target) = synergy.effect {
+                check(kinds.contains(&target), item, "unknown target building")?;
+            }
+            check(synergy.strength >= 0.0, item, "strength can't be negative")?;
+        }
+
+        for event in &self.events {
+            let item = || format!("event {}", event.id);
+            check((0.0..=1.0).contains(&event.chance), item, "chance must be within 0..1")?;
+        }
+
+        for achievement in &self.achievements {
+            let item = || format!("achievement {}", achievement.id);
+            check(!achievement.name.is_empty(), item, "name can't be empty")?;
+            if let Some(AchievementReward::ProductionMultiplier(mult)) = &achievement.reward {
+                check(*mult >= 1.0, item, "production reward must be at least 1")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse<T: DeserializeOwned>(file: &str, json: &str) -> Result<Vec<T>, ContentError> {
+    serde_json::from_str(json).map_err(|e| ContentError::Parse {
+        file: file.to_string(),
+        message: e.to_string(),
+    })
+}
+
+pub(super) fn read_override<T: DeserializeOwned>(
+    dir: &Path,
+    file: &str,
+) -> Result<Option<Vec<T>>, ContentError> {
+    let path = dir.join(file);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json = std::fs::read_to_string(&path).map_err(|e| ContentError::Parse {
+        file: path.display().to_string(),
+        message: e.to_string(),
+    })?;
+    parse(&path.display().to_string(), &json).map(Some)
+}
+
+/// Replace entries whose key matches, append the rest.
+fn merge<T, K: PartialEq>(base: &mut Vec<T>, overrides: Vec<T>, key: impl Fn(&T) -> K) {
+    for item in overrides {
+        match base.iter().position(|b| key(b) == key(&item)) {
+            Some(index) => base[index] = item,
+            None => base.push(item),
+        }
+    }
+}
+
+fn unique(file: &'static str, ids: impl Iterator<Item = String>) -> Result<(), ContentError> {
+    let mut seen = HashSet::new();
+    for id in ids {
+        if !seen.insert(id.clone()) {
+            return Err(ContentError::DuplicateId { file, id });
+        }
+    }
+    Ok(())
+}
+
+fn check(
+    ok: bool,
+    item: impl Fn() -> String,
+    reason: &'static str,
+) -> Result<(), ContentError> {
+    if ok {
+        Ok(())
+    } else {
+        Err(ContentError::Invalid {
+            item: item(),
+            reason,
+        })
+    }
+}
+
+/// Depth-first search for a prerequisite cycle.
+fn check_acyclic(upgrades: &[Upgrade]) -> Result<(), ContentError> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        Visiting,
+        Done,
+    }
+
+    fn visit(
+        id: UpgradeId,
+        prereqs: &HashMap<UpgradeId, &[UpgradeId]>,
+        marks: &mut HashMap<UpgradeId, Mark>,
+    ) -> Result<(), ContentError> {
+        match marks.get(&id) {
+            Some(Mark::Done) => return Ok(()),
+            Some(Mark::Visiting) => return Err(ContentError::PrerequisiteCycle(id)),
+            None => {}
+        }
+        marks.insert(id, Mark::Visiting);
+        for prereq in prereqs.get(&id).copied().unwrap_or_default() {
+            visit(*prereq, prereqs, marks)?;
+        }
+        marks.insert(id, Mark::Done);
+        Ok(())
+    }
+
+    let prereqs: HashMap<UpgradeId, &[UpgradeId]> = upgrades
+        .iter()
+        .map(|u| (u.id, u.prerequisites.as_slice()))
+        .collect();
+    let mut marks = HashMap::new();
+    for upgrade in upgrades {
+        visit(upgrade.id, &prereqs, &mut marks)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_content_is_valid() {
+        let content = ContentRegistry::builtin().unwrap();
+        assert!(!content.buildings.is_empty());
+        assert!(!content.upgrades.is_empty());
+        assert!(!content.tasks.is_empty());
+        assert!(!content.achievements.is_empty());
+        assert!(!content.synergies.is_empty());
+    }
+
+    #[test]
+    fn test_validation_catches_bad_references() {
+        let mut content = ContentRegistry::builtin().unwrap();
+        content.upgrades[0].prerequisites = vec![9_999];
+        assert!(matches!(
+            content.validate(),
+            Err(ContentError::UnknownPrerequisite { .. })
+        ));
+
+        let mut content = ContentRegistry::builtin().unwrap();
+        let (a, b) = (content.upgrades[0].id, content.upgrades[1].id);
+        content.upgrades[0].prerequisites = vec![b];
+        content.upgrades[1].prerequisites = vec![a];
+        assert!(matches!(
+            content.validate(),
+            Err(ContentError::PrerequisiteCycle(_))
+        ));
+
+        let mut content = ContentRegistry::builtin().unwrap();
+        content.buildings[0].base_cost = 0.0;
+        assert!(matches!(content.validate(), Err(ContentError::Invalid { .. })));
+
+        let mut content = ContentRegistry::builtin().unwrap();
+        content.synergies[0].source = BuildingKind::intern("nowhere:Missing");
+        assert!(matches!(content.validate(), Err(ContentError::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_user_overrides_replace_and_extend() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("achievements.json"),
+            r#"[
+                {"id": "first_build", "name": "Hello, World!", "description": "Buy a building"},
+                {"id": "modded", "name": "Modder", "description": "Install a content pack"}
+            ]"#,
+        )
+        .unwrap();
+
+        let builtin = ContentRegistry::builtin().unwrap();
+        let content = builtin.clone().with_overrides(dir).unwrap();
+        assert_eq!(content.achievements.len(), builtin.achievements.len() + 1);
+        let first = content
+            .achievements
+            .iter()
+            .find(|a| a.id == "first_build")
+            .unwrap();
+        assert_eq!(first.name, "Hello, World!");
+    }
+
+    #[test]
+    fn test_invalid_override_is_rejected() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("upgrades.json"),
+            r#"[{"id": 500, "name": "Bad", "description": "", "cost": {"compute": 1.0},
+                 "prerequisites": [404], "effect": {"MultiplyAllProduction": 2.0}}]"#,
+        )
+        .unwrap();
+
+        let result = ContentRegistry::builtin().unwrap().with_overrides(dir);
+        assert!(matches!(
+            result,
+            Err(ContentError::UnknownPrerequisite { upgrade: 500, .. })
+        ));
+    }
+
+    #[test]
+    fn test_packs_extend_content_and_bad_packs_are_skipped() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let good = dir.join("quantum");
+        let bad = dir.join("broken");
+        std::fs::create_dir_all(&good).unwrap();
+        std::fs::create_dir_all(&bad).unwrap();
+        std::fs::write(good.join("pack.json"), r#"{"id": "quantum", "name": "Quantum"}"#).unwrap();
+        std::fs::write(
+            good.join("buildings.json"),
+            r#"[{"kind": "quantum:QubitRack", "name": "Qubit Rack", "base_cost": 1e9,
+                 "cost_multiplier": 1.15, "base_production": 1e6, "level_bonus": 0.5,
+                 "level_cost_growth": 2.0, "resource_type": "Compute",
+                 "unlock_threshold": 1e9, "tier": 6}]"#,
+        )
+        .unwrap();
+        std::fs::write(bad.join("pack.json"), r#"{"id": "broken", "name": "Broken"}"#).unwrap();
+        std::fs::write(
+            bad.join("upgrades.json"),
+            r#"[{"id": "broken:x", "name": "X", "cost": {"compute": 1.0},
+                 "effect": {"UnlockBuilding": "broken:Missing"}}]"#,
+        )
+        .unwrap();
+
+        let builtin = ContentRegistry::builtin().unwrap();
+        let content = builtin.clone().with_packs(dir);
+        assert_eq!(content.pack_ids(), vec!["quantum".to_string()]);
+        assert_eq!(content.buildings.len(), builtin.buildings.len() + 1);
+        let rack = content.buildings.last().unwrap();
+        assert_eq!(rack.kind, BuildingKind::intern("quantum:QubitRack"));
+        assert_eq!(rack.kind.namespace(), Some("quantum"));
+        assert_eq!(content.upgrades.len(), builtin.upgrades.len());
+    }
+}
@@ -0,0 +1,227 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use color_eyre::eyre::Result;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use super::buildings::ResourceType;
+use super::challenges::{ChallengeDef, RunModifiers, MINUTE_TICKS};
+use super::formulas;
+use super::resources::{format_si, Resources};
+use super::save;
+
+/// Challenge ids of the form `daily:2026-10-18`.
+pub const ID_PREFIX: &str = "daily:";
+
+const LEADERBOARD_FILE: &str = "daily_leaderboard.json";
+/// Plain-text copy of the leaderboard for pasting into team chat.
+const EXPORT_FILE: &str = "daily_leaderboard.md";
+
+const GOALS: [f64; 3] = [250_000.0, 500_000.0, 1_000_000.0];
+const EVENT_RATES: [f64; 3] = [1.0, 2.0, 3.0];
+
+/// Today's date as used for the daily challenge. UTC, so everyone gets
+/// the same challenge at the same time.
+pub fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+pub fn daily_id(date: NaiveDate) -> String {
+    format!("{ID_PREFIX}{date}")
+}
+
+/// The date of a daily challenge id, or `None` for other challenges.
+pub fn date_of(id: &str) -> Option<NaiveDate> {
+    id.strip_prefix(ID_PREFIX)?.parse().ok()
+}
+
+/// RNG seed for a day: a hash of `YYYYMMDD` (splitmix64 finalizer).
+pub fn daily_seed(date: NaiveDate) -> u64 {
+    let day = date.format("%Y%m%d").to_string().parse::<u64>().unwrap_or(0);
+    let mut z = day.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// The challenge for `date`. Rules and goal are picked from the day's
+/// seed, so everyone playing on the same day gets the same one.
+pub fn daily_challenge(date: NaiveDate) -> ChallengeDef {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(daily_seed(date));
+    let goal_amount = GOALS[rng.gen_range(0..GOALS.len())];
+    let modifiers = RunModifiers {
+        no_upgrades: rng.gen_bool(0.25),
+        event_rate: EVENT_RATES[rng.gen_range(0..EVENT_RATES.len())],
+        no_offline: true,
+        no_bonuses: true,
+        time_limit_ticks: Some(rng.gen_range(20..=40) * MINUTE_TICKS),
+        ..Default::default()
+    };
+    ChallengeDef {
+        id: daily_id(date),
+        name: format!("Daily {date}"),
+        description: format!(
+            "Reach {} compute; same events and tasks for everyone today",
+            format_si(goal_amount)
+        ),
+        modifiers,
+        start: Resources {
+            compute: 50.0,
+            ..Default::default()
+        },
+        goal: ResourceType::Compute,
+        goal_amount,
+        reputation: 1.0,
+    }
+}
+
+/// One finished daily run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyResult {
+    pub date: NaiveDate,
+    /// Ticks from start to reaching the goal.
+    pub ticks: u64,
+    pub score: u64,
+    pub finished_at: DateTime<Utc>,
+}
+
+impl DailyResult {
+    pub fn new(date: NaiveDate, ticks: u64, limit_ticks: u64) -> Self {
+        Self {
+            date,
+            ticks,
+            score: formulas::daily_score(ticks, limit_ticks),
+            finished_at: Utc::now(),
+        }
+    }
+
+    pub fn time(&self) -> String {
+        let secs = self.ticks / 4;
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    }
+}
+
+/// Every daily result recorded on this machine.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub results: Vec<DailyResult>,
+}
+
+impl Leaderboard {
+    pub fn record(&mut self, result: DailyResult) {
+        self.results.push(result);
+        self.results
+            .sort_by(|a, b| b.date.cmp(&a.date).then(b.score.cmp(&a.score)));
+    }
+
+    /// Best result for `date`.
+    pub fn best(&self, date: NaiveDate) -> Option<&DailyResult> {
+        self.results.iter().find(|r| r.date == date)
+    }
+
+    /// Best result of each day, newest first.
+    pub fn best_per_day(&self) -> Vec<&DailyResult> {
+        let mut best: Vec<&DailyResult> = Vec::new();
+        for result in &self.results {
+            if best.last().is_none_or(|last| last.date != result.date) {
+                best.push(result);
+            }
+        }
+        best
+    }
+
+    /// Markdown table of each day's best run, ready to paste into chat.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("| Date | Time | Score |\n|---|---|---|\n");
+        for result in self.best_per_day() {
+            writeln!(out, "| {} | {} | {} |", result.date, result.time(), result.score).ok();
+        }
+        out
+    }
+}
+
+pub fn leaderboard_path() -> PathBuf {
+    save::data_dir().join(LEADERBOARD_FILE)
+}
+
+/// The leaderboard in the data dir; empty if there is none yet.
+pub fn load() -> Leaderboard {
+    load_from(&leaderboard_path())
+}
+
+pub fn load_from(path: &Path) -> Leaderboard {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Add results to the leaderboard in the data dir.
+pub fn record(results: Vec<DailyResult>) -> Result<()> {
+    record_to(&leaderboard_path(), results)
+}
+
+pub fn record_to(path: &Path, results: Vec<DailyResult>) -> Result<()> {
+    if results.is_empty() {
+        return Ok(());
+    }
+    let mut leaderboard = load_from(path);
+    for result in results {
+        leaderboard.record(result);
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&leaderboard)?)?;
+    Ok(())
+}
+
+/// Write the leaderboard as Markdown next to the save and return its path.
+pub fn export() -> Result<PathBuf> {
+    let path = save::data_dir().join(EXPORT_FILE);
+    std::fs::write(&path, load().to_markdown())?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_daily_challenge_is_stable_per_day() {
+        let day = date("2026-10-18");
+        let a = daily_challenge(day);
+        let b = daily_challenge(day);
+        assert_eq!(a.id, "daily:2026-10-18");
+        assert_eq!(a.modifiers, b.modifiers);
+        assert_eq!(a.goal_amount, b.goal_amount);
+        assert_eq!(date_of(&a.id), Some(day));
+        assert_ne!(daily_seed(day), daily_seed(date("2026-10-19")));
+        assert!(a.modifiers.no_offline && a.modifiers.no_bonuses);
+    }
+
+    #[test]
+    fn test_leaderboard_roundtrip_and_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LEADERBOARD_FILE);
+        let day = date("2026-10-18");
+        record_to(
+            &path,
+            vec![
+                DailyResult::new(day, 4_000, 8_000),
+                DailyResult::new(day, 2_000, 8_000),
+                DailyResult::new(date("2026-10-17"), 6_000, 8_000),
+            ],
+        )
+        .unwrap();
+
+        let leaderboard = load_from(&path);
+        assert_eq!(leaderboard.results.len(), 3);
+        assert_eq!(leaderboard.best(day).unwrap().ticks, 2_000);
+        assert_eq!(leaderboard.best_per_day().len(), 2);
+        let markdown = leaderboard.to_markdown();
+        assert!(markdown.contains("| 2026-10-18 | 8m 20s | 4000 |"));
+    }
+}
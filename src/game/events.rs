@@ -1,13 +1,29 @@
+use chrono::{DateTime, Utc};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use super::buildings::BuildingKind;
-use super::resources::Resources;
+use super::formulas;
+use super::resources::{format_cost, Resources};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameEvent {
     pub kind: GameEventKind,
     pub tick: u64,
+    /// Wall-clock time the event happened. Saves from before this field
+    /// existed get the load time.
+    #[serde(default = "Utc::now")]
+    pub timestamp: DateTime<Utc>,
+}
+
+impl GameEvent {
+    pub fn new(kind: GameEventKind, tick: u64) -> Self {
+        Self {
+            kind,
+            tick,
+            timestamp: Utc::now(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +36,98 @@ pub enum GameEventKind {
     HardwareFailure(BuildingKind),
     BonusDrop { resource: BonusResource, amount: f64 },
     OpenSourceContribution { bonus_reputation: f64 },
+    CapacityExceeded { throttle: f64 },
+    MarketCrash { drop: f64 },
+    MarketBubble { rise: f64 },
+    /// An event defined by a content pack.
+    Custom { id: String, message: String, effect: Resources, good: bool },
+    // Player actions
+    BuildingPurchased(BuildingKind),
+    BuildingUpgraded { kind: BuildingKind, level: u32 },
+    UpgradePurchased { name: String },
+    PurchaseFailed { item: String, reason: String },
+    ResearchStarted { name: String },
+    ResearchCancelled { name: String },
+    CryptoTraded { sold: Resources, bought: Resources },
+    TaskCompleted { name: String },
+    TaskFailed { name: String },
+    TaskExpired { name: String },
+    AchievementUnlocked { name: String },
+    Prestige { reputation: f64 },
+    Ipo { equity: f64 },
+    IncidentDetected { name: String, severity: u8 },
+    IncidentEscalated { name: String, severity: u8 },
+    IncidentMitigated { name: String },
+    IncidentResolved { name: String, seconds: f64 },
+    ChallengeStarted { name: String },
+    ChallengeCompleted { name: String, reputation: f64 },
+    ChallengeFailed { name: String },
+}
+
+/// A random event added by a content pack.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomEventDef {
+    pub id: String,
+    pub message: String,
+    /// Chance per tick of the event firing.
+    pub chance: f64,
+    /// Resources gained (or lost, if negative) when it fires.
+    #[serde(default)]
+    pub effect: Resources,
+    #[serde(default)]
+    pub good: bool,
+}
+
+/// How long a server overload throttles the affected building, and by how much.
+const OVERLOAD_TICKS: u32 = 40; // 10 seconds
+const OVERLOAD_MULTIPLIER: f64 = 0.5;
+/// How long a hardware failure takes the affected building offline.
+const HARDWARE_FAILURE_TICKS: u32 = 60; // 15 seconds
+
+/// A temporary production modifier left behind by an event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveEffect {
+    pub kind: EffectKind,
+    pub remaining_ticks: u32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum EffectKind {
+    Overloaded(BuildingKind),
+    Offline(BuildingKind),
+}
+
+impl ActiveEffect {
+    /// The lasting effect of an event, if it has one.
+    pub fn from_event(event: &GameEventKind) -> Option<Self> {
+        match event {
+            GameEventKind::ServerOverloaded(kind) => Some(Self {
+                kind: EffectKind::Overloaded(*kind),
+                remaining_ticks: OVERLOAD_TICKS,
+            }),
+            GameEventKind::HardwareFailure(kind) => Some(Self {
+                kind: EffectKind::Offline(*kind),
+                remaining_ticks: HARDWARE_FAILURE_TICKS,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Multiplier this effect applies to a building's production.
+    pub fn production_multiplier(&self, building: BuildingKind) -> f64 {
+        match self.kind {
+            EffectKind::Overloaded(kind) if kind == building => OVERLOAD_MULTIPLIER,
+            EffectKind::Offline(kind) if kind == building => 0.0,
+            _ => 1.0,
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self.kind {
+            EffectKind::Overloaded(kind) => format!("{:?} overloaded", kind),
+            EffectKind::Offline(kind) => format!("{:?} offline", kind),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,6 +169,137 @@ impl GameEventKind {
             GameEventKind::OpenSourceContribution { bonus_reputation } => {
                 format!("Open source PR merged! +{:.0} reputation", bonus_reputation)
             }
+            GameEventKind::CapacityExceeded { throttle } => format!(
+                "Power/cooling over capacity - output throttled to {:.0}%",
+                throttle * 100.0
+            ),
+            GameEventKind::MarketCrash { drop } => {
+                format!("Crypto market crash! BTC down {:.0}%", drop * 100.0)
+            }
+            GameEventKind::MarketBubble { rise } => {
+                format!("Crypto bubble! BTC up {:.0}%", rise * 100.0)
+            }
+            GameEventKind::Custom { message, .. } => message.clone(),
+            GameEventKind::CryptoTraded { sold, bought } => {
+                format!("Traded {} for {}", format_cost(sold), format_cost(bought))
+            }
+            GameEventKind::BuildingPurchased(kind) => format!("Purchased {:?}", kind),
+            GameEventKind::BuildingUpgraded { kind, level } => {
+                if formulas::milestone_multiplier(*level) > formulas::milestone_multiplier(level.saturating_sub(1)) {
+                    format!("Upgraded {:?} to Lv.{} - milestone bonus!", kind, level)
+                } else {
+                    format!("Upgraded {:?} to Lv.{}", kind, level)
+                }
+            }
+            GameEventKind::UpgradePurchased { name } => format!("Researched {}", name),
+            GameEventKind::ResearchStarted { name } => format!("Started researching {}", name),
+            GameEventKind::ResearchCancelled { name } => {
+                format!("Cancelled research of {} (refunded)", name)
+            }
+            GameEventKind::PurchaseFailed { item, reason } => {
+                format!("Can't buy {}: {}", item, reason)
+            }
+            GameEventKind::TaskCompleted { name } => format!("Task completed: {}", name),
+            GameEventKind::TaskFailed { name } => format!("Task failed: {}", name),
+            GameEventKind::TaskExpired { name } => format!("Task expired: {}", name),
+            GameEventKind::AchievementUnlocked { name } => {
+                format!("Achievement unlocked: {}", name)
+            }
+            GameEventKind::Prestige { reputation } => {
+                format!("Prestige! +{:.0} reputation", reputation)
+            }
+            GameEventKind::Ipo { equity } => format!("IPO! +{:.0} equity", equity),
+            GameEventKind::IncidentDetected { name, severity } => {
+                format!("Incident detected: {} (severity {}) - respond in the task terminal", name, severity)
+            }
+            GameEventKind::IncidentEscalated { name, severity } => {
+                format!("Incident escalated: {} now severity {}", name, severity)
+            }
+            GameEventKind::IncidentMitigated { name } => format!("Incident mitigated: {}", name),
+            GameEventKind::IncidentResolved { name, seconds } => {
+                format!("Incident resolved: {} after {:.0}s", name, seconds)
+            }
+            GameEventKind::ChallengeStarted { name } => format!("Challenge started: {}", name),
+            GameEventKind::ChallengeCompleted { name, reputation } => {
+                format!("Challenge complete: {}! +{:.0} reputation", name, reputation)
+            }
+            GameEventKind::ChallengeFailed { name } => format!("Challenge failed: {}", name),
+        }
+    }
+
+    /// Short machine-friendly name, used by the journal query CLI.
+    pub fn slug(&self) -> &'static str {
+        match self {
+            GameEventKind::ServerOverloaded(_) => "server_overloaded",
+            GameEventKind::DDoSAttack { .. } => "ddos",
+            GameEventKind::ViralRepo { .. } => "viral_repo",
+            GameEventKind::SecurityBreach { .. } => "security_breach",
+            GameEventKind::TrafficSpike { .. } => "traffic_spike",
+            GameEventKind::HardwareFailure(_) => "hardware_failure",
+            GameEventKind::BonusDrop { .. } => "bonus_drop",
+            GameEventKind::OpenSourceContribution { .. } => "open_source",
+            GameEventKind::CapacityExceeded { .. } => "capacity_exceeded",
+            GameEventKind::MarketCrash { .. } => "market_crash",
+            GameEventKind::MarketBubble { .. } => "market_bubble",
+            GameEventKind::Custom { .. } => "custom",
+            GameEventKind::CryptoTraded { .. } => "crypto_traded",
+            GameEventKind::BuildingPurchased(_) => "building_purchased",
+            GameEventKind::BuildingUpgraded { .. } => "building_upgraded",
+            GameEventKind::UpgradePurchased { .. } => "upgrade_purchased",
+            GameEventKind::PurchaseFailed { .. } => "purchase_failed",
+            GameEventKind::ResearchStarted { .. } => "research_started",
+            GameEventKind::ResearchCancelled { .. } => "research_cancelled",
+            GameEventKind::TaskCompleted { .. } => "task_completed",
+            GameEventKind::TaskFailed { .. } => "task_failed",
+            GameEventKind::TaskExpired { .. } => "task_expired",
+            GameEventKind::AchievementUnlocked { .. } => "achievement",
+            GameEventKind::Prestige { .. } => "prestige",
+            GameEventKind::Ipo { .. } => "ipo",
+            GameEventKind::IncidentDetected { .. } => "incident_detected",
+            GameEventKind::IncidentEscalated { .. } => "incident_escalated",
+            GameEventKind::IncidentMitigated { .. } => "incident_mitigated",
+            GameEventKind::IncidentResolved { .. } => "incident_resolved",
+            GameEventKind::ChallengeStarted { .. } => "challenge_started",
+            GameEventKind::ChallengeCompleted { .. } => "challenge_completed",
+            GameEventKind::ChallengeFailed { .. } => "challenge_failed",
+        }
+    }
+
+    pub fn category(&self) -> EventCategory {
+        match self {
+            GameEventKind::ServerOverloaded(_)
+            | GameEventKind::DDoSAttack { .. }
+            | GameEventKind::SecurityBreach { .. }
+            | GameEventKind::HardwareFailure(_)
+            | GameEventKind::CapacityExceeded { .. }
+            | GameEventKind::MarketCrash { .. }
+            | GameEventKind::Custom { good: false, .. } => EventCategory::Incident,
+            GameEventKind::ViralRepo { .. }
+            | GameEventKind::TrafficSpike { .. }
+            | GameEventKind::BonusDrop { .. }
+            | GameEventKind::OpenSourceContribution { .. }
+            | GameEventKind::MarketBubble { .. }
+            | GameEventKind::Custom { good: true, .. } => EventCategory::Bonus,
+            GameEventKind::BuildingPurchased(_)
+            | GameEventKind::BuildingUpgraded { .. }
+            | GameEventKind::UpgradePurchased { .. }
+            | GameEventKind::PurchaseFailed { .. }
+            | GameEventKind::ResearchStarted { .. }
+            | GameEventKind::ResearchCancelled { .. }
+            | GameEventKind::CryptoTraded { .. } => EventCategory::Purchase,
+            GameEventKind::TaskCompleted { .. }
+            | GameEventKind::TaskFailed { .. }
+            | GameEventKind::TaskExpired { .. }
+            | GameEventKind::IncidentDetected { .. }
+            | GameEventKind::IncidentEscalated { .. }
+            | GameEventKind::IncidentMitigated { .. }
+            | GameEventKind::IncidentResolved { .. } => EventCategory::Task,
+            GameEventKind::AchievementUnlocked { .. } => EventCategory::Achievement,
+            GameEventKind::Prestige { .. }
+            | GameEventKind::Ipo { .. }
+            | GameEventKind::ChallengeStarted { .. }
+            | GameEventKind::ChallengeCompleted { .. }
+            | GameEventKind::ChallengeFailed { .. } => EventCategory::Prestige,
         }
     }
 
@@ -74,17 +313,95 @@ impl GameEventKind {
             GameEventKind::HardwareFailure(_) => EventSeverity::Warning,
             GameEventKind::BonusDrop { .. } => EventSeverity::Good,
             GameEventKind::OpenSourceContribution { .. } => EventSeverity::Good,
+            GameEventKind::CapacityExceeded { .. } => EventSeverity::Warning,
+            GameEventKind::MarketCrash { .. } => EventSeverity::Error,
+            GameEventKind::MarketBubble { .. } => EventSeverity::Good,
+            GameEventKind::Custom { good: true, .. } => EventSeverity::Good,
+            GameEventKind::Custom { good: false, .. } => EventSeverity::Warning,
+            GameEventKind::BuildingPurchased(_)
+            | GameEventKind::BuildingUpgraded { .. }
+            | GameEventKind::UpgradePurchased { .. }
+            | GameEventKind::ResearchStarted { .. }
+            | GameEventKind::ResearchCancelled { .. }
+            | GameEventKind::CryptoTraded { .. } => EventSeverity::Info,
+            GameEventKind::PurchaseFailed { .. } => EventSeverity::Warning,
+            GameEventKind::TaskCompleted { .. } => EventSeverity::Good,
+            GameEventKind::TaskFailed { .. } | GameEventKind::TaskExpired { .. } => {
+                EventSeverity::Warning
+            }
+            GameEventKind::IncidentDetected { .. } | GameEventKind::IncidentEscalated { .. } => {
+                EventSeverity::Error
+            }
+            GameEventKind::IncidentMitigated { .. } => EventSeverity::Good,
+            GameEventKind::IncidentResolved { .. } => EventSeverity::Info,
+            GameEventKind::AchievementUnlocked { .. } => EventSeverity::Good,
+            GameEventKind::Prestige { .. } | GameEventKind::Ipo { .. } => EventSeverity::Good,
+            GameEventKind::ChallengeStarted { .. } => EventSeverity::Info,
+            GameEventKind::ChallengeCompleted { .. } => EventSeverity::Good,
+            GameEventKind::ChallengeFailed { .. } => EventSeverity::Warning,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EventSeverity {
+    Info,
     Good,
     Warning,
     Error,
 }
 
+impl EventSeverity {
+    pub const ALL: [EventSeverity; 4] = [
+        EventSeverity::Info,
+        EventSeverity::Good,
+        EventSeverity::Warning,
+        EventSeverity::Error,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EventSeverity::Info => "info",
+            EventSeverity::Good => "good",
+            EventSeverity::Warning => "warn",
+            EventSeverity::Error => "error",
+        }
+    }
+}
+
+/// Broad grouping of event kinds, used for filtering the log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventCategory {
+    Incident,
+    Bonus,
+    Purchase,
+    Task,
+    Achievement,
+    Prestige,
+}
+
+impl EventCategory {
+    pub const ALL: [EventCategory; 6] = [
+        EventCategory::Incident,
+        EventCategory::Bonus,
+        EventCategory::Purchase,
+        EventCategory::Task,
+        EventCategory::Achievement,
+        EventCategory::Prestige,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EventCategory::Incident => "incident",
+            EventCategory::Bonus => "bonus",
+            EventCategory::Purchase => "purchase",
+            EventCategory::Task => "task",
+            EventCategory::Achievement => "achievement",
+            EventCategory::Prestige => "prestige",
+        }
+    }
+}
+
 /// Apply the immediate effect of a game event to resources.
 pub fn apply_event(event: &GameEventKind, resources: &mut Resources) {
     match event {
@@ -106,24 +423,30 @@ pub fn apply_event(event: &GameEventKind, resources: &mut Resources) {
         GameEventKind::OpenSourceContribution { bonus_reputation } => {
             resources.reputation += bonus_reputation;
         }
+        GameEventKind::Custom { effect, .. } => {
+            resources.add(effect);
+            resources.clamp_non_negative();
+        }
         // TrafficSpike, ServerOverloaded, HardwareFailure have duration-based
-        // effects handled separately via active_effects in GameState
+        // effects handled separately in GameState (see ActiveEffect)
         _ => {}
     }
 }
 
-/// Probability of any event firing per tick. Scales with monitoring stacks.
+/// Probability of any event firing per tick.
 const BASE_EVENT_CHANCE: f64 = 0.005; // ~2% per second at 4Hz
-const MONITORING_BONUS: f64 = 0.002;
 
 /// Try to generate a random event based on current game state.
+/// `event_rate` scales the chance of anything happening (challenge runs).
+/// `failure_odds` is only asked once an overload or failure is rolled.
 pub fn maybe_generate_event(
     rng: &mut impl Rng,
     tick: u64,
-    monitoring_count: u32,
     total_compute: f64,
+    event_rate: f64,
+    failure_odds: impl Fn(BuildingKind) -> f64,
 ) -> Option<GameEvent> {
-    let chance = BASE_EVENT_CHANCE + (monitoring_count as f64 * MONITORING_BONUS);
+    let chance = BASE_EVENT_CHANCE * event_rate;
     if rng.gen::<f64>() >= chance {
         return None;
     }
@@ -176,7 +499,30 @@ pub fn maybe_generate_event(
         GameEventKind::HardwareFailure(BuildingKind::VPS)
     };
 
-    Some(GameEvent { kind, tick })
+    // Monitoring can catch overloads and failures before they land
+    if let GameEventKind::ServerOverloaded(target) | GameEventKind::HardwareFailure(target) = &kind {
+        if rng.gen::<f64>() >= failure_odds(*target) {
+            return None;
+        }
+    }
+
+    Some(GameEvent::new(kind, tick))
+}
+
+/// Roll each pack event; the first one that hits fires.
+pub fn maybe_generate_custom_event(
+    rng: &mut impl Rng,
+    tick: u64,
+    defs: &[CustomEventDef],
+) -> Option<GameEvent> {
+    let def = defs.iter().find(|def| rng.gen::<f64>() < def.chance)?;
+    let kind = GameEventKind::Custom {
+        id: def.id.clone(),
+        message: def.message.clone(),
+        effect: def.effect.clone(),
+        good: def.good,
+    };
+    Some(GameEvent::new(kind, tick))
 }
 
 #[cfg(test)]
@@ -213,6 +559,21 @@ mod tests {
         assert!(resources.bandwidth >= 0.0);
     }
 
+    #[test]
+    fn test_active_effects() {
+        let overload =
+            ActiveEffect::from_event(&GameEventKind::ServerOverloaded(BuildingKind::RaspberryPi))
+                .unwrap();
+        assert_eq!(overload.production_multiplier(BuildingKind::RaspberryPi), 0.5);
+        assert_eq!(overload.production_multiplier(BuildingKind::VPS), 1.0);
+
+        let failure =
+            ActiveEffect::from_event(&GameEventKind::HardwareFailure(BuildingKind::VPS)).unwrap();
+        assert_eq!(failure.production_multiplier(BuildingKind::VPS), 0.0);
+
+        assert!(ActiveEffect::from_event(&GameEventKind::DDoSAttack { severity: 1 }).is_none());
+    }
+
     #[test]
     fn test_event_descriptions() {
         let events = vec![
@@ -224,4 +585,23 @@ mod tests {
             assert!(!event.description().is_empty());
         }
     }
+
+    #[test]
+    fn test_player_action_categories() {
+        let purchase = GameEventKind::BuildingPurchased(BuildingKind::RaspberryPi);
+        assert_eq!(purchase.category(), EventCategory::Purchase);
+        assert_eq!(purchase.severity_color(), EventSeverity::Info);
+
+        let expired = GameEventKind::TaskExpired { name: "Test".into() };
+        assert_eq!(expired.category(), EventCategory::Task);
+        assert_eq!(expired.severity_color(), EventSeverity::Warning);
+    }
+
+    #[test]
+    fn test_event_timestamp_defaults_on_old_saves() {
+        let json = r#"{"kind":{"DDoSAttack":{"severity":3}},"tick":42}"#;
+        let event: GameEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(event.tick, 42);
+        assert!(event.timestamp <= Utc::now());
+    }
 }
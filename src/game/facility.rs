@@ -0,0 +1,70 @@
+use super::buildings::FacilityStats;
+use super::formulas;
+
+/// Power and cooling available before any facilities are built.
+pub const BASE_CAPACITY: FacilityStats = FacilityStats::new(5.0, 5.0);
+
+/// Power and cooling supplied by facilities versus drawn by buildings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FacilityLoad {
+    pub capacity: FacilityStats,
+    pub demand: FacilityStats,
+}
+
+impl Default for FacilityLoad {
+    fn default() -> Self {
+        Self {
+            capacity: BASE_CAPACITY,
+            demand: FacilityStats::NONE,
+        }
+    }
+}
+
+impl FacilityLoad {
+    pub fn add_capacity(&mut self, supply: FacilityStats, units: f64) {
+        self.capacity.power += supply.power * units;
+        self.capacity.cooling += supply.cooling * units;
+    }
+
+    pub fn add_demand(&mut self, draw: FacilityStats, units: f64) {
+        self.demand.power += draw.power * units;
+        self.demand.cooling += draw.cooling * units;
+    }
+
+    pub fn power_throttle(&self) -> f64 {
+        formulas::capacity_throttle(self.capacity.power, self.demand.power)
+    }
+
+    pub fn cooling_throttle(&self) -> f64 {
+        formulas::capacity_throttle(self.capacity.cooling, self.demand.cooling)
+    }
+
+    /// Production multiplier for every building: the tighter of the two.
+    pub fn throttle(&self) -> f64 {
+        self.power_throttle().min(self.cooling_throttle())
+    }
+
+    pub fn is_throttled(&self) -> bool {
+        self.throttle() < 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle_uses_tighter_constraint() {
+        let mut load = FacilityLoad::default();
+        assert_eq!(load.throttle(), 1.0);
+
+        load.add_demand(FacilityStats::new(10.0, 6.0), 1.0);
+        assert_eq!(load.power_throttle(), 0.5);
+        assert!((load.cooling_throttle() - 5.0 / 6.0).abs() < 1e-9);
+        assert_eq!(load.throttle(), 0.5);
+        assert!(load.is_throttled());
+
+        load.add_capacity(FacilityStats::new(5.0, 0.0), 1.0);
+        assert!((load.throttle() - 5.0 / 6.0).abs() < 1e-9);
+    }
+}
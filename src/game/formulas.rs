@@ -4,6 +4,35 @@ pub fn building_cost(base_cost: f64, cost_multiplier: f64, count: u32) -> f64 {
     base_cost * cost_multiplier.powi(count as i32)
 }
 
+/// Building levels that grant an extra production multiplier once reached.
+pub const LEVEL_MILESTONES: [(u32, f64); 3] = [(5, 1.5), (10, 2.0), (25, 3.0)];
+
+/// Cost of buying level `level + 1` of a building.
+/// cost(level) = base_cost * 10 * growth^level
+pub fn level_cost(base_cost: f64, growth: f64, level: u32) -> f64 {
+    base_cost * 10.0 * growth.powi(level as i32)
+}
+
+/// Product of the milestone bonuses reached at `level`.
+pub fn milestone_multiplier(level: u32) -> f64 {
+    LEVEL_MILESTONES
+        .iter()
+        .filter(|(at, _)| level >= *at)
+        .map(|(_, mult)| mult)
+        .product()
+}
+
+/// The first milestone above `level`, if any.
+pub fn next_milestone(level: u32) -> Option<(u32, f64)> {
+    LEVEL_MILESTONES.iter().copied().find(|(at, _)| *at > level)
+}
+
+/// Production multiplier from building level:
+/// (1 + level_bonus * level) * milestone bonuses
+pub fn level_multiplier(level: u32, level_bonus: f64) -> f64 {
+    (1.0 + level_bonus * level as f64) * milestone_multiplier(level)
+}
+
 /// Calculate production per tick for a building type.
 /// production = count * base_production * (1 + level_bonus * level) * global_multiplier
 pub fn building_production(
@@ -13,7 +42,84 @@ pub fn building_production(
     level_bonus: f64,
     global_multiplier: f64,
 ) -> f64 {
-    count as f64 * base_production * (1.0 + level_bonus * level as f64) * global_multiplier
+    count as f64 * base_production * level_multiplier(level, level_bonus) * global_multiplier
+}
+
+/// Share of full output possible when `demand` exceeds `capacity`.
+pub fn capacity_throttle(capacity: f64, demand: f64) -> f64 {
+    if demand <= capacity {
+        1.0
+    } else {
+        (capacity / demand).max(0.0)
+    }
+}
+
+/// Share of upkeep `demand` that `available` resources can pay.
+pub fn upkeep_satisfaction(available: f64, demand: f64) -> f64 {
+    if demand <= 0.0 {
+        1.0
+    } else {
+        (available / demand).clamp(0.0, 1.0)
+    }
+}
+
+/// Production multiplier from `sources` buildings each adding `strength`,
+/// with diminishing returns.
+pub fn synergy_multiplier(strength: f64, sources: u32) -> f64 {
+    1.0 + strength * (1.0 + sources as f64).ln()
+}
+
+/// Chance an incident still happens with `sources` buildings guarding
+/// against it.
+pub fn failure_odds(strength: f64, sources: u32) -> f64 {
+    1.0 / (1.0 + strength * sources as f64)
+}
+
+/// Next trend of the crypto price: the old trend decays and the price is
+/// pulled back towards `base_price`.
+pub fn market_trend(trend: f64, price: f64, base_price: f64) -> f64 {
+    trend * 0.95 + 0.002 * (base_price / price).ln()
+}
+
+/// Next crypto price after a step of `trend` plus random `noise`.
+pub fn market_price(price: f64, trend: f64, noise: f64) -> f64 {
+    (price * (trend + noise).exp()).max(1.0)
+}
+
+/// Ticks needed to research an upgrade with the given total cost.
+/// seconds = 5 * log10(cost)^1.5, so 500 takes ~22s and 1T ~3.5m.
+pub fn research_ticks(cost: f64) -> u64 {
+    let seconds = 5.0 * cost.max(10.0).log10().powf(1.5);
+    (seconds * 4.0).round() as u64
+}
+
+/// Seconds until `missing` more of a resource accrues at `rate_per_sec`.
+/// Zero if nothing is missing, `None` if it never will at this rate.
+pub fn time_to_afford(missing: f64, rate_per_sec: f64) -> Option<f64> {
+    if missing <= 0.0 {
+        Some(0.0)
+    } else if rate_per_sec > 0.0 {
+        Some(missing / rate_per_sec)
+    } else {
+        None
+    }
+}
+
+/// Ticks before monitoring notices an incident: 10s, 2s less per
+/// MonitoringStack.
+pub fn detection_delay_ticks(monitoring: u32) -> u64 {
+    40u64.saturating_sub(8 * monitoring as u64)
+}
+
+/// Share of bandwidth an open incident drains per tick.
+pub fn incident_drain(severity: u8) -> f64 {
+    0.001 * severity as f64
+}
+
+/// Daily challenge score: 1000 for using the whole time limit, more the
+/// faster the goal is reached.
+pub fn daily_score(ticks: u64, limit_ticks: u64) -> u64 {
+    (1000.0 * limit_ticks as f64 / ticks.max(1) as f64).round() as u64
 }
 
 #[cfg(test)]
@@ -29,6 +135,89 @@ mod tests {
         assert!((building_cost(base, mult, 10) - 10.0 * 1.15_f64.powi(10)).abs() < 0.01);
     }
 
+    #[test]
+    fn test_level_cost() {
+        assert!((level_cost(10.0, 2.0, 0) - 100.0).abs() < 0.001);
+        assert!((level_cost(10.0, 2.0, 3) - 800.0).abs() < 0.001);
+        assert!((level_cost(10.0, 2.5, 2) - 625.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_level_milestones() {
+        assert_eq!(milestone_multiplier(4), 1.0);
+        assert_eq!(milestone_multiplier(5), 1.5);
+        assert_eq!(milestone_multiplier(10), 3.0);
+        assert_eq!(next_milestone(0), Some((5, 1.5)));
+        assert_eq!(next_milestone(5), Some((10, 2.0)));
+        assert_eq!(next_milestone(25), None);
+        // Level 5, bonus 0.5: (1 + 2.5) * 1.5
+        assert!((level_multiplier(5, 0.5) - 5.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_capacity_throttle() {
+        assert_eq!(capacity_throttle(10.0, 5.0), 1.0);
+        assert_eq!(capacity_throttle(10.0, 20.0), 0.5);
+        assert_eq!(capacity_throttle(0.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn test_upkeep_satisfaction() {
+        assert_eq!(upkeep_satisfaction(5.0, 0.0), 1.0);
+        assert_eq!(upkeep_satisfaction(10.0, 4.0), 1.0);
+        assert_eq!(upkeep_satisfaction(1.0, 4.0), 0.25);
+        assert_eq!(upkeep_satisfaction(-1.0, 4.0), 0.0);
+    }
+
+    #[test]
+    fn test_synergy_formulas() {
+        assert_eq!(synergy_multiplier(0.25, 0), 1.0);
+        let one = synergy_multiplier(0.25, 1) - 1.0;
+        let ten = synergy_multiplier(0.25, 10) - 1.0;
+        assert!(ten > one && ten < one * 10.0);
+
+        assert_eq!(failure_odds(0.2, 0), 1.0);
+        assert_eq!(failure_odds(0.2, 5), 0.5);
+    }
+
+    #[test]
+    fn test_market_reverts_to_base() {
+        assert!(market_trend(0.0, 500.0, 1_000.0) > 0.0);
+        assert!(market_trend(0.0, 2_000.0, 1_000.0) < 0.0);
+        assert_eq!(market_trend(0.0, 1_000.0, 1_000.0), 0.0);
+        assert_eq!(market_price(1_000.0, 0.0, 0.0), 1_000.0);
+        assert!(market_price(1_000.0, 0.01, 0.0) > 1_000.0);
+    }
+
+    #[test]
+    fn test_research_ticks() {
+        assert_eq!(research_ticks(0.0), 20); // 5s minimum
+        assert!(research_ticks(500.0) < research_ticks(5_000.0));
+        assert!((research_ticks(1e12) as f64 / 4.0 - 5.0 * 12f64.powf(1.5)).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_time_to_afford() {
+        assert_eq!(time_to_afford(-5.0, 0.0), Some(0.0));
+        assert_eq!(time_to_afford(10.0, 2.0), Some(5.0));
+        assert_eq!(time_to_afford(10.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_incident_formulas() {
+        assert_eq!(detection_delay_ticks(0), 40);
+        assert_eq!(detection_delay_ticks(2), 24);
+        assert_eq!(detection_delay_ticks(10), 0);
+        assert!(incident_drain(5) > incident_drain(1));
+    }
+
+    #[test]
+    fn test_daily_score() {
+        assert_eq!(daily_score(8_000, 8_000), 1000);
+        assert_eq!(daily_score(2_000, 8_000), 4000);
+        assert_eq!(daily_score(0, 8_000), 8_000_000);
+    }
+
     #[test]
     fn test_building_production() {
         // 5 buildings, 1.0 base production, level 2, 0.5 level bonus, 1.0 global
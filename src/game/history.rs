@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use super::resources::Resources;
+
+/// Samples kept per range.
+const MINUTE_SAMPLES: usize = 60; // 1s resolution
+const HOUR_SAMPLES: usize = 60; // 1m resolution
+const DAY_SAMPLES: usize = 96; // 15m resolution
+
+/// Number of 1s samples averaged into one hour/day sample.
+const HOUR_BUCKET: u32 = 60;
+const DAY_BUCKET: u32 = 15 * 60;
+
+/// Resource balances and production (per tick) at a point in time.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Sample {
+    pub resources: Resources,
+    pub production: Resources,
+}
+
+impl Sample {
+    fn add(&mut self, other: &Sample) {
+        self.resources.add(&other.resources);
+        self.production.add(&other.production);
+    }
+
+    fn scaled(&self, factor: f64) -> Sample {
+        Sample {
+            resources: self.resources.scaled(factor),
+            production: self.production.scaled(factor),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryRange {
+    Minute,
+    Hour,
+    Day,
+}
+
+impl HistoryRange {
+    pub fn next(self) -> Self {
+        match self {
+            HistoryRange::Minute => HistoryRange::Hour,
+            HistoryRange::Hour => HistoryRange::Day,
+            HistoryRange::Day => HistoryRange::Minute,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HistoryRange::Minute => "1m",
+            HistoryRange::Hour => "1h",
+            HistoryRange::Day => "24h",
+        }
+    }
+}
+
+/// Running sum used to average 1s samples into a coarser bucket.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Bucket {
+    sum: Sample,
+    count: u32,
+}
+
+impl Bucket {
+    /// Add a sample; returns the bucket average once `size` samples are in.
+    fn push(&mut self, sample: &Sample, size: u32) -> Option<Sample> {
+        self.sum.add(sample);
+        self.count += 1;
+        if self.count < size {
+            return None;
+        }
+        let avg = self.sum.scaled(1.0 / self.count as f64);
+        *self = Bucket::default();
+        Some(avg)
+    }
+}
+
+/// Downsampled resource history covering the last minute, hour and day.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct History {
+    minute: VecDeque<Sample>,
+    hour: VecDeque<Sample>,
+    day: VecDeque<Sample>,
+    hour_bucket: Bucket,
+    day_bucket: Bucket,
+}
+
+impl History {
+    /// Record one sample. Expected to be called once per second.
+    pub fn record(&mut self, sample: Sample) {
+        if let Some(avg) = self.hour_bucket.push(&sample, HOUR_BUCKET) {
+            push_capped(&mut self.hour, avg, HOUR_SAMPLES);
+        }
+        if let Some(avg) = self.day_bucket.push(&sample, DAY_BUCKET) {
+            push_capped(&mut self.day, avg, DAY_SAMPLES);
+        }
+        push_capped(&mut self.minute, sample, MINUTE_SAMPLES);
+    }
+
+    pub fn samples(&self, range: HistoryRange) -> &VecDeque<Sample> {
+        match range {
+            HistoryRange::Minute => &self.minute,
+            HistoryRange::Hour => &self.hour,
+            HistoryRange::Day => &self.day,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.minute.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        *self = History::default();
+    }
+}
+
+fn push_capped(queue: &mut VecDeque<Sample>, sample: Sample, cap: usize) {
+    queue.push_back(sample);
+    while queue.len() > cap {
+        queue.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(compute: f64) -> Sample {
+        Sample {
+            resources: Resources {
+                compute,
+                ..Default::default()
+            },
+            production: Resources::default(),
+        }
+    }
+
+    #[test]
+    fn test_minute_is_capped() {
+        let mut history = History::default();
+        for i in 0..100 {
+            history.record(sample(i as f64));
+        }
+        let minute = history.samples(HistoryRange::Minute);
+        assert_eq!(minute.len(), MINUTE_SAMPLES);
+        assert_eq!(minute.back().unwrap().resources.compute, 99.0);
+    }
+
+    #[test]
+    fn test_hour_samples_are_averaged() {
+        let mut history = History::default();
+        for i in 0..120 {
+            history.record(sample(i as f64));
+        }
+        let hour = history.samples(HistoryRange::Hour);
+        assert_eq!(hour.len(), 2);
+        // Average of 0..60 and 60..120
+        assert!((hour[0].resources.compute - 29.5).abs() < 1e-9);
+        assert!((hour[1].resources.compute - 89.5).abs() < 1e-9);
+        assert!(history.samples(HistoryRange::Day).is_empty());
+    }
+
+    #[test]
+    fn test_history_roundtrip() {
+        let mut history = History::default();
+        for i in 0..70 {
+            history.record(sample(i as f64));
+        }
+        let json = serde_json::to_string(&history).unwrap();
+        let loaded: History = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.samples(HistoryRange::Minute).len(), MINUTE_SAMPLES);
+        assert_eq!(loaded.samples(HistoryRange::Hour).len(), 1);
+    }
+}
@@ -0,0 +1,306 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::events::GameEventKind;
+use super::formulas;
+use super::resources::Resources;
+use super::tasks::{TaskDefinition, TaskKind};
+
+/// Ticks between severity increases while an incident goes unmitigated.
+pub const ESCALATION_TICKS: u64 = 60; // 15 seconds
+/// Ticks from mitigation until the incident is resolved.
+pub const RECOVERY_TICKS: u64 = 40; // 10 seconds
+/// An attack nobody mitigates burns out on its own after this long.
+pub const BURNOUT_TICKS: u64 = 4 * 60 * 3; // 3 minutes
+const MAX_SEVERITY: u8 = 10;
+/// Time the player has to answer a mitigation prompt.
+const PROMPT_TICKS: u32 = ESCALATION_TICKS as u32;
+
+/// Where an incident is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum IncidentState {
+    /// Hitting production, but nobody has noticed yet.
+    Undetected,
+    /// Alert fired; a mitigation prompt is waiting in the task terminal.
+    Detected,
+    /// The player picked up the prompt.
+    Acknowledged,
+    /// Fix applied; no more damage, recovering.
+    Mitigated,
+    Resolved,
+}
+
+impl IncidentState {
+    pub fn label(self) -> &'static str {
+        match self {
+            IncidentState::Undetected => "undetected",
+            IncidentState::Detected => "detected",
+            IncidentState::Acknowledged => "acknowledged",
+            IncidentState::Mitigated => "mitigated",
+            IncidentState::Resolved => "resolved",
+        }
+    }
+}
+
+/// A multi-stage incident started by an event. Until it is mitigated it
+/// keeps doing damage every tick and gets worse over time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Incident {
+    pub id: u32,
+    pub name: String,
+    pub severity: u8,
+    pub state: IncidentState,
+    pub started_tick: u64,
+    /// When monitoring notices it; earlier with more MonitoringStacks.
+    pub detected_tick: u64,
+    pub acknowledged_tick: Option<u64>,
+    pub mitigated_tick: Option<u64>,
+    pub resolved_tick: Option<u64>,
+    /// Last escalation (or the start), for timing the next one.
+    pub escalated_tick: u64,
+}
+
+/// Something that changed on an incident during a tick.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IncidentUpdate {
+    Detected,
+    Escalated,
+    Resolved,
+    /// Went unmitigated until it died down on its own.
+    BurnedOut,
+}
+
+impl Incident {
+    /// The incident an event starts, if it starts one.
+    pub fn from_event(event: &GameEventKind, id: u32, tick: u64, monitoring: u32) -> Option<Self> {
+        match event {
+            GameEventKind::DDoSAttack { severity } => Some(Self {
+                id,
+                name: "DDoS".into(),
+                severity: *severity,
+                state: IncidentState::Undetected,
+                started_tick: tick,
+                detected_tick: tick + formulas::detection_delay_ticks(monitoring),
+                acknowledged_tick: None,
+                mitigated_tick: None,
+                resolved_tick: None,
+                escalated_tick: tick,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state < IncidentState::Mitigated
+    }
+
+    /// Resources lost this tick while the incident is open.
+    pub fn drain(&self, resources: &Resources) -> Resources {
+        if !self.is_open() {
+            return Resources::default();
+        }
+        Resources {
+            bandwidth: resources.bandwidth * formulas::incident_drain(self.severity),
+            ..Default::default()
+        }
+    }
+
+    /// Move the incident along for `tick` and report what changed.
+    pub fn advance(&mut self, tick: u64) -> Option<IncidentUpdate> {
+        match self.state {
+            IncidentState::Undetected if tick >= self.detected_tick => {
+                self.state = IncidentState::Detected;
+                Some(IncidentUpdate::Detected)
+            }
+            IncidentState::Undetected | IncidentState::Detected | IncidentState::Acknowledged
+                if tick >= self.started_tick + BURNOUT_TICKS =>
+            {
+                self.resolve(tick);
+                Some(IncidentUpdate::BurnedOut)
+            }
+            IncidentState::Undetected | IncidentState::Detected | IncidentState::Acknowledged
+                if tick >= self.escalated_tick + ESCALATION_TICKS
+                    && self.severity < MAX_SEVERITY =>
+            {
+                self.severity += 1;
+                self.escalated_tick = tick;
+                Some(IncidentUpdate::Escalated)
+            }
+            IncidentState::Mitigated
+                if self
+                    .mitigated_tick
+                    .is_some_and(|t| tick >= t + RECOVERY_TICKS) =>
+            {
+                self.resolve(tick);
+                Some(IncidentUpdate::Resolved)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn acknowledge(&mut self, tick: u64) {
+        if matches!(self.state, IncidentState::Undetected | IncidentState::Detected) {
+            self.state = IncidentState::Acknowledged;
+            self.acknowledged_tick = Some(tick);
+        }
+    }
+
+    pub fn mitigate(&mut self, tick: u64) {
+        if self.is_open() {
+            self.acknowledge(tick);
+            self.state = IncidentState::Mitigated;
+            self.mitigated_tick = Some(tick);
+        }
+    }
+
+    /// Move every tick of the incident `ticks` later. Time spent offline
+    /// is frozen for incidents, so they pick up where they left off.
+    pub fn shift(&mut self, ticks: u64) {
+        self.started_tick += ticks;
+        self.detected_tick += ticks;
+        self.escalated_tick += ticks;
+        for tick in [
+            &mut self.acknowledged_tick,
+            &mut self.mitigated_tick,
+            &mut self.resolved_tick,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *tick += ticks;
+        }
+    }
+
+    fn resolve(&mut self, tick: u64) {
+        self.state = IncidentState::Resolved;
+        self.resolved_tick = Some(tick);
+    }
+
+    /// The "mitigate now?" prompt for the task terminal, with the options
+    /// in a random order.
+    pub fn prompt(&self, rng: &mut impl Rng) -> TaskDefinition {
+        const FIX: &str = "Enable rate limiting and traffic scrubbing";
+        let mut options = vec![
+            FIX.to_string(),
+            "Ignore it, it'll pass".into(),
+            "Restart the load balancer".into(),
+        ];
+        options.shuffle(rng);
+        let correct = options.iter().position(|o| o == FIX).unwrap_or_default();
+        TaskDefinition {
+            name: format!("INCIDENT: {} (sev {})", self.name, self.severity),
+            kind: TaskKind::IncidentResponse {
+                question: format!(
+                    "{} at severity {}/10 is draining bandwidth. Mitigate now?",
+                    self.name, self.severity
+                ),
+                options,
+                correct,
+            },
+            reward: Resources::default(),
+            time_limit_ticks: PROMPT_TICKS,
+            difficulty: self.severity,
+            incident: Some(self.id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ddos(monitoring: u32) -> Incident {
+        Incident::from_event(&GameEventKind::DDoSAttack { severity: 3 }, 1, 100, monitoring)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_only_ddos_starts_an_incident() {
+        let breach = GameEventKind::SecurityBreach { lost_compute: 1.0 };
+        assert!(Incident::from_event(&breach, 1, 0, 0).is_none());
+        assert!(ddos(0).is_open());
+    }
+
+    #[test]
+    fn test_monitoring_detects_earlier() {
+        assert!(ddos(3).detected_tick < ddos(0).detected_tick);
+        let mut incident = ddos(0);
+        assert_eq!(incident.advance(100), None);
+        assert_eq!(
+            incident.advance(incident.detected_tick),
+            Some(IncidentUpdate::Detected)
+        );
+    }
+
+    #[test]
+    fn test_lifecycle() {
+        let mut incident = ddos(10);
+        incident.advance(100);
+        assert_eq!(incident.state, IncidentState::Detected);
+
+        // Ignored: severity rises and bandwidth keeps draining
+        assert_eq!(
+            incident.advance(100 + ESCALATION_TICKS),
+            Some(IncidentUpdate::Escalated)
+        );
+        assert_eq!(incident.severity, 4);
+        let resources = Resources {
+            bandwidth: 1_000.0,
+            ..Default::default()
+        };
+        assert!(incident.drain(&resources).bandwidth > 0.0);
+
+        incident.mitigate(200);
+        assert_eq!(incident.state, IncidentState::Mitigated);
+        assert_eq!(incident.acknowledged_tick, Some(200));
+        assert_eq!(incident.drain(&resources).bandwidth, 0.0);
+        assert_eq!(incident.advance(200 + RECOVERY_TICKS - 1), None);
+        assert_eq!(
+            incident.advance(200 + RECOVERY_TICKS),
+            Some(IncidentUpdate::Resolved)
+        );
+        assert_eq!(incident.resolved_tick, Some(200 + RECOVERY_TICKS));
+    }
+
+    #[test]
+    fn test_unmitigated_incidents_burn_out() {
+        let mut incident = ddos(10);
+        incident.advance(100);
+        assert_eq!(
+            incident.advance(100 + BURNOUT_TICKS),
+            Some(IncidentUpdate::BurnedOut)
+        );
+    }
+
+    #[test]
+    fn test_prompt_options_are_shuffled() {
+        use rand::SeedableRng;
+        let incident = ddos(0);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut positions = Vec::new();
+        for _ in 0..20 {
+            let TaskKind::IncidentResponse { options, correct, .. } = incident.prompt(&mut rng).kind
+            else {
+                panic!("not an incident prompt");
+            };
+            assert!(options[correct].starts_with("Enable rate limiting"));
+            positions.push(correct);
+        }
+        positions.dedup();
+        assert!(positions.len() > 1);
+    }
+
+    #[test]
+    fn test_shift_keeps_timings() {
+        let mut incident = ddos(0);
+        incident.acknowledge(110);
+        incident.shift(1_000);
+        assert_eq!(incident.started_tick, 1_100);
+        assert_eq!(incident.acknowledged_tick, Some(1_110));
+        assert_ne!(
+            incident.advance(1_100 + BURNOUT_TICKS - 1),
+            Some(IncidentUpdate::BurnedOut)
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use chrono::{DateTime, Duration, Utc};
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use super::events::GameEvent;
+use super::save;
+
+const JOURNAL_FILE: &str = "journal.jsonl";
+
+/// Rotate the journal once it grows past this size.
+const MAX_JOURNAL_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Number of rotated journal files kept (journal.1.jsonl .. journal.N.jsonl).
+const MAX_ROTATED_FILES: u32 = 3;
+
+/// One line of the on-disk journal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Which run the event happened in: prestiges so far, over all IPOs.
+    pub run: u32,
+    #[serde(flatten)]
+    pub event: GameEvent,
+}
+
+impl JournalEntry {
+    pub fn new(event: GameEvent, run: u32) -> Self {
+        Self { run, event }
+    }
+}
+
+pub fn journal_path() -> PathBuf {
+    save::data_dir().join(JOURNAL_FILE)
+}
+
+fn rotated_path(path: &Path, index: u32) -> PathBuf {
+    path.with_extension(format!("{index}.jsonl"))
+}
+
+/// Append entries to the journal in the data dir, rotating it if needed.
+pub fn append(entries: &[JournalEntry]) -> Result<()> {
+    append_to(&journal_path(), entries)
+}
+
+pub fn append_to(path: &Path, entries: &[JournalEntry]) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    rotate_if_needed(path, MAX_JOURNAL_BYTES)?;
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    for entry in entries {
+        let line = serde_json::to_string(entry)?;
+        writeln!(file, "{line}")?;
+    }
+    Ok(())
+}
+
+fn rotate_if_needed(path: &Path, max_bytes: u64) -> Result<()> {
+    let size = match std::fs::metadata(path) {
+        Ok(meta) => meta.len(),
+        Err(_) => return Ok(()),
+    };
+    if size < max_bytes {
+        return Ok(());
+    }
+
+    let oldest = rotated_path(path, MAX_ROTATED_FILES);
+    if oldest.exists() {
+        std::fs::remove_file(&oldest)?;
+    }
+    for i in (1..MAX_ROTATED_FILES).rev() {
+        let from = rotated_path(path, i);
+        if from.exists() {
+            std::fs::rename(&from, rotated_path(path, i + 1))?;
+        }
+    }
+    std::fs::rename(path, rotated_path(path, 1))?;
+    Ok(())
+}
+
+/// Filter for reading entries back out of the journal.
+#[derive(Debug, Default)]
+pub struct JournalQuery {
+    pub since: Option<DateTime<Utc>>,
+    /// Matches an event slug (`ddos`) or category (`incident`).
+    pub kind: Option<String>,
+}
+
+impl JournalQuery {
+    fn matches(&self, entry: &JournalEntry) -> bool {
+        if let Some(since) = self.since {
+            if entry.event.timestamp < since {
+                return false;
+            }
+        }
+        if let Some(kind) = &self.kind {
+            let kind = kind.to_lowercase();
+            return entry.event.kind.slug() == kind
+                || entry.event.kind.category().label() == kind;
+        }
+        true
+    }
+}
+
+/// Read matching entries, oldest first, across the journal and its rotations.
+pub fn query(query: &JournalQuery) -> Result<Vec<JournalEntry>> {
+    query_at(&journal_path(), query)
+}
+
+pub fn query_at(path: &Path, query: &JournalQuery) -> Result<Vec<JournalEntry>> {
+    let mut files: Vec<PathBuf> = (1..=MAX_ROTATED_FILES)
+        .rev()
+        .map(|i| rotated_path(path, i))
+        .collect();
+    files.push(path.to_path_buf());
+
+    let mut entries = Vec::new();
+    for file in files.iter().filter(|f| f.exists()) {
+        let reader = BufReader::new(std::fs::File::open(file)?);
+        for line in reader.lines() {
+            let line = line?;
+            match serde_json::from_str::<JournalEntry>(&line) {
+                Ok(entry) if query.matches(&entry) => entries.push(entry),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Skipping bad journal line in {:?}: {e}", file),
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Parse a duration like `30s`, `15m`, `1h` or `2d`. `None` if it is
+/// malformed or too large to represent.
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    let input = input.trim();
+    let split = input.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = input.split_at(split);
+    let amount: i64 = amount.parse().ok()?;
+    match unit {
+        "s" => Duration::try_seconds(amount),
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::events::GameEventKind;
+
+    fn entry(kind: GameEventKind, minutes_ago: i64) -> JournalEntry {
+        let mut event = GameEvent::new(kind, 0);
+        event.timestamp = Utc::now() - Duration::minutes(minutes_ago);
+        JournalEntry::new(event, 0)
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30s"), Some(Duration::seconds(30)));
+        assert_eq!(parse_duration("15m"), Some(Duration::minutes(15)));
+        assert_eq!(parse_duration("1h"), Some(Duration::hours(1)));
+        assert_eq!(parse_duration("2d"), Some(Duration::days(2)));
+        assert_eq!(parse_duration("h"), None);
+        assert_eq!(parse_duration("10"), None);
+        assert_eq!(parse_duration("5w"), None);
+        assert_eq!(parse_duration("9999999999999d"), None);
+        assert_eq!(parse_duration("99999999999999999999s"), None);
+    }
+
+    #[test]
+    fn test_append_and_query() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(JOURNAL_FILE);
+
+        let entries = vec![
+            entry(GameEventKind::DDoSAttack { severity: 3 }, 120),
+            entry(GameEventKind::DDoSAttack { severity: 5 }, 10),
+            entry(GameEventKind::SecurityBreach { lost_compute: 1.0 }, 5),
+        ];
+        append_to(&path, &entries).unwrap();
+
+        let all = query_at(&path, &JournalQuery::default()).unwrap();
+        assert_eq!(all.len(), 3);
+
+        let recent_ddos = query_at(
+            &path,
+            &JournalQuery {
+                since: Some(Utc::now() - Duration::hours(1)),
+                kind: Some("ddos".into()),
+            },
+        )
+        .unwrap();
+        assert_eq!(recent_ddos.len(), 1);
+
+        let incidents = query_at(
+            &path,
+            &JournalQuery {
+                since: None,
+                kind: Some("incident".into()),
+            },
+        )
+        .unwrap();
+        assert_eq!(incidents.len(), 3);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(JOURNAL_FILE);
+
+        std::fs::write(&path, "x".repeat(16)).unwrap();
+        rotate_if_needed(&path, 8).unwrap();
+        assert!(!path.exists());
+        assert!(rotated_path(&path, 1).exists());
+    }
+}
@@ -0,0 +1,269 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use super::events::GameEventKind;
+use super::formulas;
+use super::resources::Resources;
+
+/// Long-run price of one BTC in compute; the random walk drifts back here.
+pub const BASE_PRICE: f64 = 1_000.0;
+/// Gap between buy and sell prices, as a fraction of the mid price.
+pub const SPREAD: f64 = 0.02;
+/// Price samples kept for the chart (one per second).
+pub const MAX_HISTORY: usize = 240;
+/// Open limit orders allowed at once.
+pub const MAX_ORDERS: usize = 5;
+
+/// Random-walk noise per second.
+const VOLATILITY: f64 = 0.02;
+/// Chance per second of a crash or a bubble.
+const SHOCK_CHANCE: f64 = 0.002;
+
+/// What crypto can be exchanged to and from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TradeAsset {
+    Compute,
+    Bandwidth,
+    Storage,
+}
+
+impl TradeAsset {
+    pub const ALL: [TradeAsset; 3] = [TradeAsset::Compute, TradeAsset::Bandwidth, TradeAsset::Storage];
+
+    /// Units of this asset worth one unit of compute.
+    fn weight(self) -> f64 {
+        match self {
+            TradeAsset::Compute => 1.0,
+            TradeAsset::Bandwidth => 0.5,
+            TradeAsset::Storage => 0.25,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TradeAsset::Compute => "CPU",
+            TradeAsset::Bandwidth => "BW",
+            TradeAsset::Storage => "SSD",
+        }
+    }
+
+    pub fn amount(self, resources: &Resources) -> f64 {
+        match self {
+            TradeAsset::Compute => resources.compute,
+            TradeAsset::Bandwidth => resources.bandwidth,
+            TradeAsset::Storage => resources.storage,
+        }
+    }
+
+    pub fn resources(self, amount: f64) -> Resources {
+        let mut resources = Resources::default();
+        match self {
+            TradeAsset::Compute => resources.compute = amount,
+            TradeAsset::Bandwidth => resources.bandwidth = amount,
+            TradeAsset::Storage => resources.storage = amount,
+        }
+        resources
+    }
+}
+
+/// Buying or selling crypto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// A limit order. Its funds are held when placed and returned if cancelled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Order {
+    pub id: u32,
+    pub side: TradeSide,
+    pub asset: TradeAsset,
+    /// Crypto to buy or sell.
+    pub crypto: f64,
+    /// Price in `asset` per BTC at which the order fills.
+    pub limit: f64,
+}
+
+impl Order {
+    /// A new order; `Market::place` gives it an id.
+    pub fn new(side: TradeSide, asset: TradeAsset, crypto: f64, limit: f64) -> Self {
+        Self {
+            id: 0,
+            side,
+            asset,
+            crypto,
+            limit,
+        }
+    }
+
+    /// What the order holds until it fills or is cancelled.
+    pub fn escrow(&self) -> Resources {
+        match self.side {
+            TradeSide::Buy => self.asset.resources(self.crypto * self.limit),
+            TradeSide::Sell => Resources {
+                crypto: self.crypto,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// What the order pays out when it fills.
+    pub fn proceeds(&self) -> Resources {
+        match self.side {
+            TradeSide::Buy => Resources {
+                crypto: self.crypto,
+                ..Default::default()
+            },
+            TradeSide::Sell => self.asset.resources(self.crypto * self.limit),
+        }
+    }
+}
+
+/// Simulated BTC exchange: a mean-reverting random walk on the price,
+/// with the occasional crash or bubble.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Market {
+    /// Mid price of one BTC in compute.
+    pub price: f64,
+    /// Current drift per second (log scale).
+    pub trend: f64,
+    pub history: VecDeque<f64>,
+    pub orders: Vec<Order>,
+    next_order_id: u32,
+}
+
+impl Default for Market {
+    fn default() -> Self {
+        Self {
+            price: BASE_PRICE,
+            trend: 0.0,
+            history: VecDeque::new(),
+            orders: Vec::new(),
+            next_order_id: 0,
+        }
+    }
+}
+
+impl Market {
+    /// Price of one BTC in `asset` for the given side, spread included.
+    pub fn quote(&self, side: TradeSide, asset: TradeAsset) -> f64 {
+        let mid = self.price * asset.weight();
+        match side {
+            TradeSide::Buy => mid * (1.0 + SPREAD / 2.0),
+            TradeSide::Sell => mid * (1.0 - SPREAD / 2.0),
+        }
+    }
+
+    /// Advance one second. Returns a crash or bubble if one hit.
+    pub fn step(&mut self, rng: &mut impl Rng) -> Option<GameEventKind> {
+        let noise = rng.gen_range(-1.0..1.0) * VOLATILITY;
+        self.trend = formulas::market_trend(self.trend, self.price, BASE_PRICE);
+        self.price = formulas::market_price(self.price, self.trend, noise);
+
+        let roll: f64 = rng.gen();
+        let shock = if roll < SHOCK_CHANCE {
+            let drop = rng.gen_range(0.3..0.6);
+            self.price *= 1.0 - drop;
+            self.trend = self.trend.min(0.0);
+            Some(GameEventKind::MarketCrash { drop })
+        } else if roll < SHOCK_CHANCE * 2.0 {
+            let rise = rng.gen_range(0.5..1.5);
+            self.price *= 1.0 + rise;
+            self.trend = self.trend.max(0.01);
+            Some(GameEventKind::MarketBubble { rise })
+        } else {
+            None
+        };
+
+        self.history.push_back(self.price);
+        while self.history.len() > MAX_HISTORY {
+            self.history.pop_front();
+        }
+        shock
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.orders.len() >= MAX_ORDERS
+    }
+
+    /// Add an order and return its id.
+    pub fn place(&mut self, mut order: Order) -> u32 {
+        let id = self.next_order_id;
+        self.next_order_id += 1;
+        order.id = id;
+        self.orders.push(order);
+        id
+    }
+
+    pub fn cancel(&mut self, id: u32) -> Option<Order> {
+        let index = self.orders.iter().position(|o| o.id == id)?;
+        Some(self.orders.remove(index))
+    }
+
+    /// Remove and return the orders the current price reaches.
+    pub fn take_filled(&mut self) -> Vec<Order> {
+        let (filled, open) = std::mem::take(&mut self.orders).into_iter().partition(|o| {
+            match o.side {
+                TradeSide::Buy => self.quote(TradeSide::Buy, o.asset) <= o.limit,
+                TradeSide::Sell => self.quote(TradeSide::Sell, o.asset) >= o.limit,
+            }
+        });
+        self.orders = open;
+        filled
+    }
+
+    /// Change in price over the charted history, as a fraction.
+    pub fn change(&self) -> f64 {
+        match self.history.front() {
+            Some(first) if *first > 0.0 => self.price / first - 1.0,
+            _ => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_quote_includes_spread() {
+        let market = Market::default();
+        let buy = market.quote(TradeSide::Buy, TradeAsset::Compute);
+        let sell = market.quote(TradeSide::Sell, TradeAsset::Compute);
+        assert!(buy > BASE_PRICE && sell < BASE_PRICE);
+        assert_eq!(market.quote(TradeSide::Buy, TradeAsset::Storage), buy * 0.25);
+    }
+
+    #[test]
+    fn test_step_is_deterministic_and_bounded() {
+        let mut a = Market::default();
+        let mut b = Market::default();
+        let mut rng_a = rand::rngs::StdRng::seed_from_u64(7);
+        let mut rng_b = rand::rngs::StdRng::seed_from_u64(7);
+        for _ in 0..1_000 {
+            a.step(&mut rng_a);
+            b.step(&mut rng_b);
+        }
+        assert_eq!(a.price, b.price);
+        assert!(a.price > 0.0);
+        assert_eq!(a.history.len(), MAX_HISTORY);
+    }
+
+    #[test]
+    fn test_limit_orders_fill_when_price_crosses() {
+        let mut market = Market::default();
+        market.place(Order::new(TradeSide::Buy, TradeAsset::Compute, 1.0, 900.0));
+        market.place(Order::new(TradeSide::Sell, TradeAsset::Compute, 1.0, 1_100.0));
+        assert!(market.take_filled().is_empty());
+
+        market.price = 800.0;
+        let filled = market.take_filled();
+        assert_eq!(filled.len(), 1);
+        assert_eq!(filled[0].side, TradeSide::Buy);
+        assert_eq!(market.orders.len(), 1);
+    }
+}
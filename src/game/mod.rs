@@ -1,10 +1,26 @@
 pub mod buildings;
+pub mod challenges;
+pub mod content;
+pub mod daily;
 pub mod events;
+pub mod facility;
 pub mod formulas;
+pub mod history;
+pub mod incidents;
+pub mod journal;
+pub mod market;
+pub mod packs;
 pub mod network_info;
+pub mod notifications;
+pub mod production;
 pub mod progression;
+pub mod purchase;
+pub mod research;
 pub mod resources;
 pub mod save;
+pub mod scripting;
 pub mod state;
+pub mod stats;
+pub mod synergies;
 pub mod tasks;
 pub mod upgrades;
@@ -0,0 +1,236 @@
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+
+/// Most toasts shown on screen at once.
+pub const MAX_VISIBLE: usize = 3;
+
+/// Notifications kept for the history view.
+const MAX_HISTORY: usize = 100;
+
+/// What a notification is about. Decides its priority, title and how long
+/// it stays on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Info,
+    Warning,
+    Welcome,
+    Achievement,
+    Prestige,
+}
+
+impl NotificationKind {
+    /// Higher priorities are shown first and can bump lower ones off screen.
+    pub fn priority(self) -> u8 {
+        match self {
+            NotificationKind::Info => 0,
+            NotificationKind::Warning => 1,
+            NotificationKind::Welcome => 2,
+            NotificationKind::Achievement => 3,
+            NotificationKind::Prestige => 4,
+        }
+    }
+
+    /// Ticks on screen (4 ticks = 1 second).
+    pub fn duration_ticks(self) -> u32 {
+        match self {
+            NotificationKind::Info => 20,
+            NotificationKind::Warning => 12,
+            NotificationKind::Welcome => 40,
+            NotificationKind::Achievement => 32,
+            NotificationKind::Prestige => 40,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            NotificationKind::Info => "INFO",
+            NotificationKind::Warning => "WARNING",
+            NotificationKind::Welcome => "WELCOME BACK",
+            NotificationKind::Achievement => "ACHIEVEMENT",
+            NotificationKind::Prestige => "PRESTIGE",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub message: String,
+    pub created: DateTime<Utc>,
+    pub remaining_ticks: u32,
+}
+
+impl Notification {
+    pub fn new(kind: NotificationKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            created: Utc::now(),
+            remaining_ticks: kind.duration_ticks(),
+        }
+    }
+}
+
+/// Queue of toasts: up to `MAX_VISIBLE` on screen, the rest waiting in
+/// priority order, and a history of everything shown.
+#[derive(Debug, Default)]
+pub struct NotificationManager {
+    visible: Vec<Notification>,
+    queue: VecDeque<Notification>,
+    history: VecDeque<Notification>,
+}
+
+impl NotificationManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, notification: Notification) {
+        self.record(&notification);
+
+        if self.visible.len() < MAX_VISIBLE {
+            self.visible.push(notification);
+            return;
+        }
+
+        // Bump the lowest-priority toast back into the queue if the new
+        // one outranks it
+        let lowest = self
+            .visible
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, n)| n.kind.priority())
+            .map(|(i, n)| (i, n.kind.priority()));
+        match lowest {
+            Some((i, priority)) if priority < notification.kind.priority() => {
+                let bumped = std::mem::replace(&mut self.visible[i], notification);
+                self.enqueue_front(bumped);
+            }
+            _ => self.enqueue(notification),
+        }
+    }
+
+    pub fn extend(&mut self, notifications: impl IntoIterator<Item = Notification>) {
+        for notification in notifications {
+            self.push(notification);
+        }
+    }
+
+    /// Count down visible toasts and fill freed slots from the queue.
+    pub fn tick(&mut self) {
+        for notification in &mut self.visible {
+            notification.remaining_ticks = notification.remaining_ticks.saturating_sub(1);
+        }
+        self.visible.retain(|n| n.remaining_ticks > 0);
+        while self.visible.len() < MAX_VISIBLE {
+            match self.queue.pop_front() {
+                Some(next) => self.visible.push(next),
+                None => break,
+            }
+        }
+    }
+
+    /// Toasts currently on screen, highest priority first.
+    pub fn visible(&self) -> Vec<&Notification> {
+        let mut visible: Vec<_> = self.visible.iter().collect();
+        visible.sort_by_key(|n| std::cmp::Reverse(n.kind.priority()));
+        visible
+    }
+
+    pub fn queued(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Everything pushed so far, newest first.
+    pub fn history(&self) -> impl Iterator<Item = &Notification> {
+        self.history.iter().rev()
+    }
+
+    fn record(&mut self, notification: &Notification) {
+        self.history.push_back(notification.clone());
+        while self.history.len() > MAX_HISTORY {
+            self.history.pop_front();
+        }
+    }
+
+    /// Insert behind everything of equal or higher priority.
+    fn enqueue(&mut self, notification: Notification) {
+        let priority = notification.kind.priority();
+        let pos = self
+            .queue
+            .iter()
+            .position(|n| n.kind.priority() < priority)
+            .unwrap_or(self.queue.len());
+        self.queue.insert(pos, notification);
+    }
+
+    /// Insert ahead of everything of equal or lower priority.
+    fn enqueue_front(&mut self, notification: Notification) {
+        let priority = notification.kind.priority();
+        let pos = self
+            .queue
+            .iter()
+            .position(|n| n.kind.priority() <= priority)
+            .unwrap_or(self.queue.len());
+        self.queue.insert(pos, notification);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(manager: &NotificationManager) -> Vec<&str> {
+        manager.visible().iter().map(|n| n.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_back_to_back_notifications_stack() {
+        let mut manager = NotificationManager::new();
+        manager.push(Notification::new(NotificationKind::Achievement, "a"));
+        manager.push(Notification::new(NotificationKind::Achievement, "b"));
+        assert_eq!(messages(&manager), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_overflow_is_queued_by_priority() {
+        let mut manager = NotificationManager::new();
+        for msg in ["1", "2", "3"] {
+            manager.push(Notification::new(NotificationKind::Achievement, msg));
+        }
+        manager.push(Notification::new(NotificationKind::Info, "info"));
+        manager.push(Notification::new(NotificationKind::Achievement, "4"));
+        assert_eq!(manager.queued(), 2);
+
+        for _ in 0..NotificationKind::Achievement.duration_ticks() {
+            manager.tick();
+        }
+        // The achievement jumps ahead of the earlier info toast
+        assert_eq!(messages(&manager), vec!["4", "info"]);
+    }
+
+    #[test]
+    fn test_higher_priority_bumps_lowest() {
+        let mut manager = NotificationManager::new();
+        manager.push(Notification::new(NotificationKind::Warning, "warn"));
+        manager.push(Notification::new(NotificationKind::Achievement, "a"));
+        manager.push(Notification::new(NotificationKind::Achievement, "b"));
+        manager.push(Notification::new(NotificationKind::Prestige, "prestige"));
+
+        assert_eq!(messages(&manager), vec!["prestige", "a", "b"]);
+        assert_eq!(manager.queued(), 1);
+    }
+
+    #[test]
+    fn test_per_kind_duration() {
+        let mut manager = NotificationManager::new();
+        manager.push(Notification::new(NotificationKind::Warning, "short"));
+        manager.push(Notification::new(NotificationKind::Welcome, "long"));
+        for _ in 0..NotificationKind::Warning.duration_ticks() {
+            manager.tick();
+        }
+        assert_eq!(messages(&manager), vec!["long"]);
+        assert_eq!(manager.history().count(), 2);
+        assert_eq!(manager.history().next().unwrap().message, "long");
+    }
+}
@@ -0,0 +1,296 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::buildings::BuildingDef;
+use super::content::{read_override, ContentError};
+use super::events::CustomEventDef;
+use super::progression::AchievementDef;
+use super::resources::Resources;
+use super::scripting::ScriptSource;
+use super::tasks::TaskDefinition;
+use super::upgrades::{Upgrade, UpgradeEffect, UpgradeId};
+
+/// Sub-directory of the config dir holding one folder per content pack.
+const PACKS_DIR: &str = "packs";
+/// Every pack folder needs one of these.
+const MANIFEST: &str = "pack.json";
+/// Optional folder of Rhai scripts inside a pack.
+const SCRIPTS_DIR: &str = "scripts";
+
+/// What a pack says about itself in `pack.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackManifest {
+    /// Namespace for everything the pack adds, e.g. `quantum`.
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// An upgrade as written in a pack. Pack upgrades use string ids, which
+/// are hashed into the numeric id space the rest of the game uses.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PackUpgrade {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub cost: Resources,
+    #[serde(default)]
+    pub prerequisites: Vec<UpgradeRef>,
+    pub effect: UpgradeEffect,
+}
+
+/// A prerequisite: a built-in upgrade by number or a pack upgrade by name.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum UpgradeRef {
+    Builtin(UpgradeId),
+    Pack(String),
+}
+
+impl UpgradeRef {
+    fn resolve(&self) -> UpgradeId {
+        match self {
+            UpgradeRef::Builtin(id) => *id,
+            UpgradeRef::Pack(id) => pack_upgrade_id(id),
+        }
+    }
+}
+
+impl PackUpgrade {
+    fn into_upgrade(self) -> Upgrade {
+        Upgrade {
+            id: pack_upgrade_id(&self.id),
+            name: self.name,
+            description: self.description,
+            cost: self.cost,
+            prerequisites: self.prerequisites.iter().map(UpgradeRef::resolve).collect(),
+            effect: self.effect,
+            purchased: false,
+        }
+    }
+}
+
+/// A loaded content pack.
+#[derive(Debug, Clone)]
+pub struct Pack {
+    pub manifest: PackManifest,
+    pub buildings: Vec<BuildingDef>,
+    pub upgrades: Vec<Upgrade>,
+    pub tasks: Vec<TaskDefinition>,
+    pub events: Vec<CustomEventDef>,
+    pub achievements: Vec<AchievementDef>,
+    pub scripts: Vec<ScriptSource>,
+}
+
+pub fn packs_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("idle-terminal")
+        .join(PACKS_DIR)
+}
+
+/// Stable numeric id for a pack upgrade. FNV-1a, with the top bit set so
+/// it can never collide with a built-in upgrade.
+pub fn pack_upgrade_id(id: &str) -> UpgradeId {
+    let hash = id.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    (hash as UpgradeId) | !(UpgradeId::MAX >> 1)
+}
+
+/// Load every pack folder in `dir`, sorted by folder name. A folder that
+/// fails to load gives an error without stopping the others.
+pub fn load_packs(dir: &Path) -> Vec<Result<Pack, ContentError>> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut folders: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.join(MANIFEST).is_file())
+        .collect();
+    folders.sort();
+    folders.iter().map(|folder| load_pack(folder)).collect()
+}
+
+pub fn load_pack(dir: &Path) -> Result<Pack, ContentError> {
+    let path = dir.join(MANIFEST);
+    let manifest: PackManifest = std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+        .map_err(|message| ContentError::Parse {
+            file: path.display().to_string(),
+            message,
+        })?;
+    if manifest.id.is_empty() || manifest.id.contains(':') {
+        return Err(ContentError::Invalid {
+            item: format!("pack {}", dir.display()),
+            reason: "id must be non-empty and can't contain ':'",
+        });
+    }
+
+    let upgrades = read_override::<PackUpgrade>(dir, "upgrades.json")?.unwrap_or_default();
+    // Upgrade ids are hashed below, so check the names first
+    let upgrade_ids: Vec<String> = upgrades.iter().map(|u| u.id.clone()).collect();
+    let pack = Pack {
+        buildings: read_override(dir, "buildings.json")?.unwrap_or_default(),
+        upgrades: upgrades.into_iter().map(PackUpgrade::into_upgrade).collect(),
+        tasks: read_override(dir, "tasks.json")?.unwrap_or_default(),
+        events: read_override(dir, "events.json")?.unwrap_or_default(),
+        achievements: read_override(dir, "achievements.json")?.unwrap_or_default(),
+        scripts: read_scripts(&dir.join(SCRIPTS_DIR), &manifest.id)?,
+        manifest,
+    };
+    pack.check_namespaces(upgrade_ids, dir)?;
+    Ok(pack)
+}
+
+/// Every `.rhai` file in `dir`, named `<pack id>:<file stem>`.
+fn read_scripts(dir: &Path, pack: &str) -> Result<Vec<ScriptSource>, ContentError> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "rhai"))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| {
+            let source = std::fs::read_to_string(path).map_err(|e| ContentError::Parse {
+                file: path.display().to_string(),
+                message: e.to_string(),
+            })?;
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            Ok(ScriptSource {
+                name: format!("{pack}:{stem}"),
+                source,
+            })
+        })
+        .collect()
+}
+
+impl Pack {
+    /// Everything a pack adds must live under `<pack id>:` so packs can't
+    /// clash with the base game or each other. Tasks are keyed by name;
+    /// upgrades by the string ids given in `upgrade_ids`.
+    fn check_namespaces(&self, upgrade_ids: Vec<String>, dir: &Path) -> Result<(), ContentError> {
+        let prefix = format!("{}:", self.manifest.id);
+        let mut ids = self
+            .buildings
+            .iter()
+            .map(|b| b.kind.id().to_string())
+            .chain(upgrade_ids)
+            .chain(self.tasks.iter().map(|t| t.name.clone()))
+            .chain(self.events.iter().map(|e| e.id.clone()))
+            .chain(self.achievements.iter().map(|a| a.id.clone()));
+        match ids.find(|id| !id.starts_with(&prefix)) {
+            Some(id) => Err(ContentError::NotNamespaced {
+                pack: dir.display().to_string(),
+                id,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A pack directory holding `files`, removed when dropped.
+    fn pack_dir(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (file, json) in files {
+            std::fs::write(dir.path().join(file), json).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_pack_upgrade_ids_are_stable_and_distinct() {
+        assert_eq!(pack_upgrade_id("quantum:qec"), pack_upgrade_id("quantum:qec"));
+        assert_ne!(pack_upgrade_id("quantum:qec"), pack_upgrade_id("quantum:qed"));
+        assert!(pack_upgrade_id("quantum:qec") > 1_000_000);
+    }
+
+    #[test]
+    fn test_load_pack() {
+        let dir = pack_dir(
+            &[
+                ("pack.json", r#"{"id": "quantum", "name": "Quantum", "version": "1.0"}"#),
+                (
+                    "upgrades.json",
+                    r#"[{"id": "quantum:qec", "name": "Error Correction", "cost": {"compute": 10.0},
+                         "prerequisites": [0], "effect": {"MultiplyAllProduction": 1.5}}]"#,
+                ),
+                (
+                    "events.json",
+                    r#"[{"id": "quantum:decoherence", "message": "Qubits decohered",
+                         "chance": 0.001, "effect": {"compute": -50.0}}]"#,
+                ),
+            ],
+        );
+        let pack = load_pack(dir.path()).unwrap();
+        assert_eq!(pack.manifest.id, "quantum");
+        assert_eq!(pack.upgrades[0].id, pack_upgrade_id("quantum:qec"));
+        assert_eq!(pack.upgrades[0].prerequisites, vec![0]);
+        assert_eq!(pack.events.len(), 1);
+    }
+
+    #[test]
+    fn test_pack_ids_must_be_namespaced() {
+        let dir = pack_dir(
+            &[
+                ("pack.json", r#"{"id": "quantum", "name": "Quantum"}"#),
+                (
+                    "achievements.json",
+                    r#"[{"id": "qubit", "name": "Qubit", "description": ""}]"#,
+                ),
+            ],
+        );
+        assert!(matches!(
+            load_pack(dir.path()),
+            Err(ContentError::NotNamespaced { .. })
+        ));
+    }
+
+    #[test]
+    fn test_pack_upgrades_and_tasks_must_be_namespaced() {
+        let upgrade = pack_dir(
+            &[
+                ("pack.json", r#"{"id": "quantum", "name": "Quantum"}"#),
+                (
+                    "upgrades.json",
+                    r#"[{"id": "other:qec", "name": "Error Correction", "cost": {},
+                         "effect": {"MultiplyAllProduction": 1.5}}]"#,
+                ),
+            ],
+        );
+        assert!(matches!(
+            load_pack(upgrade.path()),
+            Err(ContentError::NotNamespaced { id, .. }) if id == "other:qec"
+        ));
+
+        let task = pack_dir(
+            &[
+                ("pack.json", r#"{"id": "quantum", "name": "Quantum"}"#),
+                (
+                    "tasks.json",
+                    r#"[{"name": "Calibrate", "kind": {"TypeCommand": {"command": "calibrate"}},
+                         "reward": {}, "time_limit_ticks": 40, "difficulty": 1}]"#,
+                ),
+            ],
+        );
+        assert!(matches!(
+            load_pack(task.path()),
+            Err(ContentError::NotNamespaced { id, .. }) if id == "Calibrate"
+        ));
+    }
+}
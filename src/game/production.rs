@@ -0,0 +1,337 @@
+use super::buildings::{BuildingKind, ResourceType};
+use super::facility::FacilityLoad;
+use super::formulas;
+use super::resources::Resources;
+use super::upgrades::UpgradeId;
+
+/// Something the player can buy whose effect on production can be previewed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Purchase {
+    Building(BuildingKind),
+    BuildingLevel(BuildingKind),
+    Upgrade(UpgradeId),
+}
+
+/// How long a purchase takes to afford and to pay for itself.
+#[derive(Debug, Clone)]
+pub struct PurchaseAdvice {
+    pub purchase: Purchase,
+    pub cost: Resources,
+    /// Extra production per tick once bought.
+    pub gain: Resources,
+    /// Seconds until affordable at the current net flow (production less
+    /// upkeep); `None` if never.
+    pub time_to_afford: Option<f64>,
+    /// Seconds of the added production needed to cover the cost, each
+    /// resource paying back its own share; `None` if some resource it
+    /// costs is never gained back.
+    pub payback: Option<f64>,
+}
+
+impl PurchaseAdvice {
+    pub fn new(
+        purchase: Purchase,
+        cost: Resources,
+        gain: Resources,
+        balance: &Resources,
+        net_per_tick: &Resources,
+    ) -> Self {
+        let pairs = [
+            (cost.compute, balance.compute, net_per_tick.compute),
+            (cost.bandwidth, balance.bandwidth, net_per_tick.bandwidth),
+            (cost.storage, balance.storage, net_per_tick.storage),
+            (cost.reputation, balance.reputation, net_per_tick.reputation),
+            (cost.crypto, balance.crypto, net_per_tick.crypto),
+        ];
+        let time_to_afford = pairs
+            .iter()
+            .map(|(cost, have, per_tick)| formulas::time_to_afford(cost - have, per_tick * 4.0))
+            .try_fold(0.0_f64, |acc, t| t.map(|t| acc.max(t)));
+
+        // Resources aren't interchangeable, so each one pays back on its own
+        let returns = [
+            (cost.compute, gain.compute),
+            (cost.bandwidth, gain.bandwidth),
+            (cost.storage, gain.storage),
+            (cost.reputation, gain.reputation),
+            (cost.crypto, gain.crypto),
+        ];
+        let payback = if gain.sum() > 0.0 {
+            returns
+                .iter()
+                .filter(|(cost, _)| *cost > 0.0)
+                .map(|(cost, gain)| formulas::time_to_afford(*cost, gain * 4.0))
+                .try_fold(0.0_f64, |acc, t| t.map(|t| acc.max(t)))
+        } else {
+            None
+        };
+
+        Self {
+            purchase,
+            cost,
+            gain,
+            time_to_afford,
+            payback,
+        }
+    }
+}
+
+/// The purchase with the shortest payback time.
+pub fn best_value(advice: &[PurchaseAdvice]) -> Option<Purchase> {
+    advice
+        .iter()
+        .filter_map(|a| a.payback.map(|p| (a.purchase, p)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(purchase, _)| purchase)
+}
+
+/// Every factor that goes into one building's production, in the order
+/// `recalculate_production` applies them.
+#[derive(Debug, Clone)]
+pub struct BuildingProduction {
+    pub kind: BuildingKind,
+    pub resource_type: ResourceType,
+    /// Base production of a single unit per tick.
+    pub base: f64,
+    pub count: u32,
+    pub level: u32,
+    pub level_bonus: f64,
+    /// Product of purchased `MultiplyProduction` upgrades for this building.
+    pub upgrade_multiplier: f64,
+    pub cicd_multiplier: f64,
+    /// Reputation and `MultiplyAllProduction` upgrades.
+    pub global_multiplier: f64,
+    /// Temporary effects such as overloads and hardware failures.
+    pub effect_multiplier: f64,
+    /// Active traffic spike; crypto miners are unaffected.
+    pub spike_multiplier: f64,
+    /// Throttling from insufficient power or cooling.
+    pub facility_multiplier: f64,
+    /// Boosts from other buildings (see `synergies`).
+    pub synergy_multiplier: f64,
+    /// Share of upkeep that could be paid this tick.
+    pub upkeep_multiplier: f64,
+}
+
+impl BuildingProduction {
+    pub fn level_multiplier(&self) -> f64 {
+        formulas::level_multiplier(self.level, self.level_bonus)
+    }
+
+    /// Production per tick after all factors.
+    pub fn total(&self) -> f64 {
+        formulas::building_production(
+            self.count,
+            self.base,
+            self.level,
+            self.level_bonus,
+            self.upgrade_multiplier
+                * self.cicd_multiplier
+                * self.global_multiplier
+                * self.effect_multiplier
+                * self.spike_multiplier
+                * self.synergy_multiplier
+                * self.facility_multiplier
+                * self.upkeep_multiplier,
+        )
+    }
+}
+
+/// Per-building production, as computed by `GameState::recalculate_production`.
+#[derive(Debug, Clone, Default)]
+pub struct ProductionBreakdown {
+    pub buildings: Vec<BuildingProduction>,
+    pub facility: FacilityLoad,
+    /// Bandwidth and storage all buildings need per tick at full output.
+    pub upkeep: Resources,
+}
+
+impl ProductionBreakdown {
+    pub fn get(&self, kind: BuildingKind) -> Option<&BuildingProduction> {
+        self.buildings.iter().find(|b| b.kind == kind)
+    }
+
+    /// Summed production per tick across all buildings.
+    pub fn total(&self) -> Resources {
+        let mut total = Resources::default();
+        for building in &self.buildings {
+            let prod = building.total();
+            match building.resource_type {
+                ResourceType::Compute => total.compute += prod,
+                ResourceType::Bandwidth => total.bandwidth += prod,
+                ResourceType::Storage => total.storage += prod,
+                ResourceType::Crypto => total.crypto += prod,
+            }
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: BuildingKind, resource_type: ResourceType, count: u32) -> BuildingProduction {
+        BuildingProduction {
+            kind,
+            resource_type,
+            base: 1.0,
+            count,
+            level: 1,
+            level_bonus: 0.5,
+            upgrade_multiplier: 2.0,
+            cicd_multiplier: 1.1,
+            global_multiplier: 1.0,
+            effect_multiplier: 1.0,
+            spike_multiplier: 1.0,
+            facility_multiplier: 1.0,
+            synergy_multiplier: 1.0,
+            upkeep_multiplier: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_building_total_multiplies_factors() {
+        let b = entry(BuildingKind::RaspberryPi, ResourceType::Compute, 2);
+        assert!((b.total() - 2.0 * 1.5 * 2.0 * 1.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_purchase_advice() {
+        let cost = Resources {
+            compute: 100.0,
+            ..Default::default()
+        };
+        let gain = Resources {
+            compute: 0.5, // 2/s
+            ..Default::default()
+        };
+        let balance = Resources {
+            compute: 60.0,
+            ..Default::default()
+        };
+        let production = Resources {
+            compute: 1.0, // 4/s
+            ..Default::default()
+        };
+        let advice = PurchaseAdvice::new(
+            Purchase::Building(BuildingKind::RaspberryPi),
+            cost,
+            gain,
+            &balance,
+            &production,
+        );
+        assert_eq!(advice.time_to_afford, Some(10.0));
+        assert_eq!(advice.payback, Some(50.0));
+    }
+
+    #[test]
+    fn test_purchase_advice_unaffordable_without_production() {
+        let cost = Resources {
+            storage: 10.0,
+            ..Default::default()
+        };
+        let advice = PurchaseAdvice::new(
+            Purchase::Upgrade(2),
+            cost,
+            Resources::default(),
+            &Resources::default(),
+            &Resources::default(),
+        );
+        assert_eq!(advice.time_to_afford, None);
+        assert_eq!(advice.payback, None);
+    }
+
+    #[test]
+    fn test_best_value_picks_shortest_payback() {
+        let make = |purchase, payback| PurchaseAdvice {
+            purchase,
+            cost: Resources::default(),
+            gain: Resources::default(),
+            time_to_afford: Some(0.0),
+            payback,
+        };
+        let advice = vec![
+            make(Purchase::Upgrade(0), Some(30.0)),
+            make(Purchase::Upgrade(1), None),
+            make(Purchase::Upgrade(2), Some(10.0)),
+        ];
+        assert_eq!(best_value(&advice), Some(Purchase::Upgrade(2)));
+        assert_eq!(best_value(&[]), None);
+    }
+
+    #[test]
+    fn test_best_value_compares_resources_separately() {
+        let advise = |purchase, cost: Resources, gain: Resources| {
+            PurchaseAdvice::new(
+                purchase,
+                cost,
+                gain,
+                &Resources::default(),
+                &Resources::default(),
+            )
+        };
+        // Lots of compute for storage: the storage is never paid back
+        let storage_for_compute = advise(
+            Purchase::Building(BuildingKind::USBDrive),
+            Resources {
+                storage: 100.0,
+                ..Default::default()
+            },
+            Resources {
+                compute: 50.0,
+                ..Default::default()
+            },
+        );
+        assert_eq!(storage_for_compute.payback, None);
+
+        // Paid in two resources: the slower one decides
+        let mixed = advise(
+            Purchase::Upgrade(7),
+            Resources {
+                compute: 100.0,
+                bandwidth: 100.0,
+                ..Default::default()
+            },
+            Resources {
+                compute: 5.0,   // 5s
+                bandwidth: 2.5, // 10s
+                ..Default::default()
+            },
+        );
+        assert_eq!(mixed.payback, Some(10.0));
+
+        let compute = advise(
+            Purchase::Building(BuildingKind::RaspberryPi),
+            Resources {
+                compute: 100.0,
+                ..Default::default()
+            },
+            Resources {
+                compute: 5.0, // 5s
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            best_value(&[storage_for_compute, mixed, compute]),
+            Some(Purchase::Building(BuildingKind::RaspberryPi))
+        );
+    }
+
+    #[test]
+    fn test_breakdown_total_by_resource() {
+        let breakdown = ProductionBreakdown {
+            buildings: vec![
+                entry(BuildingKind::RaspberryPi, ResourceType::Compute, 1),
+                entry(BuildingKind::HomeRouter, ResourceType::Bandwidth, 2),
+            ],
+            facility: FacilityLoad::default(),
+            upkeep: Resources::default(),
+        };
+        let total = breakdown.total();
+        assert!((total.compute - 3.3).abs() < 1e-9);
+        assert!((total.bandwidth - 6.6).abs() < 1e-9);
+        assert_eq!(total.storage, 0.0);
+        assert!(breakdown.get(BuildingKind::USBDrive).is_none());
+    }
+}
@@ -1,7 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::buildings::BuildingKind;
+use super::content;
+
+/// Run compute needed before a prestige is possible.
+pub const PRESTIGE_COMPUTE: f64 = 1_000_000.0;
+
 /// Calculate reputation earned from a prestige.
-/// Formula: floor(sqrt(total_compute / 1_000_000))
-pub fn prestige_reputation(compute: f64) -> f64 {
-    (compute / 1_000_000.0).sqrt().floor().max(0.0)
+/// Formula: floor(sqrt(run_compute / 1_000_000))
+pub fn prestige_reputation(run_compute: f64) -> f64 {
+    (run_compute / PRESTIGE_COMPUTE).sqrt().floor().max(0.0)
+}
+
+/// Run compute at which the next reputation point is earned.
+pub fn next_reputation_at(run_compute: f64) -> f64 {
+    (prestige_reputation(run_compute) + 1.0).powi(2) * PRESTIGE_COMPUTE
+}
+
+/// Reputation a prestige would gain per minute at `compute_per_sec`,
+/// ignoring the rounding down to whole points.
+pub fn reputation_per_minute(run_compute: f64, compute_per_sec: f64) -> f64 {
+    let now = (run_compute.max(0.0) / PRESTIGE_COMPUTE).sqrt();
+    let later = ((run_compute + compute_per_sec * 60.0).max(0.0) / PRESTIGE_COMPUTE).sqrt();
+    later - now
 }
 
 /// Calculate global multiplier from total reputation.
@@ -10,66 +32,190 @@ pub fn reputation_multiplier(reputation: f64) -> f64 {
     1.0 + 0.10 * reputation
 }
 
-/// Static achievement definition.
+/// Reputation needed to take the company public.
+pub const IPO_REPUTATION: f64 = 25.0;
+
+/// Calculate Equity earned from an IPO.
+/// Formula: floor(sqrt(reputation / 25))
+pub fn ipo_equity(reputation: f64) -> f64 {
+    (reputation / IPO_REPUTATION).sqrt().floor().max(0.0)
+}
+
+/// Each point of Equity gives +50% production, on top of reputation.
+pub fn equity_multiplier(equity: f64) -> f64 {
+    1.0 + 0.5 * equity
+}
+
+/// Permanent mechanics unlocked by total Equity. They survive every reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EquityPerk {
+    /// Runs start with 10K compute instead of 50.
+    SeedRound,
+    /// Offline progress at 50% instead of 25%.
+    AlwaysOn,
+    /// One more research slot.
+    ResearchDept,
+}
+
+impl EquityPerk {
+    pub const ALL: [EquityPerk; 3] = [
+        EquityPerk::SeedRound,
+        EquityPerk::AlwaysOn,
+        EquityPerk::ResearchDept,
+    ];
+
+    pub fn equity_required(self) -> f64 {
+        match self {
+            EquityPerk::SeedRound => 1.0,
+            EquityPerk::AlwaysOn => 2.0,
+            EquityPerk::ResearchDept => 3.0,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            EquityPerk::SeedRound => "Seed Round",
+            EquityPerk::AlwaysOn => "Always-On Ops",
+            EquityPerk::ResearchDept => "Research Dept",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            EquityPerk::SeedRound => "Runs start with 10K compute",
+            EquityPerk::AlwaysOn => "Offline progress at 50%",
+            EquityPerk::ResearchDept => "+1 research slot",
+        }
+    }
+}
+
+/// Research jobs that progress at once: one to start, one more after
+/// 1M lifetime compute and another after the first prestige, kept
+/// through IPOs.
+pub fn research_slots(lifetime_compute: f64, total_prestiges: u32) -> usize {
+    1 + usize::from(lifetime_compute >= 1_000_000.0) + usize::from(total_prestiges >= 1)
+}
+
+/// Achievement definition, loaded from content data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AchievementDef {
-    pub id: &'static str,
-    pub name: &'static str,
-    pub description: &'static str,
-}
-
-pub fn all_achievement_defs() -> Vec<AchievementDef> {
-    vec![
-        AchievementDef {
-            id: "first_build",
-            name: "Hello World",
-            description: "Purchase your first building",
-        },
-        AchievementDef {
-            id: "ten_builds",
-            name: "Sys Admin",
-            description: "Own 10 buildings total",
-        },
-        AchievementDef {
-            id: "first_upgrade",
-            name: "Patch Tuesday",
-            description: "Purchase your first upgrade",
-        },
-        AchievementDef {
-            id: "first_prestige",
-            name: "Reboot",
-            description: "Prestige for the first time",
-        },
-        AchievementDef {
-            id: "compute_1m",
-            name: "Megahertz",
-            description: "Accumulate 1M compute",
-        },
-        AchievementDef {
-            id: "compute_1b",
-            name: "Gigaflops",
-            description: "Accumulate 1B compute",
-        },
-        AchievementDef {
-            id: "compute_1t",
-            name: "Teraflops",
-            description: "Accumulate 1T compute",
-        },
-        AchievementDef {
-            id: "task_10",
-            name: "On Call",
-            description: "Complete 10 tasks",
-        },
-        AchievementDef {
-            id: "task_50",
-            name: "Incident Commander",
-            description: "Complete 50 tasks",
-        },
-        AchievementDef {
-            id: "prestige_5",
-            name: "Veteran",
-            description: "Prestige 5 times",
-        },
-    ]
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// What unlocks it. Without one, only a script can unlock it.
+    #[serde(default)]
+    pub condition: Option<AchievementCondition>,
+    #[serde(default)]
+    pub reward: Option<AchievementReward>,
+    /// Name and description stay secret until unlocked.
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+/// A data-driven unlock rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AchievementCondition {
+    BuildingsOwned(u32),
+    Owns { kind: BuildingKind, count: u32 },
+    UpgradesPurchased(usize),
+    Prestiges(u32),
+    LifetimeCompute(f64),
+    TasksCompleted(u32),
+    ChallengesCompleted(usize),
+}
+
+impl AchievementCondition {
+    /// Value the tracked stat has to reach.
+    pub fn target(&self) -> f64 {
+        match self {
+            AchievementCondition::BuildingsOwned(n) | AchievementCondition::Prestiges(n) => {
+                *n as f64
+            }
+            AchievementCondition::Owns { count, .. } => *count as f64,
+            AchievementCondition::UpgradesPurchased(n) => *n as f64,
+            AchievementCondition::LifetimeCompute(n) => *n,
+            AchievementCondition::TasksCompleted(n) => *n as f64,
+            AchievementCondition::ChallengesCompleted(n) => *n as f64,
+        }
+    }
+
+    /// What is being counted, for progress like "37/50 tasks".
+    pub fn unit(&self) -> String {
+        match self {
+            AchievementCondition::BuildingsOwned(_) => "buildings".into(),
+            AchievementCondition::Owns { kind, .. } => format!("{kind:?}"),
+            AchievementCondition::UpgradesPurchased(_) => "upgrades".into(),
+            AchievementCondition::Prestiges(_) => "prestiges".into(),
+            AchievementCondition::LifetimeCompute(_) => "CPU".into(),
+            AchievementCondition::TasksCompleted(_) => "tasks".into(),
+            AchievementCondition::ChallengesCompleted(_) => "challenges".into(),
+        }
+    }
+}
+
+/// What unlocking an achievement gives.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AchievementReward {
+    /// Permanent production boost that survives prestige.
+    ProductionMultiplier(f64),
+    Reputation(f64),
+    /// Cosmetic title shown in the header.
+    Title(String),
+}
+
+impl AchievementReward {
+    pub fn description(&self) -> String {
+        match self {
+            AchievementReward::ProductionMultiplier(mult) => format!("x{mult:.2} production"),
+            AchievementReward::Reputation(rep) => format!("+{rep:.0} reputation"),
+            AchievementReward::Title(title) => format!("title \"{title}\""),
+        }
+    }
+}
+
+/// An unlocked achievement and when it was unlocked.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "AchievementRecord")]
+pub struct UnlockedAchievement {
+    pub id: String,
+    /// `None` for achievements unlocked before times were recorded.
+    pub unlocked_at: Option<DateTime<Utc>>,
+}
+
+impl UnlockedAchievement {
+    pub fn now(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            unlocked_at: Some(Utc::now()),
+        }
+    }
+}
+
+/// Saved form of an unlocked achievement. Older saves stored bare ids.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AchievementRecord {
+    Id(String),
+    Full {
+        id: String,
+        unlocked_at: Option<DateTime<Utc>>,
+    },
+}
+
+impl From<AchievementRecord> for UnlockedAchievement {
+    fn from(record: AchievementRecord) -> Self {
+        match record {
+            AchievementRecord::Id(id) => Self {
+                id,
+                unlocked_at: None,
+            },
+            AchievementRecord::Full { id, unlocked_at } => Self { id, unlocked_at },
+        }
+    }
+}
+
+pub fn all_achievement_defs() -> &'static [AchievementDef] {
+    &content::registry().achievements
 }
 
 #[cfg(test)]
@@ -86,6 +232,26 @@ mod tests {
         assert_eq!(prestige_reputation(100_000_000.0), 10.0);
     }
 
+    #[test]
+    fn test_reputation_projection() {
+        assert_eq!(next_reputation_at(0.0), 1_000_000.0);
+        assert_eq!(next_reputation_at(1_000_000.0), 4_000_000.0);
+        assert_eq!(next_reputation_at(5_000_000.0), 9_000_000.0);
+        // 0 -> 1M compute in a minute is one reputation point
+        assert!((reputation_per_minute(0.0, 1_000_000.0 / 60.0) - 1.0).abs() < 1e-9);
+        assert!(reputation_per_minute(4_000_000.0, 1_000.0) > 0.0);
+        assert_eq!(reputation_per_minute(4_000_000.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_ipo_equity() {
+        assert_eq!(ipo_equity(24.0), 0.0);
+        assert_eq!(ipo_equity(25.0), 1.0);
+        assert_eq!(ipo_equity(100.0), 2.0);
+        assert_eq!(ipo_equity(250.0), 3.0);
+        assert!((equity_multiplier(2.0) - 2.0).abs() < 0.001);
+    }
+
     #[test]
     fn test_reputation_multiplier() {
         assert_eq!(reputation_multiplier(0.0), 1.0);
@@ -93,10 +259,30 @@ mod tests {
         assert!((reputation_multiplier(10.0) - 2.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_unlocked_achievements_read_old_and_new_saves() {
+        let old: Vec<UnlockedAchievement> =
+            serde_json::from_str(r#"["first_build", "task_10"]"#).unwrap();
+        assert_eq!(old[1].id, "task_10");
+        assert_eq!(old[1].unlocked_at, None);
+
+        let new = vec![UnlockedAchievement::now("first_build")];
+        let json = serde_json::to_string(&new).unwrap();
+        let back: Vec<UnlockedAchievement> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, new);
+    }
+
+    #[test]
+    fn test_research_slots() {
+        assert_eq!(research_slots(0.0, 0), 1);
+        assert_eq!(research_slots(2_000_000.0, 0), 2);
+        assert_eq!(research_slots(2_000_000.0, 3), 3);
+    }
+
     #[test]
     fn test_achievement_ids_unique() {
         let defs = all_achievement_defs();
-        let mut ids: Vec<_> = defs.iter().map(|d| d.id).collect();
+        let mut ids: Vec<_> = defs.iter().map(|d| &d.id).collect();
         ids.sort();
         ids.dedup();
         assert_eq!(ids.len(), defs.len());
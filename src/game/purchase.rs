@@ -0,0 +1,116 @@
+use std::fmt;
+
+use super::resources::{format_cost, Resources};
+use super::upgrades::{all_upgrades, UpgradeId};
+
+/// Why a purchase did not go through.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PurchaseError {
+    /// How much more of each resource is needed.
+    InsufficientFunds { missing: Resources },
+    /// Upgrades that must be researched first.
+    MissingPrerequisite(Vec<UpgradeId>),
+    AlreadyPurchased,
+    /// Already in the research queue.
+    InProgress,
+    /// Every research slot and the backlog are taken.
+    QueueFull,
+    /// Not unlocked yet (or, for levels, no building owned).
+    Locked,
+    /// No building or upgrade with that id.
+    NotFound,
+    /// Every market order slot is taken.
+    TooManyOrders,
+    /// A trade or order for nothing.
+    EmptyTrade,
+    /// Forbidden by the current challenge's rules.
+    ChallengeRule,
+}
+
+impl fmt::Display for PurchaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PurchaseError::InsufficientFunds { missing } => {
+                write!(f, "need {} more", format_cost(missing))
+            }
+            PurchaseError::MissingPrerequisite(ids) => {
+                let upgrades = all_upgrades();
+                let names: Vec<&str> = ids
+                    .iter()
+                    .filter_map(|id| upgrades.iter().find(|u| u.id == *id))
+                    .map(|u| u.name.as_str())
+                    .collect();
+                write!(f, "requires {}", names.join(", "))
+            }
+            PurchaseError::AlreadyPurchased => write!(f, "already purchased"),
+            PurchaseError::InProgress => write!(f, "already being researched"),
+            PurchaseError::QueueFull => write!(f, "research queue is full"),
+            PurchaseError::Locked => write!(f, "locked"),
+            PurchaseError::NotFound => write!(f, "not found"),
+            PurchaseError::TooManyOrders => write!(f, "too many open orders"),
+            PurchaseError::EmptyTrade => write!(f, "nothing to trade"),
+            PurchaseError::ChallengeRule => write!(f, "not allowed in this challenge"),
+        }
+    }
+}
+
+impl std::error::Error for PurchaseError {}
+
+/// `Ok` if `have` covers `cost`, otherwise the shortfall.
+pub fn check_funds(have: &Resources, cost: &Resources) -> Result<(), PurchaseError> {
+    if have.can_afford(cost) {
+        return Ok(());
+    }
+    let short = |need: f64, have: f64| (need - have).max(0.0);
+    Err(PurchaseError::InsufficientFunds {
+        missing: Resources {
+            compute: short(cost.compute, have.compute),
+            bandwidth: short(cost.bandwidth, have.bandwidth),
+            storage: short(cost.storage, have.storage),
+            reputation: short(cost.reputation, have.reputation),
+            crypto: short(cost.crypto, have.crypto),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_funds_reports_shortfall() {
+        let have = Resources {
+            compute: 40.0,
+            storage: 100.0,
+            ..Default::default()
+        };
+        let cost = Resources {
+            compute: 100.0,
+            storage: 50.0,
+            ..Default::default()
+        };
+        let missing = match check_funds(&have, &cost) {
+            Err(PurchaseError::InsufficientFunds { missing }) => missing,
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(missing.compute, 60.0);
+        assert_eq!(missing.storage, 0.0);
+        assert!(check_funds(&cost, &cost).is_ok());
+    }
+
+    #[test]
+    fn test_error_messages() {
+        let missing = Resources {
+            compute: 60.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            PurchaseError::InsufficientFunds { missing }.to_string(),
+            "need 60.0 CPU more"
+        );
+        assert_eq!(
+            PurchaseError::MissingPrerequisite(vec![0]).to_string(),
+            "requires Overclocking"
+        );
+    }
+}
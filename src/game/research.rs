@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+use super::resources::Resources;
+use super::upgrades::UpgradeId;
+
+/// Extra jobs that can wait in the queue beyond the active slots.
+pub const QUEUE_BACKLOG: usize = 3;
+
+/// An upgrade being researched. Its cost is paid up front and refunded
+/// in full if cancelled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResearchJob {
+    pub id: UpgradeId,
+    pub cost: Resources,
+    pub total_ticks: u64,
+    pub progress_ticks: u64,
+}
+
+impl ResearchJob {
+    pub fn new(id: UpgradeId, cost: Resources, total_ticks: u64) -> Self {
+        Self {
+            id,
+            cost,
+            total_ticks,
+            progress_ticks: 0,
+        }
+    }
+
+    pub fn remaining_ticks(&self) -> u64 {
+        self.total_ticks.saturating_sub(self.progress_ticks)
+    }
+
+    /// Progress from 0.0 to 1.0.
+    pub fn progress(&self) -> f64 {
+        if self.total_ticks == 0 {
+            1.0
+        } else {
+            self.progress_ticks as f64 / self.total_ticks as f64
+        }
+    }
+}
+
+/// Upgrades queued for research, oldest first. Up to `slots` jobs whose
+/// prerequisites are done progress at once; the rest wait.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResearchQueue {
+    pub jobs: Vec<ResearchJob>,
+}
+
+impl ResearchQueue {
+    pub fn contains(&self, id: UpgradeId) -> bool {
+        self.jobs.iter().any(|j| j.id == id)
+    }
+
+    pub fn is_full(&self, slots: usize) -> bool {
+        self.jobs.len() >= slots + QUEUE_BACKLOG
+    }
+
+    pub fn push(&mut self, job: ResearchJob) {
+        self.jobs.push(job);
+    }
+
+    pub fn remove(&mut self, id: UpgradeId) -> Option<ResearchJob> {
+        let index = self.jobs.iter().position(|j| j.id == id)?;
+        Some(self.jobs.remove(index))
+    }
+
+    /// Ids of the jobs that progress this tick: the first `slots` jobs
+    /// that `ready` says can run.
+    pub fn active(&self, slots: usize, ready: impl Fn(UpgradeId) -> bool) -> Vec<UpgradeId> {
+        self.jobs
+            .iter()
+            .filter(|j| ready(j.id))
+            .take(slots)
+            .map(|j| j.id)
+            .collect()
+    }
+
+    /// Advance the given jobs by `ticks` and remove the ones that finished.
+    pub fn advance(&mut self, active: &[UpgradeId], ticks: u64) -> Vec<UpgradeId> {
+        let mut finished = Vec::new();
+        for job in self.jobs.iter_mut().filter(|j| active.contains(&j.id)) {
+            job.progress_ticks = (job.progress_ticks + ticks).min(job.total_ticks);
+            if job.remaining_ticks() == 0 {
+                finished.push(job.id);
+            }
+        }
+        self.jobs.retain(|j| !finished.contains(&j.id));
+        finished
+    }
+
+    pub fn clear(&mut self) {
+        self.jobs.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(jobs: &[(UpgradeId, u64)]) -> ResearchQueue {
+        ResearchQueue {
+            jobs: jobs
+                .iter()
+                .map(|(id, ticks)| ResearchJob::new(*id, Resources::default(), *ticks))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_active_respects_slots_and_readiness() {
+        let q = queue(&[(0, 10), (1, 10), (2, 10)]);
+        assert_eq!(q.active(1, |_| true), vec![0]);
+        assert_eq!(q.active(2, |id| id != 0), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_advance_finishes_jobs() {
+        let mut q = queue(&[(0, 10), (1, 20)]);
+        assert!(q.advance(&[0, 1], 5).is_empty());
+        assert_eq!(q.jobs[0].progress(), 0.5);
+
+        assert_eq!(q.advance(&[0, 1], 5), vec![0]);
+        assert_eq!(q.jobs.len(), 1);
+        assert_eq!(q.jobs[0].remaining_ticks(), 10);
+        assert!(!q.contains(0));
+    }
+
+    #[test]
+    fn test_is_full() {
+        let q = queue(&[(0, 1), (1, 1), (2, 1), (3, 1)]);
+        assert!(q.is_full(1));
+        assert!(!q.is_full(2));
+    }
+}
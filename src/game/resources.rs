@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Resources {
     pub compute: f64,
     pub bandwidth: f64,
@@ -33,6 +34,53 @@ impl Resources {
         self.reputation += other.reputation;
         self.crypto += other.crypto;
     }
+
+    /// Raise any negative amount to zero, e.g. after a penalty.
+    pub fn clamp_non_negative(&mut self) {
+        self.compute = self.compute.max(0.0);
+        self.bandwidth = self.bandwidth.max(0.0);
+        self.storage = self.storage.max(0.0);
+        self.reputation = self.reputation.max(0.0);
+        self.crypto = self.crypto.max(0.0);
+    }
+
+    /// What is left of this gross flow after paying `upkeep`.
+    pub fn net_flow(&self, upkeep: &Resources) -> Resources {
+        let mut net = self.clone();
+        net.subtract(upkeep);
+        net
+    }
+
+    /// Sum of all resource amounts, for rough cross-resource comparisons.
+    pub fn sum(&self) -> f64 {
+        self.compute + self.bandwidth + self.storage + self.reputation + self.crypto
+    }
+
+    pub fn scaled(&self, factor: f64) -> Resources {
+        Resources {
+            compute: self.compute * factor,
+            bandwidth: self.bandwidth * factor,
+            storage: self.storage * factor,
+            reputation: self.reputation * factor,
+            crypto: self.crypto * factor,
+        }
+    }
+}
+
+/// Format a cost like "2.00K CPU + 500 SSD", skipping zero amounts.
+pub fn format_cost(cost: &Resources) -> String {
+    let parts: Vec<String> = [
+        (cost.compute, "CPU"),
+        (cost.bandwidth, "BW"),
+        (cost.storage, "SSD"),
+        (cost.reputation, "REP"),
+        (cost.crypto, "BTC"),
+    ]
+    .iter()
+    .filter(|(v, _)| *v > 0.0)
+    .map(|(v, label)| format!("{} {}", format_si(*v), label))
+    .collect();
+    parts.join(" + ")
 }
 
 /// Format a number with SI suffixes: 1.23K, 4.56M, etc.
@@ -14,14 +14,21 @@ struct SaveData {
     pub version: u32,
 }
 
-const SAVE_VERSION: u32 = 1;
+/// Bumped whenever loading an older save needs more than serde defaults;
+/// see `migrate`.
+const SAVE_VERSION: u32 = 3;
 
-pub fn save_path() -> PathBuf {
+/// Directory holding the save file and other persistent game data.
+pub fn data_dir() -> PathBuf {
     let data_dir = dirs::data_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join("idle-terminal");
     std::fs::create_dir_all(&data_dir).ok();
-    data_dir.join(SAVE_FILE)
+    data_dir
+}
+
+pub fn save_path() -> PathBuf {
+    data_dir().join(SAVE_FILE)
 }
 
 pub fn save_game(state: &GameState) -> Result<()> {
@@ -41,6 +48,22 @@ pub fn save_game(state: &GameState) -> Result<()> {
     Ok(())
 }
 
+/// Bring a save written by an older version up to date.
+fn migrate(state: &mut GameState, version: u32) {
+    if version < 2 {
+        // Run totals weren't tracked: count the balance as this run's
+        if state.run_compute == 0.0 {
+            state.run_compute = state.resources.compute;
+        }
+        // Power and cooling caps are new; cover what's already built
+        state.grant_facility_capacity();
+    }
+    if version < 3 {
+        // Prestiges before the last IPO weren't kept
+        state.total_prestiges = state.total_prestiges.max(state.prestige_count);
+    }
+}
+
 pub struct LoadResult {
     pub state: GameState,
     pub offline_ticks: u64,
@@ -57,6 +80,12 @@ pub fn load_game() -> Result<Option<LoadResult>> {
     let save_data: SaveData = serde_json::from_str(&json)?;
 
     let mut state = save_data.game_state;
+    migrate(&mut state, save_data.version);
+    state.sync_content();
+    // Traffic spikes don't carry over the time away
+    state.traffic_spike_remaining = 0;
+    state.traffic_spike_multiplier = 1.0;
+    state.recalculate_production();
 
     // Calculate offline progression
     let now = Utc::now();
@@ -64,25 +93,28 @@ pub fn load_game() -> Result<Option<LoadResult>> {
     let elapsed_ms = elapsed.num_milliseconds().max(0) as u64;
     let missed_ticks = elapsed_ms / 250; // 4Hz game tick
 
-    // Cap offline ticks at 8 hours = 115,200 ticks
-    let offline_ticks = missed_ticks.min(115_200);
+    // Cap offline ticks at 8 hours = 115,200 ticks. Some challenges
+    // forbid offline progress entirely.
+    let offline_ticks = if state.modifiers.no_offline {
+        0
+    } else {
+        missed_ticks.min(115_200)
+    };
 
     // Record resources before offline progression
     let resources_before = state.resources.clone();
 
     // Apply offline production at reduced rate
     let efficiency = state.offline_efficiency;
-    let mut offline_production = state.production_per_tick.clone();
-    offline_production.compute *= efficiency;
-    offline_production.bandwidth *= efficiency;
-    offline_production.storage *= efficiency;
-    offline_production.crypto *= efficiency;
-
-    for _ in 0..offline_ticks {
-        state.resources.add(&offline_production);
-        state.total_ticks += 1;
+    state.apply_offline_production(offline_ticks, efficiency);
+    state.total_ticks += offline_ticks;
+    for incident in &mut state.incidents {
+        incident.shift(offline_ticks);
     }
 
+    // Research keeps running while away
+    state.advance_research(offline_ticks);
+
     // Calculate earnings
     let offline_earnings = super::resources::Resources {
         compute: state.resources.compute - resources_before.compute,
@@ -110,6 +142,7 @@ pub fn delete_save() -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::buildings::BuildingKind;
 
     #[test]
     fn test_save_path_is_valid() {
@@ -120,9 +153,8 @@ mod tests {
     #[test]
     fn test_save_and_load_roundtrip() {
         // Use a temp dir to avoid polluting the real save location
-        let dir = std::env::temp_dir().join("idle_terminal_test");
-        std::fs::create_dir_all(&dir).ok();
-        let path = dir.join("test_save.json");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test_save.json");
 
         let state = GameState::new();
         let save_data = SaveData {
@@ -139,9 +171,27 @@ mod tests {
 
         assert_eq!(loaded.game_state.resources.compute, state.resources.compute);
         assert_eq!(loaded.version, SAVE_VERSION);
+    }
 
-        // Cleanup
-        std::fs::remove_file(&path).ok();
-        std::fs::remove_dir(&dir).ok();
+    #[test]
+    fn test_migrate_only_touches_old_saves() {
+        // A fresh run that started with 10K compute hasn't produced any
+        let mut state = GameState::new();
+        state.resources.compute = 10_000.0;
+        state.buildings.get_mut(&BuildingKind::VPS).unwrap().count = 40;
+        migrate(&mut state, SAVE_VERSION);
+        assert_eq!(state.run_compute, 0.0);
+        assert_eq!(state.buildings[&BuildingKind::PDU].count, 0);
+
+        // Saves from before power caps get facilities for what they own
+        migrate(&mut state, 1);
+        assert_eq!(state.run_compute, 10_000.0);
+        assert!(state.buildings[&BuildingKind::PDU].count > 0);
+        state.recalculate_production();
+        assert!(!state.production_breakdown.facility.is_throttled());
+
+        state.prestige_count = 4;
+        migrate(&mut state, 2);
+        assert_eq!(state.total_prestiges, 4);
     }
 }
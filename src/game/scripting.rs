@@ -0,0 +1,420 @@
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{CallFnOptions, Dynamic, Engine, Map, Scope, AST};
+use std::sync::{Arc, Mutex};
+
+use super::events::GameEventKind;
+use super::notifications::NotificationKind;
+use super::resources::Resources;
+use super::state::GameState;
+use super::tasks::{TaskDefinition, TaskKind};
+
+/// Rhai operations a single hook call may run before it is aborted, so a
+/// runaway script can't stall the game loop.
+pub const OPERATION_BUDGET: u64 = 50_000;
+/// Failed calls before a script is switched off.
+const MAX_FAILURES: u32 = 3;
+/// Scripted tasks waiting to be handed out.
+const MAX_QUEUED_TASKS: usize = 5;
+/// Time limit of scripted tasks unless the script gives one.
+const DEFAULT_TASK_SECONDS: i64 = 30;
+
+/// A script as loaded from a pack's `scripts/` folder.
+#[derive(Debug, Clone)]
+pub struct ScriptSource {
+    /// `<pack id>:<file stem>`.
+    pub name: String,
+    pub source: String,
+}
+
+/// What a script asked the game to do. Scripts never touch `GameState`
+/// directly; they queue commands that are applied after the call returns.
+#[derive(Debug, Clone)]
+enum ScriptCommand {
+    Grant(Resources),
+    Event { message: String, good: bool, effect: Resources },
+    Task(TaskDefinition),
+    Unlock(String),
+}
+
+struct Script {
+    name: String,
+    ast: AST,
+    failures: u32,
+}
+
+impl Script {
+    fn has_hook(&self, hook: &str) -> bool {
+        self.ast.iter_functions().any(|f| f.name == hook)
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.failures >= MAX_FAILURES
+    }
+
+    /// Id of the pack the script came from.
+    fn pack(&self) -> &str {
+        self.name.split_once(':').map_or(self.name.as_str(), |(pack, _)| pack)
+    }
+}
+
+/// Runs pack scripts once per second and on game events.
+///
+/// Scripts may define `on_tick(game)` and `on_event(game, slug)`. `game` is
+/// a read-only snapshot map; scripts act through `grant`, `event`, `task`
+/// and `unlock`. `unlock` only reaches achievements in the script's own
+/// pack namespace.
+pub struct ScriptHost {
+    engine: Engine,
+    scripts: Vec<Script>,
+    commands: Arc<Mutex<Vec<ScriptCommand>>>,
+    /// Last tick whose events have been passed to `on_event`. Unset until
+    /// the first run, so events from a loaded save aren't replayed.
+    last_tick: Option<u64>,
+}
+
+impl ScriptHost {
+    /// Compile `sources`. Scripts that fail to compile are skipped with a
+    /// warning.
+    pub fn new(sources: &[ScriptSource]) -> Self {
+        let commands = Arc::new(Mutex::new(Vec::new()));
+        let engine = sandboxed_engine(&commands);
+        let scripts = sources
+            .iter()
+            .filter_map(|source| match engine.compile(&source.source) {
+                Ok(ast) => Some(Script {
+                    name: source.name.clone(),
+                    ast,
+                    failures: 0,
+                }),
+                Err(e) => {
+                    tracing::warn!("Skipping script {}: {e}", source.name);
+                    None
+                }
+            })
+            .collect();
+        Self {
+            engine,
+            scripts,
+            commands,
+            last_tick: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty()
+    }
+
+    /// Run hooks for the current tick and apply what the scripts asked for.
+    pub fn tick(&mut self, state: &mut GameState) {
+        if self.is_empty() || !state.total_ticks.is_multiple_of(4) {
+            return;
+        }
+        let since = self
+            .last_tick
+            .unwrap_or(state.total_ticks.saturating_sub(4));
+        let slugs: Vec<&'static str> = state
+            .event_log
+            .iter()
+            .filter(|e| e.tick > since)
+            .map(|e| e.kind.slug())
+            .collect();
+        self.last_tick = Some(state.total_ticks);
+
+        let game = snapshot(state);
+        let mut failed = Vec::new();
+        let mut commands = Vec::new();
+        for script in self.scripts.iter_mut().filter(|s| !s.is_disabled()) {
+            let mut calls: Vec<(&str, Vec<Dynamic>)> = Vec::new();
+            if script.has_hook("on_tick") {
+                calls.push(("on_tick", vec![game.clone().into()]));
+            }
+            if script.has_hook("on_event") {
+                for slug in &slugs {
+                    calls.push(("on_event", vec![game.clone().into(), (*slug).into()]));
+                }
+            }
+            for (hook, args) in calls {
+                let options = CallFnOptions::new().eval_ast(false);
+                let result = self.engine.call_fn_with_options::<Dynamic>(
+                    options,
+                    &mut Scope::new(),
+                    &script.ast,
+                    hook,
+                    args,
+                );
+                if let Err(e) = result {
+                    script.failures += 1;
+                    tracing::warn!("Script {} failed in {hook}: {e}", script.name);
+                    if script.is_disabled() {
+                        failed.push(script.name.clone());
+                        break;
+                    }
+                }
+            }
+
+            // Remember which pack asked for what
+            let queued = {
+                let mut queued = self.commands.lock().unwrap_or_else(|e| e.into_inner());
+                std::mem::take(&mut *queued)
+            };
+            let pack = script.pack();
+            commands.extend(queued.into_iter().map(|c| (pack.to_string(), c)));
+        }
+
+        for name in failed {
+            state.notify(
+                NotificationKind::Warning,
+                format!("Script {name} disabled after repeated errors"),
+            );
+        }
+        for (pack, command) in commands {
+            apply(command, &pack, state);
+        }
+    }
+}
+
+/// An engine that can't reach the filesystem, can't `eval` and gives up
+/// after `OPERATION_BUDGET` operations.
+fn sandboxed_engine(commands: &Arc<Mutex<Vec<ScriptCommand>>>) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_module_resolver(DummyModuleResolver::new())
+        .set_max_operations(OPERATION_BUDGET)
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(4_096)
+        .set_max_array_size(1_024)
+        .set_max_map_size(256)
+        .disable_symbol("eval")
+        .on_print(|text| tracing::info!("[script] {text}"))
+        .on_debug(|text, _, _| tracing::debug!("[script] {text}"));
+
+    let queue = |commands: &Arc<Mutex<Vec<ScriptCommand>>>| {
+        let commands = Arc::clone(commands);
+        move |command| {
+            commands
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(command)
+        }
+    };
+
+    let push = queue(commands);
+    engine.register_fn("grant", move |resources: Map| {
+        push(ScriptCommand::Grant(resources_from_map(&resources)))
+    });
+    let push = queue(commands);
+    engine.register_fn("event", move |message: &str, good: bool| {
+        push(ScriptCommand::Event {
+            message: message.to_string(),
+            good,
+            effect: Resources::default(),
+        })
+    });
+    let push = queue(commands);
+    engine.register_fn("event", move |message: &str, good: bool, effect: Map| {
+        push(ScriptCommand::Event {
+            message: message.to_string(),
+            good,
+            effect: resources_from_map(&effect),
+        })
+    });
+    let push = queue(commands);
+    engine.register_fn("task", move |name: &str, command: &str, reward: Map| {
+        let task = scripted_task(name, command, &reward, DEFAULT_TASK_SECONDS);
+        push(ScriptCommand::Task(task))
+    });
+    let push = queue(commands);
+    engine.register_fn(
+        "task",
+        move |name: &str, command: &str, reward: Map, seconds: i64| {
+            push(ScriptCommand::Task(scripted_task(name, command, &reward, seconds)))
+        },
+    );
+    let push = queue(commands);
+    engine.register_fn("unlock", move |id: &str| push(ScriptCommand::Unlock(id.to_string())));
+    engine
+}
+
+fn scripted_task(name: &str, command: &str, reward: &Map, seconds: i64) -> TaskDefinition {
+    TaskDefinition {
+        name: name.to_string(),
+        kind: TaskKind::TypeCommand {
+            command: command.to_string(),
+        },
+        reward: resources_from_map(reward),
+        time_limit_ticks: (seconds.clamp(5, 600) * 4) as u32,
+        difficulty: 1,
+        incident: None,
+    }
+}
+
+fn resources_from_map(map: &Map) -> Resources {
+    let get = |key: &str| {
+        map.get(key)
+            .and_then(|v| v.as_float().ok().or_else(|| v.as_int().ok().map(|i| i as f64)))
+            .filter(|v| v.is_finite())
+            .unwrap_or(0.0)
+    };
+    Resources {
+        compute: get("compute"),
+        bandwidth: get("bandwidth"),
+        storage: get("storage"),
+        reputation: get("reputation"),
+        crypto: get("crypto"),
+    }
+}
+
+/// Read-only view of the game handed to hooks.
+fn snapshot(state: &GameState) -> Map {
+    let mut game = Map::new();
+    let resources = &state.resources;
+    game.insert("compute".into(), resources.compute.into());
+    game.insert("bandwidth".into(), resources.bandwidth.into());
+    game.insert("storage".into(), resources.storage.into());
+    game.insert("reputation".into(), resources.reputation.into());
+    game.insert("crypto".into(), resources.crypto.into());
+    game.insert("ticks".into(), (state.total_ticks as i64).into());
+    game.insert("prestiges".into(), (state.prestige_count as i64).into());
+    game.insert("tasks_completed".into(), (state.tasks_completed as i64).into());
+    game.insert("lifetime_compute".into(), state.lifetime_compute.into());
+    game.insert("run_compute".into(), state.run_compute.into());
+
+    let mut buildings = Map::new();
+    for (kind, building) in &state.buildings {
+        buildings.insert(kind.id().into(), (building.count as i64).into());
+    }
+    game.insert("buildings".into(), buildings.into());
+    game
+}
+
+/// Whether `id` is namespaced under `pack`, e.g. `quantum:first_qubit`.
+fn in_namespace(pack: &str, id: &str) -> bool {
+    id.split_once(':').is_some_and(|(namespace, _)| namespace == pack)
+}
+
+fn apply(command: ScriptCommand, pack: &str, state: &mut GameState) {
+    match command {
+        ScriptCommand::Grant(resources) => {
+            state.resources.add(&resources);
+            state.resources.clamp_non_negative();
+            state.stats.record_reputation(resources.reputation);
+        }
+        ScriptCommand::Event {
+            message,
+            good,
+            effect,
+        } => state.trigger_event(GameEventKind::Custom {
+            id: "script".into(),
+            message,
+            effect,
+            good,
+        }),
+        ScriptCommand::Task(task) if state.task_queue.len() < MAX_QUEUED_TASKS => {
+            state.task_queue.push_back(task);
+        }
+        ScriptCommand::Task(_) => {}
+        ScriptCommand::Unlock(id) if in_namespace(pack, &id) => {
+            state.unlock_achievement(&id);
+        }
+        ScriptCommand::Unlock(id) => {
+            tracing::warn!("Pack {pack} can't unlock {id}: not in its namespace");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host(source: &str) -> ScriptHost {
+        ScriptHost::new(&[ScriptSource {
+            name: "test:script".into(),
+            source: source.into(),
+        }])
+    }
+
+    #[test]
+    fn test_on_tick_can_grant_and_queue_tasks() {
+        let mut state = GameState::new();
+        let mut scripts = host(
+            r#"
+            fn on_tick(game) {
+                if game.compute >= 50.0 {
+                    grant(#{ compute: 25 });
+                    task("Flush cache", "redis-cli flushall", #{ compute: 100.0 });
+                }
+            }
+            "#,
+        );
+        let before = state.resources.compute;
+        scripts.tick(&mut state);
+        assert_eq!(state.resources.compute, before + 25.0);
+        assert_eq!(state.task_queue.len(), 1);
+        assert_eq!(state.task_queue[0].time_limit_ticks, 120);
+    }
+
+    #[test]
+    fn test_on_event_fires_for_new_events() {
+        let mut state = GameState::new();
+        let mut scripts = host(
+            r#"
+            fn on_event(game, slug) {
+                if slug == "prestige" { event("The board is pleased", true, #{ crypto: 1.0 }); }
+            }
+            "#,
+        );
+        state.total_ticks = 4;
+        state.log_event(GameEventKind::Prestige { reputation: 1.0 });
+        scripts.tick(&mut state);
+        assert_eq!(state.resources.crypto, 1.0);
+        assert_eq!(state.event_log.back().unwrap().kind.slug(), "custom");
+
+        // Already-seen events don't fire again
+        state.total_ticks = 8;
+        scripts.tick(&mut state);
+        assert_eq!(state.resources.crypto, 1.0);
+    }
+
+    #[test]
+    fn test_unlock_is_limited_to_own_pack() {
+        assert!(in_namespace("test", "test:secret"));
+        assert!(!in_namespace("test", "first_build"));
+        assert!(!in_namespace("test", "other:secret"));
+        assert!(!in_namespace("test", "testing:secret"));
+
+        let mut state = GameState::new();
+        let mut scripts = host(r#"fn on_tick(game) { unlock("first_build"); }"#);
+        state.total_ticks = 4;
+        scripts.tick(&mut state);
+        assert!(!state.has_achievement("first_build"));
+    }
+
+    #[test]
+    fn test_runaway_scripts_are_stopped_and_disabled() {
+        let mut state = GameState::new();
+        let mut scripts = host("fn on_tick(game) { loop { } }");
+        for second in 1..=MAX_FAILURES as u64 + 1 {
+            state.total_ticks = second * 4;
+            scripts.tick(&mut state);
+        }
+        assert!(scripts.scripts[0].is_disabled());
+        assert_eq!(scripts.scripts[0].failures, MAX_FAILURES);
+        assert!(state
+            .drain_notifications()
+            .iter()
+            .any(|n| n.message.contains("disabled")));
+    }
+
+    #[test]
+    fn test_sandbox_blocks_eval_and_imports() {
+        let scripts = host(r#"fn on_tick(game) { eval("grant(#{ compute: 1 })"); }"#);
+        assert!(scripts.is_empty());
+
+        let mut state = GameState::new();
+        let mut scripts = host(r#"fn on_tick(game) { import "std" as s; }"#);
+        state.total_ticks = 4;
+        scripts.tick(&mut state);
+        assert_eq!(scripts.scripts[0].failures, 1);
+    }
+}
@@ -1,14 +1,58 @@
+use chrono::NaiveDate;
 use rand::SeedableRng;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 
-use super::buildings::{all_building_defs, BuildingInstance, BuildingKind, ResourceType};
-use super::events::{apply_event, maybe_generate_event, GameEvent, GameEventKind};
+use super::buildings::{all_building_defs, BuildingInstance, BuildingKind, ResourceType, Upkeep};
+use super::events::{
+    apply_event, maybe_generate_custom_event, maybe_generate_event, ActiveEffect, EventSeverity, GameEvent, GameEventKind,
+};
+use super::challenges::{self, ActiveChallenge, RunModifiers};
+use super::content;
+use super::daily::{self, DailyResult};
+use super::history::{History, Sample};
+use super::incidents::{Incident, IncidentState, IncidentUpdate};
+use super::journal::JournalEntry;
+use super::market::{Market, Order, TradeAsset, TradeSide};
+use super::notifications::{Notification, NotificationKind};
+use super::production::{BuildingProduction, ProductionBreakdown, Purchase, PurchaseAdvice};
+use super::formulas;
+use super::progression::{
+    self, AchievementCondition, AchievementDef, AchievementReward, EquityPerk,
+    UnlockedAchievement,
+};
+use super::purchase::{check_funds, PurchaseError};
+use super::research::{ResearchJob, ResearchQueue};
 use super::resources::Resources;
-use super::progression;
-use super::upgrades::{all_upgrades, Upgrade, UpgradeEffect, UpgradeId};
-
-const MAX_EVENT_LOG: usize = 100;
+use super::stats::Stats;
+use super::synergies;
+use super::tasks::TaskDefinition;
+use super::upgrades::{all_upgrades, dependents, ResearchStatus, Upgrade, UpgradeEffect, UpgradeId};
+
+const MAX_EVENT_LOG: usize = 1000;
+/// Journal entries kept while nothing drains the queue (e.g. headless
+/// states); the oldest are dropped past this.
+const MAX_JOURNAL_QUEUE: usize = 1000;
+/// Offline progress is applied a minute at a time.
+const OFFLINE_CHUNK_TICKS: u64 = 240;
+/// Repeats of the same failed purchase within this many ticks (e.g. a
+/// held key) are not toasted or logged again.
+const FAILURE_REPEAT_TICKS: u64 = 8;
+
+/// How deep a reset goes. Each layer wipes everything the one before it
+/// does. Equity, achievements, completed challenges, stats and the event
+/// log survive every layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ResetLayer {
+    /// Prestige or a challenge start: resources except reputation,
+    /// buildings, upgrades, research, the crypto market and its open
+    /// orders, run multipliers, traffic spikes, outages and production
+    /// history. Queued research and open orders are dropped without a
+    /// refund; what they hold is lost with the rest of the run's resources.
+    Run,
+    /// IPO: also reputation and the prestige count.
+    Ipo,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameState {
@@ -23,24 +67,91 @@ pub struct GameState {
     pub event_log: VecDeque<GameEvent>,
     pub traffic_spike_remaining: u32,
     pub traffic_spike_multiplier: f64,
+    #[serde(default)]
+    pub active_effects: Vec<ActiveEffect>,
+    /// Incidents not yet resolved.
+    #[serde(default)]
+    pub incidents: Vec<Incident>,
+    #[serde(default)]
+    pub next_incident_id: u32,
+    /// Per-building factors behind `production_per_tick`.
+    #[serde(skip, default)]
+    pub production_breakdown: ProductionBreakdown,
+    /// Share of building upkeep paid on the last tick.
+    #[serde(skip, default = "full_upkeep")]
+    pub upkeep_satisfaction: f64,
     #[serde(skip, default = "default_rng")]
     pub rng: rand::rngs::StdRng,
     #[serde(default)]
     pub prestige_count: u32,
+    /// Prestiges over every IPO. Never reset, so it numbers runs.
+    #[serde(default)]
+    pub total_prestiges: u32,
+    /// Second prestige currency, earned by trading in reputation at an IPO.
+    #[serde(default)]
+    pub equity: f64,
+    #[serde(default)]
+    pub ipo_count: u32,
     #[serde(default)]
     pub lifetime_compute: f64,
+    /// Compute produced this run, online and offline. Drives prestige, so
+    /// spending or losing compute doesn't cost reputation.
+    #[serde(default)]
+    pub run_compute: f64,
     #[serde(default)]
     pub tasks_completed: u32,
     #[serde(default)]
-    pub achievements: Vec<String>,
+    pub achievements: Vec<UnlockedAchievement>,
+    #[serde(default)]
+    pub stats: Stats,
+    #[serde(default)]
+    pub history: History,
+    #[serde(default)]
+    pub research: ResearchQueue,
+    #[serde(default)]
+    pub market: Market,
+    /// Content packs active when the game was saved, plus any that were
+    /// missing on a later load, so reinstalling them restores their content.
+    #[serde(default)]
+    pub packs: Vec<String>,
+    /// Purchased upgrades whose pack is missing, re-applied when it's back.
+    #[serde(default)]
+    pub missing_upgrades: Vec<UpgradeId>,
+    /// Rules of the current run; the default outside challenges.
+    #[serde(default)]
+    pub modifiers: RunModifiers,
+    #[serde(default)]
+    pub challenge: Option<ActiveChallenge>,
+    #[serde(default)]
+    pub completed_challenges: Vec<String>,
+    /// Date of the last daily whose reputation bonus was paid.
+    #[serde(default)]
+    pub last_daily_reward: Option<NaiveDate>,
+    /// Tasks requested by scripts, handed out before random ones.
+    #[serde(skip, default)]
+    pub task_queue: VecDeque<TaskDefinition>,
+    /// Finished daily runs not yet written to the leaderboard.
+    #[serde(skip, default)]
+    pub daily_results: Vec<DailyResult>,
+    /// Events not yet written to the on-disk journal.
+    #[serde(skip, default)]
+    pub journal_queue: VecDeque<JournalEntry>,
+    /// Toasts for the UI, not yet picked up by the notification manager.
+    #[serde(skip, default)]
+    pub notifications: Vec<Notification>,
+    /// The last failed purchase and the tick it was last tried.
     #[serde(skip, default)]
-    pub compute_history: VecDeque<u64>,
+    last_failure: Option<(String, u64)>,
 }
 
 fn default_rng() -> rand::rngs::StdRng {
     rand::rngs::StdRng::from_entropy()
 }
 
+fn full_upkeep() -> f64 {
+    1.0
+}
+
 impl GameState {
     pub fn new() -> Self {
         let mut buildings = HashMap::new();
@@ -63,12 +174,35 @@ impl GameState {
             event_log: VecDeque::new(),
             traffic_spike_remaining: 0,
             traffic_spike_multiplier: 1.0,
+            active_effects: Vec::new(),
+            incidents: Vec::new(),
+            next_incident_id: 0,
+            production_breakdown: ProductionBreakdown::default(),
+            upkeep_satisfaction: 1.0,
             rng: rand::rngs::StdRng::from_entropy(),
             prestige_count: 0,
+            total_prestiges: 0,
+            equity: 0.0,
+            ipo_count: 0,
             lifetime_compute: 0.0,
+            run_compute: 0.0,
             tasks_completed: 0,
             achievements: Vec::new(),
-            compute_history: VecDeque::new(),
+            stats: Stats::default(),
+            history: History::default(),
+            research: ResearchQueue::default(),
+            market: Market::default(),
+            packs: content::registry().pack_ids(),
+            missing_upgrades: Vec::new(),
+            modifiers: RunModifiers::default(),
+            challenge: None,
+            completed_challenges: Vec::new(),
+            last_daily_reward: None,
+            task_queue: VecDeque::new(),
+            daily_results: Vec::new(),
+            journal_queue: VecDeque::new(),
+            notifications: Vec::new(),
+            last_failure: None,
         };
         state.recalculate_production();
         state
@@ -77,41 +211,64 @@ impl GameState {
     pub fn tick(&mut self) {
         self.total_ticks += 1;
 
-        // Apply production (with traffic spike multiplier)
-        let mut production = self.production_per_tick.clone();
+        let production = self.apply_production(1.0);
+
+        // Count down the traffic spike; it is part of the breakdown
         if self.traffic_spike_remaining > 0 {
-            production.compute *= self.traffic_spike_multiplier;
-            production.bandwidth *= self.traffic_spike_multiplier;
-            production.storage *= self.traffic_spike_multiplier;
             self.traffic_spike_remaining -= 1;
+            if self.traffic_spike_remaining == 0 {
+                self.traffic_spike_multiplier = 1.0;
+                self.recalculate_production();
+            }
+        }
+
+        // Expire temporary effects
+        if !self.active_effects.is_empty() {
+            for effect in &mut self.active_effects {
+                effect.remaining_ticks = effect.remaining_ticks.saturating_sub(1);
+            }
+            let before = self.active_effects.len();
+            self.active_effects.retain(|e| e.remaining_ticks > 0);
+            if self.active_effects.len() != before {
+                self.recalculate_production();
+            }
         }
-        self.resources.add(&production);
+
+        self.advance_research(1);
+        self.advance_incidents();
 
         // Track lifetime stats
         self.lifetime_compute += production.compute;
-
-        // Update sparkline history every 4 ticks (1 second)
-        if self.total_ticks % 4 == 0 {
-            self.compute_history
-                .push_back((self.resources.compute * 100.0) as u64);
-            if self.compute_history.len() > 60 {
-                self.compute_history.pop_front();
-            }
+        self.stats.record_tick(&production);
+
+        // Sample history every 4 ticks (1 second)
+        if self.total_ticks.is_multiple_of(4) {
+            self.history.record(Sample {
+                resources: self.resources.clone(),
+                production,
+            });
+            self.step_market();
+            self.check_challenge();
         }
 
         // Try to generate a random event
-        let monitoring_count = self
-            .buildings
-            .get(&BuildingKind::MonitoringStack)
-            .map(|b| b.count)
-            .unwrap_or(0);
-
+        let buildings = &self.buildings;
+        let count = |kind| buildings.get(&kind).map(|b| b.count).unwrap_or(0);
         if let Some(event) = maybe_generate_event(
             &mut self.rng,
             self.total_ticks,
-            monitoring_count,
             self.resources.compute,
-        ) {
+            self.modifiers.event_rate,
+            |kind| {
+                all_building_defs()
+                    .iter()
+                    .find(|d| d.kind == kind)
+                    .map_or(1.0, |d| synergies::failure_odds(d.tier, count))
+            },
+        )
+        .or_else(|| {
+            maybe_generate_custom_event(&mut self.rng, self.total_ticks, &content::registry().events)
+        }) {
             // Apply immediate effects
             apply_event(&event.kind, &mut self.resources);
 
@@ -123,163 +280,844 @@ impl GameState {
             {
                 self.traffic_spike_remaining = *duration_ticks;
                 self.traffic_spike_multiplier = *multiplier;
+                self.recalculate_production();
+            }
+
+            // Overloads and failures throttle a building for a while
+            if let Some(effect) = ActiveEffect::from_event(&event.kind) {
+                self.active_effects.push(effect);
+                self.recalculate_production();
+            }
+
+            // Some events start an incident that needs a response
+            let id = self.next_incident_id + 1;
+            let monitoring = self
+                .buildings
+                .get(&BuildingKind::MonitoringStack)
+                .map_or(0, |b| b.count);
+            if let Some(incident) =
+                Incident::from_event(&event.kind, id, self.total_ticks, monitoring)
+            {
+                self.next_incident_id = id;
+                self.incidents.push(incident);
+            }
+
+            self.toast_event(&event.kind);
+            self.push_event(event);
+        }
+    }
+
+    /// Drain resources for open incidents and move each one along:
+    /// prompt the player on detection, re-prompt on escalation and record
+    /// MTTR stats on resolution.
+    fn advance_incidents(&mut self) {
+        let mut updates = Vec::new();
+        for incident in &mut self.incidents {
+            let drain = incident.drain(&self.resources);
+            self.resources.subtract(&drain);
+            if let Some(update) = incident.advance(self.total_ticks) {
+                updates.push((update, incident.clone()));
+            }
+        }
+
+        for (update, incident) in updates {
+            let name = incident.name.clone();
+            match update {
+                IncidentUpdate::Detected => {
+                    self.prompt_incident(&incident);
+                    self.trigger_event(GameEventKind::IncidentDetected {
+                        name,
+                        severity: incident.severity,
+                    });
+                }
+                IncidentUpdate::Escalated => {
+                    if incident.state > IncidentState::Undetected {
+                        self.prompt_incident(&incident);
+                    }
+                    self.trigger_event(GameEventKind::IncidentEscalated {
+                        name,
+                        severity: incident.severity,
+                    });
+                }
+                IncidentUpdate::Resolved | IncidentUpdate::BurnedOut => {
+                    if update == IncidentUpdate::BurnedOut {
+                        self.stats.record_burnout();
+                    } else {
+                        self.stats.record_incident(&incident);
+                    }
+                    self.drop_prompts(incident.id);
+                    let seconds = (self.total_ticks - incident.started_tick) as f64 / 4.0;
+                    self.log_event(GameEventKind::IncidentResolved { name, seconds });
+                }
+            }
+        }
+        self.incidents.retain(|i| i.state != IncidentState::Resolved);
+    }
+
+    /// Put an incident's prompt at the front of the task queue, replacing
+    /// any older prompt for it.
+    fn prompt_incident(&mut self, incident: &Incident) {
+        self.drop_prompts(incident.id);
+        let prompt = incident.prompt(&mut self.rng);
+        self.task_queue.push_front(prompt);
+    }
+
+    fn drop_prompts(&mut self, id: u32) {
+        self.task_queue.retain(|task| task.incident != Some(id));
+    }
+
+    /// The player picked up an incident's prompt.
+    pub fn acknowledge_incident(&mut self, id: u32) {
+        let tick = self.total_ticks;
+        if let Some(incident) = self.incidents.iter_mut().find(|i| i.id == id) {
+            incident.acknowledge(tick);
+        }
+    }
+
+    /// The player answered an incident's prompt correctly.
+    pub fn mitigate_incident(&mut self, id: u32) {
+        let tick = self.total_ticks;
+        let Some(incident) = self.incidents.iter_mut().find(|i| i.id == id && i.is_open()) else {
+            return;
+        };
+        incident.mitigate(tick);
+        let name = incident.name.clone();
+        self.drop_prompts(id);
+        self.trigger_event(GameEventKind::IncidentMitigated { name });
+    }
+
+    /// Surface incidents and windfalls as toasts.
+    fn toast_event(&mut self, event: &GameEventKind) {
+        let toast = match event.severity_color() {
+            EventSeverity::Good => Some(NotificationKind::Info),
+            EventSeverity::Warning | EventSeverity::Error => Some(NotificationKind::Warning),
+            EventSeverity::Info => None,
+        };
+        if let Some(kind) = toast {
+            self.notify(kind, event.description());
+        }
+    }
+
+    /// Fire an event from outside the random roll (e.g. a script): apply
+    /// its immediate effect, toast it and log it.
+    pub fn trigger_event(&mut self, kind: GameEventKind) {
+        apply_event(&kind, &mut self.resources);
+        self.toast_event(&kind);
+        self.log_event(kind);
+    }
+
+    /// Unlock an achievement by id. Returns false if it is unknown or
+    /// already unlocked.
+    pub fn unlock_achievement(&mut self, id: &str) -> bool {
+        let Some(def) = progression::all_achievement_defs().iter().find(|d| d.id == id) else {
+            return false;
+        };
+        if self.has_achievement(id) {
+            return false;
+        }
+        self.achievements.push(UnlockedAchievement::now(def.id.clone()));
+        self.log_event(GameEventKind::AchievementUnlocked {
+            name: def.name.clone(),
+        });
+
+        let mut message = format!("* {} unlocked!", def.name);
+        if let Some(reward) = &def.reward {
+            message.push_str(&format!(" ({})", reward.description()));
+            match reward {
+                // Held back during no-bonus runs; end_challenge applies it
+                AchievementReward::ProductionMultiplier(_) if self.modifiers.no_bonuses => {}
+                AchievementReward::ProductionMultiplier(mult) => {
+                    self.global_multiplier *= mult;
+                    self.recalculate_production();
+                }
+                AchievementReward::Reputation(rep) => {
+                    self.resources.reputation += rep;
+                    self.stats.record_reputation(*rep);
+                }
+                AchievementReward::Title(_) => {}
             }
+        }
+        self.notify(NotificationKind::Achievement, message);
+        true
+    }
+
+    /// Move the crypto price one second and fill any limit orders it reaches.
+    fn step_market(&mut self) {
+        if let Some(shock) = self.market.step(&mut self.rng) {
+            let toast = match shock.severity_color() {
+                EventSeverity::Good => NotificationKind::Info,
+                _ => NotificationKind::Warning,
+            };
+            self.notify(toast, shock.description());
+            self.log_event(shock);
+        }
+
+        for order in self.market.take_filled() {
+            let bought = order.proceeds();
+            self.resources.add(&bought);
+            let event = GameEventKind::CryptoTraded {
+                sold: order.escrow(),
+                bought,
+            };
+            self.notify(
+                NotificationKind::Info,
+                format!("Order filled: {}", event.description()),
+            );
+            self.log_event(event);
+        }
+    }
+
+    /// Exchange crypto at the market price, spread included.
+    pub fn trade(
+        &mut self,
+        side: TradeSide,
+        asset: TradeAsset,
+        crypto: f64,
+    ) -> Result<(), PurchaseError> {
+        let result = self.execute_trade(side, asset, crypto);
+        self.log_trade_failure(result)
+    }
+
+    fn execute_trade(
+        &mut self,
+        side: TradeSide,
+        asset: TradeAsset,
+        crypto: f64,
+    ) -> Result<(), PurchaseError> {
+        if crypto <= 0.0 {
+            return Err(PurchaseError::EmptyTrade);
+        }
+        let value = asset.resources(crypto * self.market.quote(side, asset));
+        let coins = Resources {
+            crypto,
+            ..Default::default()
+        };
+        let (sold, bought) = match side {
+            TradeSide::Buy => (value, coins),
+            TradeSide::Sell => (coins, value),
+        };
+        check_funds(&self.resources, &sold)?;
+
+        self.resources.subtract(&sold);
+        self.resources.add(&bought);
+        self.log_event(GameEventKind::CryptoTraded { sold, bought });
+        Ok(())
+    }
 
-            // Log the event
-            self.event_log.push_back(event);
-            if self.event_log.len() > MAX_EVENT_LOG {
-                self.event_log.pop_front();
+    /// Hold the order's funds until the price reaches `limit`.
+    pub fn place_order(&mut self, order: Order) -> Result<(), PurchaseError> {
+        let result = self.execute_place_order(order);
+        self.log_trade_failure(result)
+    }
+
+    fn execute_place_order(&mut self, order: Order) -> Result<(), PurchaseError> {
+        if order.crypto <= 0.0 || order.limit <= 0.0 {
+            return Err(PurchaseError::EmptyTrade);
+        }
+        if self.market.is_full() {
+            return Err(PurchaseError::TooManyOrders);
+        }
+        let escrow = order.escrow();
+        check_funds(&self.resources, &escrow)?;
+
+        self.resources.subtract(&escrow);
+        self.market.place(order);
+        Ok(())
+    }
+
+    /// Cancel an open order and return its funds.
+    pub fn cancel_order(&mut self, id: u32) -> bool {
+        match self.market.cancel(id) {
+            Some(order) => {
+                self.resources.add(&order.escrow());
+                true
             }
+            None => false,
+        }
+    }
+
+    fn log_trade_failure(
+        &mut self,
+        result: Result<(), PurchaseError>,
+    ) -> Result<(), PurchaseError> {
+        if let Err(err) = &result {
+            self.notify(NotificationKind::Warning, format!("Trade failed: {err}"));
+        }
+        result
+    }
+
+    /// Record something that happened at the current tick in the event log.
+    pub fn log_event(&mut self, kind: GameEventKind) {
+        let event = GameEvent::new(kind, self.total_ticks);
+        self.push_event(event);
+    }
+
+    /// Take the events queued for the journal since the last call.
+    pub fn drain_journal(&mut self) -> Vec<JournalEntry> {
+        std::mem::take(&mut self.journal_queue).into()
+    }
+
+    /// Take the daily results finished since the last call.
+    pub fn drain_daily_results(&mut self) -> Vec<DailyResult> {
+        std::mem::take(&mut self.daily_results)
+    }
+
+    /// Replace the event RNG with one seeded from `seed`, so the same
+    /// seed gives the same sequence of random events.
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = rand::rngs::StdRng::seed_from_u64(seed);
+    }
+
+    /// Queue a toast for the UI.
+    pub fn notify(&mut self, kind: NotificationKind, message: impl Into<String>) {
+        self.notifications.push(Notification::new(kind, message));
+    }
+
+    /// Take the notifications raised since the last call.
+    pub fn drain_notifications(&mut self) -> Vec<Notification> {
+        std::mem::take(&mut self.notifications)
+    }
+
+    fn push_event(&mut self, event: GameEvent) {
+        self.stats.record_event(&event.kind);
+        self.journal_queue
+            .push_back(JournalEntry::new(event.clone(), self.total_prestiges));
+        if self.journal_queue.len() > MAX_JOURNAL_QUEUE {
+            self.journal_queue.pop_front();
+        }
+        self.event_log.push_back(event);
+        if self.event_log.len() > MAX_EVENT_LOG {
+            self.event_log.pop_front();
+        }
+    }
+
+    /// Add one tick of production less building upkeep and return the net
+    /// change. `efficiency` scales everything, upkeep included. When
+    /// bandwidth or storage run short, buildings with upkeep slow down
+    /// instead of going negative.
+    pub fn apply_production(&mut self, efficiency: f64) -> Resources {
+        let gross = |state: &Self| state.production_per_tick.scaled(efficiency);
+
+        let demand = self.production_breakdown.upkeep.scaled(efficiency);
+        let supply = gross(self);
+        let satisfaction = formulas::upkeep_satisfaction(
+            self.resources.bandwidth + supply.bandwidth,
+            demand.bandwidth,
+        )
+        .min(formulas::upkeep_satisfaction(
+            self.resources.storage + supply.storage,
+            demand.storage,
+        ));
+        if (satisfaction - self.upkeep_satisfaction).abs() > 1e-9 {
+            self.upkeep_satisfaction = satisfaction;
+            self.recalculate_production();
+        }
+
+        let net = gross(self).net_flow(&demand.scaled(satisfaction));
+        self.resources.add(&net);
+        self.run_compute += net.compute.max(0.0);
+        // Guard against float rounding dipping below zero
+        self.resources.bandwidth = self.resources.bandwidth.max(0.0);
+        self.resources.storage = self.resources.storage.max(0.0);
+        net
+    }
+
+    /// Apply `ticks` of production at `efficiency` in one-minute chunks.
+    /// Upkeep satisfaction, and with it the production breakdown, is
+    /// worked out once per chunk rather than every tick.
+    pub fn apply_offline_production(&mut self, ticks: u64, efficiency: f64) {
+        let mut remaining = ticks;
+        while remaining > 0 {
+            let chunk = remaining.min(OFFLINE_CHUNK_TICKS);
+            self.apply_production(efficiency * chunk as f64);
+            remaining -= chunk;
         }
     }
 
+    /// Upkeep actually paid per tick at the current satisfaction.
+    pub fn upkeep_per_tick(&self) -> Resources {
+        self.production_breakdown
+            .upkeep
+            .scaled(self.upkeep_satisfaction)
+    }
+
+    /// Production per tick after upkeep.
+    pub fn net_per_tick(&self) -> Resources {
+        self.production_per_tick.net_flow(&self.upkeep_per_tick())
+    }
+
     pub fn recalculate_production(&mut self) {
+        let was_throttled = self.production_breakdown.facility.is_throttled();
+        self.production_breakdown = self.production_breakdown();
+        self.production_per_tick = self.production_breakdown.total();
+
+        let facility = self.production_breakdown.facility;
+        if facility.is_throttled() && !was_throttled {
+            self.log_event(GameEventKind::CapacityExceeded {
+                throttle: facility.throttle(),
+            });
+            self.notify(
+                NotificationKind::Warning,
+                "Power/cooling over capacity - build a PDU or Chiller",
+            );
+        }
+    }
+
+    /// Bring a loaded save in line with the current content: add buildings
+    /// introduced since it was made and refresh upgrade definitions,
+    /// keeping what was purchased. Purchases from missing packs are kept
+    /// aside until the pack is installed again.
+    pub fn sync_content(&mut self) {
+        let mut packs = content::registry().pack_ids();
+        let missing: Vec<String> = self
+            .packs
+            .iter()
+            .filter(|id| !packs.contains(id))
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            let message = format!(
+                "Missing content packs: {} - their content is ignored",
+                missing.join(", ")
+            );
+            tracing::warn!("{message}");
+            self.notify(NotificationKind::Warning, message);
+        }
+        packs.extend(missing);
+        self.packs = packs;
+
+        for def in all_building_defs() {
+            self.buildings
+                .entry(def.kind)
+                .or_insert_with(|| BuildingInstance::new(def.kind));
+        }
+
+        let mut purchased: Vec<UpgradeId> = self
+            .upgrades
+            .iter()
+            .filter(|u| u.purchased)
+            .map(|u| u.id)
+            .collect();
+        purchased.append(&mut self.missing_upgrades);
+        self.upgrades = all_upgrades();
+        for upgrade in &mut self.upgrades {
+            upgrade.purchased = purchased.contains(&upgrade.id);
+        }
+        self.missing_upgrades = purchased
+            .into_iter()
+            .filter(|id| !self.upgrades.iter().any(|u| u.id == *id))
+            .collect();
+    }
+
+    /// Give a save from before power and cooling existed enough free PDUs
+    /// and Chillers to run what it already owns, so it doesn't load
+    /// throttled.
+    pub fn grant_facility_capacity(&mut self) {
+        let load = self.production_breakdown().facility;
+        let shortfall = [
+            (BuildingKind::PDU, load.demand.power - load.capacity.power),
+            (BuildingKind::Chiller, load.demand.cooling - load.capacity.cooling),
+        ];
         let defs = all_building_defs();
-        let mut production = Resources::default();
+        let mut granted = Vec::new();
+        for (kind, missing) in shortfall {
+            let Some(def) = defs.iter().find(|d| d.kind == kind) else {
+                continue;
+            };
+            let per_unit = def.supply.power + def.supply.cooling;
+            if missing <= 0.0 || per_unit <= 0.0 {
+                continue;
+            }
+            let units = (missing / per_unit).ceil() as u32;
+            self.buildings
+                .entry(kind)
+                .or_insert_with(|| BuildingInstance::new(kind))
+                .count += units;
+            granted.push(format!("{units} {}", def.name));
+        }
+        if !granted.is_empty() {
+            self.notify(
+                NotificationKind::Info,
+                format!("Power and cooling added for your servers: {}", granted.join(", ")),
+            );
+        }
+    }
 
-        // Calculate CI/CD pipeline global bonus
-        let cicd_count = self
+    /// Work out every factor of each owned building's production.
+    pub fn production_breakdown(&self) -> ProductionBreakdown {
+        self.breakdown_with(None)
+    }
+
+    /// Extra production per tick that a purchase would add, without
+    /// changing any state.
+    pub fn marginal_production(&self, purchase: Purchase) -> Resources {
+        let mut gain = self.breakdown_with(Some(purchase)).total();
+        gain.subtract(&self.production_breakdown.total());
+        gain
+    }
+
+    /// Cost of a purchase at the current count/level, if it can be bought.
+    pub fn purchase_cost(&self, purchase: Purchase) -> Option<Resources> {
+        match purchase {
+            Purchase::Building(kind) => {
+                let defs = all_building_defs();
+                let def = defs.iter().find(|d| d.kind == kind)?;
+                let instance = self.buildings.get(&kind)?;
+                Some(def.cost_as_resources(instance.count))
+            }
+            Purchase::BuildingLevel(kind) => self.building_level_cost(kind),
+            Purchase::Upgrade(id) => self
+                .upgrades
+                .iter()
+                .find(|u| u.id == id && !u.purchased)
+                .map(|u| u.cost.clone()),
+        }
+    }
+
+    /// Time-to-afford and payback for a purchase at current production.
+    pub fn purchase_advice(&self, purchase: Purchase) -> Option<PurchaseAdvice> {
+        let cost = self.purchase_cost(purchase)?;
+        let gain = self.marginal_production(purchase);
+        Some(PurchaseAdvice::new(
+            purchase,
+            cost,
+            gain,
+            &self.resources,
+            &self.net_per_tick(),
+        ))
+    }
+
+    /// Advice for every unlocked building and available upgrade.
+    pub fn all_purchase_advice(&self) -> Vec<PurchaseAdvice> {
+        let buildings = self
+            .unlocked_buildings()
+            .into_iter()
+            .map(Purchase::Building);
+        let levels = self
             .buildings
-            .get(&BuildingKind::CICDPipeline)
-            .map(|b| b.count)
-            .unwrap_or(0);
+            .iter()
+            .filter(|(_, b)| b.count > 0)
+            .map(|(kind, _)| Purchase::BuildingLevel(*kind))
+            .collect::<Vec<_>>();
+        let upgrades = self
+            .available_upgrades()
+            .into_iter()
+            .map(|u| Purchase::Upgrade(u.id))
+            .collect::<Vec<_>>();
+        buildings
+            .chain(levels)
+            .chain(upgrades)
+            .filter_map(|p| self.purchase_advice(p))
+            .collect()
+    }
+
+    /// Production breakdown as it would be after `change`.
+    fn breakdown_with(&self, change: Option<Purchase>) -> ProductionBreakdown {
+        let defs = all_building_defs();
+        let mut breakdown = ProductionBreakdown::default();
+
+        // Count and level of a building, including the previewed change
+        let owned = |kind: BuildingKind| -> (u32, u32) {
+            let (count, level) = self
+                .buildings
+                .get(&kind)
+                .map(|b| (b.count, b.level))
+                .unwrap_or((0, 0));
+            match change {
+                Some(Purchase::Building(k)) if k == kind => (count + 1, level),
+                Some(Purchase::BuildingLevel(k)) if k == kind && count > 0 => (count, level + 1),
+                _ => (count, level),
+            }
+        };
+
+        // Calculate CI/CD pipeline global bonus
+        let (cicd_count, _) = owned(BuildingKind::CICDPipeline);
         let cicd_multiplier = 1.0 + (cicd_count as f64 * 0.10);
 
         // Calculate per-building upgrade multipliers
+        let mut global_multiplier = self.global_multiplier;
         let mut building_multipliers: HashMap<BuildingKind, f64> = HashMap::new();
         for upgrade in &self.upgrades {
-            if !upgrade.purchased {
+            let previewed = change == Some(Purchase::Upgrade(upgrade.id));
+            if !upgrade.purchased && !previewed {
                 continue;
             }
-            if let UpgradeEffect::MultiplyProduction(kind, mult) = &upgrade.effect {
-                let entry = building_multipliers.entry(*kind).or_insert(1.0);
-                *entry *= mult;
+            match &upgrade.effect {
+                UpgradeEffect::MultiplyProduction(kind, mult) => {
+                    let entry = building_multipliers.entry(*kind).or_insert(1.0);
+                    *entry *= mult;
+                }
+                // Already folded into global_multiplier once purchased
+                UpgradeEffect::MultiplyAllProduction(mult) if previewed => {
+                    global_multiplier *= mult;
+                }
+                _ => {}
             }
         }
 
-        let total_multiplier = self.global_multiplier * cicd_multiplier;
+        // Upkeep demand, and power and cooling: facilities supply, everything else draws
+        for def in defs {
+            let (count, level) = owned(def.kind);
+            if count == 0 {
+                continue;
+            }
+            let units = count as f64;
+            breakdown.upkeep.add(&def.upkeep.as_resources().scaled(units));
+            breakdown.facility.add_capacity(
+                def.supply,
+                units * formulas::level_multiplier(level, def.level_bonus),
+            );
+            breakdown.facility.add_demand(def.draw, units);
+        }
+        let facility_multiplier = breakdown.facility.throttle();
+        let spike_multiplier = if self.traffic_spike_remaining > 0 {
+            self.traffic_spike_multiplier
+        } else {
+            1.0
+        };
 
-        for def in &defs {
-            if def.kind == BuildingKind::CICDPipeline {
+        for def in defs {
+            if def.kind == BuildingKind::CICDPipeline || def.is_facility() {
                 continue;
             }
-            if let Some(instance) = self.buildings.get(&def.kind) {
-                if instance.count == 0 {
+            if self.buildings.contains_key(&def.kind) {
+                let (count, level) = owned(def.kind);
+                if count == 0 {
                     continue;
                 }
-                let building_mult = building_multipliers.get(&def.kind).copied().unwrap_or(1.0);
-                let prod = def.production_per_tick(
-                    instance.count,
-                    instance.level,
-                    total_multiplier * building_mult,
-                );
-                match def.resource_type {
-                    ResourceType::Compute => production.compute += prod,
-                    ResourceType::Bandwidth => production.bandwidth += prod,
-                    ResourceType::Storage => production.storage += prod,
-                    ResourceType::Crypto => production.crypto += prod,
-                }
+                let upkeep_multiplier = if def.upkeep == Upkeep::NONE {
+                    1.0
+                } else {
+                    self.upkeep_satisfaction
+                };
+                let effect_multiplier = self
+                    .active_effects
+                    .iter()
+                    .map(|e| e.production_multiplier(def.kind))
+                    .product();
+                breakdown.buildings.push(BuildingProduction {
+                    kind: def.kind,
+                    resource_type: def.resource_type,
+                    base: def.base_production,
+                    count,
+                    level,
+                    level_bonus: def.level_bonus,
+                    upgrade_multiplier: building_multipliers
+                        .get(&def.kind)
+                        .copied()
+                        .unwrap_or(1.0),
+                    cicd_multiplier,
+                    global_multiplier,
+                    effect_multiplier,
+                    spike_multiplier: match def.resource_type {
+                        ResourceType::Crypto => 1.0,
+                        _ => spike_multiplier,
+                    },
+                    synergy_multiplier: synergies::production_multiplier(def.kind, |k| {
+                        owned(k).0
+                    }),
+                    facility_multiplier,
+                    upkeep_multiplier,
+                });
             }
         }
 
-        self.production_per_tick = production;
+        breakdown
     }
 
-    pub fn purchase_building(&mut self, kind: BuildingKind) -> bool {
-        let defs = all_building_defs();
-        let def = match defs.iter().find(|d| d.kind == kind) {
-            Some(d) => d,
-            None => return false,
-        };
+    pub fn purchase_building(&mut self, kind: BuildingKind) -> Result<(), PurchaseError> {
+        let result = self.buy_building(kind);
+        self.log_failure(Purchase::Building(kind), result)
+    }
 
-        let instance = match self.buildings.get(&kind) {
-            Some(i) => i,
-            None => return false,
-        };
+    fn buy_building(&mut self, kind: BuildingKind) -> Result<(), PurchaseError> {
+        let defs = all_building_defs();
+        let def = defs
+            .iter()
+            .find(|d| d.kind == kind)
+            .ok_or(PurchaseError::NotFound)?;
+        let instance = self.buildings.get(&kind).ok_or(PurchaseError::NotFound)?;
+        if !self.unlocked_buildings().contains(&kind) {
+            return Err(PurchaseError::Locked);
+        }
+        if !self.modifiers.allows_building(def.resource_type) {
+            return Err(PurchaseError::ChallengeRule);
+        }
 
         let cost = def.cost_as_resources(instance.count);
-        if !self.resources.can_afford(&cost) {
-            return false;
-        }
+        check_funds(&self.resources, &cost)?;
 
         self.resources.subtract(&cost);
+        self.stats.record_spent(&cost);
+        self.stats.record_building(kind);
         self.buildings.get_mut(&kind).unwrap().count += 1;
         self.recalculate_production();
-        true
+        self.log_event(GameEventKind::BuildingPurchased(kind));
+        Ok(())
     }
 
-    pub fn upgrade_building(&mut self, kind: BuildingKind) -> bool {
+    /// Cost of the next level of an owned building.
+    pub fn building_level_cost(&self, kind: BuildingKind) -> Option<Resources> {
         let instance = match self.buildings.get(&kind) {
             Some(i) if i.count > 0 => i,
-            _ => return false,
+            _ => return None,
         };
-
         let defs = all_building_defs();
-        let def = match defs.iter().find(|d| d.kind == kind) {
-            Some(d) => d,
-            None => return false,
-        };
+        let def = defs.iter().find(|d| d.kind == kind)?;
+        Some(def.level_cost(instance.level))
+    }
 
-        let upgrade_cost = def.base_cost * 10.0 * 2.0_f64.powi(instance.level as i32);
-        let cost = match def.resource_type {
-            ResourceType::Compute => Resources {
-                compute: upgrade_cost,
-                ..Default::default()
-            },
-            ResourceType::Bandwidth => Resources {
-                bandwidth: upgrade_cost,
-                ..Default::default()
-            },
-            ResourceType::Storage => Resources {
-                storage: upgrade_cost,
-                ..Default::default()
-            },
-            ResourceType::Crypto => Resources {
-                crypto: upgrade_cost,
-                ..Default::default()
-            },
-        };
+    pub fn upgrade_building(&mut self, kind: BuildingKind) -> Result<(), PurchaseError> {
+        let result = self.level_building(kind);
+        self.log_failure(Purchase::BuildingLevel(kind), result)
+    }
 
-        if !self.resources.can_afford(&cost) {
-            return false;
+    fn level_building(&mut self, kind: BuildingKind) -> Result<(), PurchaseError> {
+        if !self.buildings.contains_key(&kind) {
+            return Err(PurchaseError::NotFound);
         }
+        let cost = self
+            .building_level_cost(kind)
+            .ok_or(PurchaseError::Locked)?;
+        check_funds(&self.resources, &cost)?;
 
         self.resources.subtract(&cost);
-        self.buildings.get_mut(&kind).unwrap().level += 1;
+        self.stats.record_spent(&cost);
+        let instance = self.buildings.get_mut(&kind).unwrap();
+        instance.level += 1;
+        let level = instance.level;
         self.recalculate_production();
-        true
+        self.log_event(GameEventKind::BuildingUpgraded { kind, level });
+        Ok(())
     }
 
-    pub fn purchase_upgrade(&mut self, id: UpgradeId) -> bool {
-        // Check if already purchased
-        let upgrade = match self.upgrades.iter().find(|u| u.id == id) {
-            Some(u) => u,
-            None => return false,
-        };
+    pub fn purchase_upgrade(&mut self, id: UpgradeId) -> Result<(), PurchaseError> {
+        let result = self.buy_upgrade(id);
+        self.log_failure(Purchase::Upgrade(id), result)
+    }
+
+    /// Pay for an upgrade and queue it for research. Prerequisites may
+    /// still be in the queue; the job waits until they finish.
+    fn buy_upgrade(&mut self, id: UpgradeId) -> Result<(), PurchaseError> {
+        // Check if already purchased or queued
+        let upgrade = self
+            .upgrades
+            .iter()
+            .find(|u| u.id == id)
+            .ok_or(PurchaseError::NotFound)?;
         if upgrade.purchased {
-            return false;
+            return Err(PurchaseError::AlreadyPurchased);
+        }
+        if self.modifiers.no_upgrades {
+            return Err(PurchaseError::ChallengeRule);
+        }
+        if self.research.contains(id) {
+            return Err(PurchaseError::InProgress);
         }
 
         // Check prerequisites
-        for prereq_id in &upgrade.prerequisites {
-            if !self.upgrades.iter().any(|u| u.id == *prereq_id && u.purchased) {
-                return false;
-            }
+        let missing: Vec<UpgradeId> = upgrade
+            .prerequisites
+            .iter()
+            .copied()
+            .filter(|p| !self.is_done_or_queued(*p))
+            .collect();
+        if !missing.is_empty() {
+            return Err(PurchaseError::MissingPrerequisite(missing));
+        }
+        if self.research.is_full(self.research_slots()) {
+            return Err(PurchaseError::QueueFull);
         }
 
         // Check cost
         let cost = upgrade.cost.clone();
-        if !self.resources.can_afford(&cost) {
-            return false;
-        }
+        check_funds(&self.resources, &cost)?;
 
-        // Purchase
+        // Pay up front and queue; it counts as spent once research finishes
+        let name = upgrade.name.clone();
+        let ticks = formulas::research_ticks(cost.sum());
         self.resources.subtract(&cost);
-        let upgrade = self.upgrades.iter_mut().find(|u| u.id == id).unwrap();
+        self.research.push(ResearchJob::new(id, cost, ticks));
+        self.log_event(GameEventKind::ResearchStarted { name });
+        Ok(())
+    }
+
+    /// Cancel a queued upgrade and refund its cost. Anything queued that
+    /// depends on it is cancelled too.
+    pub fn cancel_research(&mut self, id: UpgradeId) -> bool {
+        let job = match self.research.remove(id) {
+            Some(job) => job,
+            None => return false,
+        };
+        self.resources.add(&job.cost);
+        let name = self
+            .upgrades
+            .iter()
+            .find(|u| u.id == id)
+            .map(|u| u.name.clone())
+            .unwrap_or_default();
+        self.log_event(GameEventKind::ResearchCancelled { name });
+
+        for dependent in dependents(&self.upgrades, id) {
+            self.cancel_research(dependent);
+        }
+        true
+    }
+
+    pub fn research_slots(&self) -> usize {
+        progression::research_slots(self.lifetime_compute, self.total_prestiges)
+            + usize::from(self.has_perk(EquityPerk::ResearchDept))
+    }
+
+    /// Queued upgrades currently progressing; the rest wait for a free
+    /// slot or for their prerequisites to finish.
+    pub fn active_research(&self) -> Vec<UpgradeId> {
+        self.research.active(self.research_slots(), |id| {
+            self.upgrades
+                .iter()
+                .find(|u| u.id == id)
+                .map(|u| u.prerequisites.iter().all(|p| self.is_purchased(*p)))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Progress queued research by `ticks`, finishing jobs as they
+    /// complete. Used every tick and for offline progress.
+    pub fn advance_research(&mut self, mut ticks: u64) {
+        while ticks > 0 {
+            let active = self.active_research();
+            // Step to the next completion so freed slots are refilled
+            let step = self
+                .research
+                .jobs
+                .iter()
+                .filter(|j| active.contains(&j.id))
+                .map(|j| j.remaining_ticks())
+                .min()
+                .map(|next| next.min(ticks));
+            let step = match step {
+                Some(step) => step,
+                None => break,
+            };
+            for id in self.research.advance(&active, step) {
+                self.complete_research(id);
+            }
+            ticks -= step;
+        }
+    }
+
+    /// Mark an upgrade purchased and apply its effect.
+    fn complete_research(&mut self, id: UpgradeId) {
+        let upgrade = match self.upgrades.iter_mut().find(|u| u.id == id) {
+            Some(u) => u,
+            None => return,
+        };
         upgrade.purchased = true;
 
         // Apply effect
         let effect = upgrade.effect.clone();
+        let name = upgrade.name.clone();
+        let cost = upgrade.cost.clone();
         match effect {
             UpgradeEffect::MultiplyAllProduction(mult) => {
                 self.global_multiplier *= mult;
@@ -294,30 +1132,132 @@ impl GameState {
             _ => {}
         }
 
+        self.stats.record_spent(&cost);
         self.recalculate_production();
-        true
+        self.notify(NotificationKind::Info, format!("Research complete: {}", name));
+        self.log_event(GameEventKind::UpgradePurchased { name });
     }
 
-    /// Get available (unpurchased, prerequisites met) upgrades.
-    pub fn available_upgrades(&self) -> Vec<&Upgrade> {
-        self.upgrades
-            .iter()
-            .filter(|u| {
+    /// Record a failed purchase in the event log, raise a toast and pass
+    /// the result on. Repeats of the same failure are coalesced, so holding
+    /// a key doesn't flood the log.
+    fn log_failure(
+        &mut self,
+        purchase: Purchase,
+        result: Result<(), PurchaseError>,
+    ) -> Result<(), PurchaseError> {
+        if let Err(err) = &result {
+            let event = GameEventKind::PurchaseFailed {
+                item: self.purchase_label(purchase),
+                reason: err.to_string(),
+            };
+            let message = event.description();
+            let tick = self.total_ticks;
+            let repeat = self.last_failure.as_ref().is_some_and(|(last, last_tick)| {
+                *last == message && tick - last_tick <= FAILURE_REPEAT_TICKS
+            });
+            self.last_failure = Some((message.clone(), tick));
+            if !repeat {
+                self.notify(NotificationKind::Warning, message);
+                self.log_event(event);
+            }
+        }
+        result
+    }
+
+    /// Display name of a purchase, e.g. "VPS" or "VPS Lv.3".
+    pub fn purchase_label(&self, purchase: Purchase) -> String {
+        let building_name = |kind: BuildingKind| {
+            all_building_defs()
+                .iter()
+                .find(|d| d.kind == kind)
+                .map(|d| d.name.to_string())
+                .unwrap_or_else(|| format!("{:?}", kind))
+        };
+        match purchase {
+            Purchase::Building(kind) => building_name(kind),
+            Purchase::BuildingLevel(kind) => {
+                let level = self.buildings.get(&kind).map(|b| b.level).unwrap_or(0);
+                format!("{} Lv.{}", building_name(kind), level + 1)
+            }
+            Purchase::Upgrade(id) => self
+                .upgrades
+                .iter()
+                .find(|u| u.id == id)
+                .map(|u| u.name.clone())
+                .unwrap_or_else(|| format!("upgrade #{id}")),
+        }
+    }
+
+    /// Get available (unpurchased, prerequisites met) upgrades.
+    pub fn available_upgrades(&self) -> Vec<&Upgrade> {
+        self.upgrades
+            .iter()
+            .filter(|u| {
                 !u.purchased
-                    && u.prerequisites
-                        .iter()
-                        .all(|p| self.upgrades.iter().any(|u2| u2.id == *p && u2.purchased))
+                    && !self.research.contains(u.id)
+                    && u.prerequisites.iter().all(|p| self.is_done_or_queued(*p))
             })
             .collect()
     }
 
+    fn is_purchased(&self, id: UpgradeId) -> bool {
+        self.upgrades.iter().any(|u| u.id == id && u.purchased)
+    }
+
+    fn is_done_or_queued(&self, id: UpgradeId) -> bool {
+        self.is_purchased(id) || self.research.contains(id)
+    }
+
+    pub fn research_status(&self, id: UpgradeId) -> Option<ResearchStatus> {
+        let upgrade = self.upgrades.iter().find(|u| u.id == id)?;
+        if upgrade.purchased {
+            return Some(ResearchStatus::Purchased);
+        }
+        if self.research.contains(id) {
+            return Some(ResearchStatus::Researching);
+        }
+        let missing: Vec<UpgradeId> = upgrade
+            .prerequisites
+            .iter()
+            .copied()
+            .filter(|p| !self.is_done_or_queued(*p))
+            .collect();
+        Some(if missing.is_empty() {
+            ResearchStatus::Available
+        } else {
+            ResearchStatus::Locked(missing)
+        })
+    }
+
+    /// Unqueued upgrades that must be researched before `id`, in an
+    /// order they can be bought.
+    pub fn research_path(&self, id: UpgradeId) -> Vec<UpgradeId> {
+        fn visit(state: &GameState, id: UpgradeId, path: &mut Vec<UpgradeId>) {
+            let upgrade = match state.upgrades.iter().find(|u| u.id == id) {
+                Some(u) => u,
+                None => return,
+            };
+            for prereq in &upgrade.prerequisites {
+                if !state.is_done_or_queued(*prereq) && !path.contains(prereq) {
+                    visit(state, *prereq, path);
+                    path.push(*prereq);
+                }
+            }
+        }
+
+        let mut path = Vec::new();
+        visit(self, id, &mut path);
+        path
+    }
+
     pub fn unlocked_buildings(&self) -> Vec<BuildingKind> {
         let peak_compute = self.resources.compute;
         let defs = all_building_defs();
         let mut unlocked: Vec<_> = defs
             .iter()
             .filter(|d| {
-                peak_compute >= d.unlock_threshold
+                (peak_compute >= d.unlock_threshold && self.equity >= d.equity_required)
                     || self
                         .buildings
                         .get(&d.kind)
@@ -332,18 +1272,111 @@ impl GameState {
     }
 
     pub fn can_prestige(&self) -> bool {
-        self.resources.compute >= 1_000_000.0
+        self.run_compute >= progression::PRESTIGE_COMPUTE
+    }
+
+    /// Reputation a prestige would gain per minute at current production.
+    pub fn reputation_per_minute(&self) -> f64 {
+        progression::reputation_per_minute(self.run_compute, self.net_per_tick().compute * 4.0)
     }
 
     pub fn prestige(&mut self) -> f64 {
-        let rep_earned = progression::prestige_reputation(self.resources.compute);
+        let rep_earned = progression::prestige_reputation(self.run_compute);
         self.resources.reputation += rep_earned;
+        self.log_event(GameEventKind::Prestige {
+            reputation: rep_earned,
+        });
+
+        // Prestiging ends any challenge without its reward
+        self.end_challenge();
+        self.stats.record_prestige();
+        self.reset(ResetLayer::Run, self.run_start());
+
+        self.prestige_count += 1;
+        self.total_prestiges += 1;
+        self.recalculate_production();
+        self.notify(
+            NotificationKind::Prestige,
+            format!(
+                "PRESTIGE! +{:.0} Reputation (x{:.2} multiplier)",
+                rep_earned,
+                progression::reputation_multiplier(self.resources.reputation),
+            ),
+        );
+
+        rep_earned
+    }
+
+    pub fn can_ipo(&self) -> bool {
+        self.resources.reputation >= progression::IPO_REPUTATION
+    }
+
+    /// Trade all reputation and prestiges for Equity and start over.
+    pub fn ipo(&mut self) -> f64 {
+        let equity_earned = progression::ipo_equity(self.resources.reputation);
+        self.equity += equity_earned;
+        self.log_event(GameEventKind::Ipo {
+            equity: equity_earned,
+        });
+
+        self.end_challenge();
+        self.reset(ResetLayer::Ipo, self.run_start());
+
+        self.ipo_count += 1;
+        self.recalculate_production();
+        self.notify(
+            NotificationKind::Prestige,
+            format!(
+                "IPO! +{:.0} Equity (x{:.2} multiplier)",
+                equity_earned,
+                progression::equity_multiplier(self.equity),
+            ),
+        );
+
+        equity_earned
+    }
+
+    pub fn has_perk(&self, perk: EquityPerk) -> bool {
+        self.equity >= perk.equity_required()
+    }
+
+    /// What a normal run starts with.
+    fn run_start(&self) -> Resources {
+        let compute = if self.has_perk(EquityPerk::SeedRound) {
+            10_000.0
+        } else {
+            50.0
+        };
+        Resources {
+            compute,
+            ..Default::default()
+        }
+    }
+
+    /// Production multiplier every run starts with.
+    fn base_multiplier(&self) -> f64 {
+        progression::reputation_multiplier(self.resources.reputation)
+            * self.achievement_multiplier()
+            * progression::equity_multiplier(self.equity)
+    }
+
+    /// Start a fresh run from `start`, wiping what `layer` covers.
+    fn reset(&mut self, layer: ResetLayer, start: Resources) {
+        self.stats.finish_run();
+
+        if layer >= ResetLayer::Ipo {
+            self.resources.reputation = 0.0;
+            self.prestige_count = 0;
+        }
 
         // Reset resources (keep reputation)
-        self.resources.compute = 50.0;
-        self.resources.bandwidth = 0.0;
-        self.resources.storage = 0.0;
-        self.resources.crypto = 0.0;
+        self.resources = Resources {
+            reputation: self.resources.reputation,
+            ..start
+        };
+        self.run_compute = 0.0;
+        self.incidents.clear();
+        self.task_queue.retain(|task| task.incident.is_none());
 
         // Reset buildings
         for instance in self.buildings.values_mut() {
@@ -355,70 +1388,241 @@ impl GameState {
         for upgrade in &mut self.upgrades {
             upgrade.purchased = false;
         }
+        self.missing_upgrades.clear();
+        self.research.clear();
+        self.market = Market::default();
 
-        // Apply reputation multiplier
-        self.global_multiplier = progression::reputation_multiplier(self.resources.reputation);
+        // Apply reputation and Equity multipliers
+        self.global_multiplier = self.base_multiplier();
         self.task_reward_multiplier = 1.0;
-        self.offline_efficiency = 0.25;
+        self.offline_efficiency = if self.has_perk(EquityPerk::AlwaysOn) {
+            0.5
+        } else {
+            0.25
+        };
 
-        // Clear transient state
-        self.event_log.clear();
+        // Clear transient state (the event log is kept across runs)
         self.traffic_spike_remaining = 0;
         self.traffic_spike_multiplier = 1.0;
-        self.compute_history.clear();
+        self.active_effects.clear();
+        self.history.clear();
+    }
 
-        self.prestige_count += 1;
+    /// Abandon the current run and start challenge `id` from scratch.
+    pub fn start_challenge(&mut self, id: &str) -> bool {
+        let Some(def) = challenges::challenge(id) else {
+            return false;
+        };
+        self.reset(ResetLayer::Run, def.start.clone());
+        self.modifiers = def.modifiers.clone();
+        if self.modifiers.no_bonuses {
+            self.global_multiplier = 1.0;
+        }
+        if let Some(date) = daily::date_of(id) {
+            self.reseed(daily::daily_seed(date));
+        }
+        self.challenge = Some(ActiveChallenge {
+            id: def.id.to_string(),
+            started_tick: self.total_ticks,
+            seeded: true,
+        });
         self.recalculate_production();
+        self.log_event(GameEventKind::ChallengeStarted {
+            name: def.name.to_string(),
+        });
+        self.notify(
+            NotificationKind::Info,
+            format!("Challenge: {} - {}", def.name, def.description),
+        );
+        true
+    }
 
-        rep_earned
+    /// Drop the challenge's rules and keep playing the run normally.
+    pub fn abandon_challenge(&mut self) {
+        if let Some(def) = self.active_challenge() {
+            self.end_challenge();
+            self.log_event(GameEventKind::ChallengeFailed {
+                name: def.name.to_string(),
+            });
+        }
+    }
+
+    pub fn active_challenge(&self) -> Option<challenges::ChallengeDef> {
+        challenges::challenge(&self.challenge.as_ref()?.id)
+    }
+
+    /// Ticks left before a timed challenge fails.
+    pub fn challenge_remaining_ticks(&self) -> Option<u64> {
+        let limit = self.modifiers.time_limit_ticks?;
+        let started = self.challenge.as_ref()?.started_tick;
+        Some((started + limit).saturating_sub(self.total_ticks))
     }
 
+    /// Drop the challenge's rules, giving back the prestige bonuses a
+    /// `no_bonuses` run held back.
+    fn end_challenge(&mut self) {
+        if self.modifiers.no_bonuses {
+            let upgrades: f64 = self
+                .upgrades
+                .iter()
+                .filter(|u| u.purchased)
+                .map(|u| match u.effect {
+                    UpgradeEffect::MultiplyAllProduction(mult) => mult,
+                    _ => 1.0,
+                })
+                .product();
+            self.global_multiplier = self.base_multiplier() * upgrades;
+        }
+        self.modifiers = RunModifiers::default();
+        self.challenge = None;
+        self.recalculate_production();
+    }
+
+    /// Pay out a met challenge goal, or fail a challenge out of time.
+    /// The reputation bonus is only paid the first time.
+    fn check_challenge(&mut self) {
+        let Some(def) = self.active_challenge() else {
+            return;
+        };
+        let name = def.name.to_string();
+        if def.goal_met(&self.resources) {
+            let (started, seeded) = self
+                .challenge
+                .as_ref()
+                .map_or((0, false), |c| (c.started_tick, c.seeded));
+            self.end_challenge();
+            // Dailies go to the leaderboard instead of the challenge list.
+            // A resumed daily drew unseeded events and tasks, so it isn't ranked.
+            let first_clear = if let Some(date) = daily::date_of(&def.id) {
+                if seeded {
+                    let limit = def.modifiers.time_limit_ticks.unwrap_or_default();
+                    self.daily_results
+                        .push(DailyResult::new(date, self.total_ticks - started, limit));
+                } else {
+                    self.notify(
+                        NotificationKind::Info,
+                        "Daily resumed from a save; not added to the leaderboard",
+                    );
+                }
+                self.last_daily_reward.replace(date) != Some(date)
+            } else if self.completed_challenges.contains(&def.id) {
+                false
+            } else {
+                self.completed_challenges.push(def.id.clone());
+                true
+            };
+            let reputation = if first_clear { def.reputation } else { 0.0 };
+            self.resources.reputation += reputation;
+            self.log_event(GameEventKind::ChallengeCompleted {
+                name: name.clone(),
+                reputation,
+            });
+            let message = if first_clear {
+                format!("Challenge complete: {name}! +{reputation:.0} Reputation")
+            } else {
+                format!("Challenge complete: {name} (bonus already claimed)")
+            };
+            self.notify(NotificationKind::Prestige, message);
+        } else if self.challenge_remaining_ticks() == Some(0) {
+            self.end_challenge();
+            self.log_event(GameEventKind::ChallengeFailed { name: name.clone() });
+            self.notify(NotificationKind::Warning, format!("Challenge failed: {name}"));
+        }
+    }
+
+    /// Unlock every achievement whose condition is met and return the
+    /// names of the new ones.
     pub fn check_achievements(&mut self) -> Vec<String> {
-        let total_buildings: u32 = self.buildings.values().map(|b| b.count).sum();
-        let upgrades_purchased = self.upgrades.iter().filter(|u| u.purchased).count();
-
-        let checks: &[(&str, &str, bool)] = &[
-            ("first_build", "Hello World", total_buildings >= 1),
-            ("ten_builds", "Sys Admin", total_buildings >= 10),
-            (
-                "first_upgrade",
-                "Patch Tuesday",
-                upgrades_purchased >= 1,
-            ),
-            ("first_prestige", "Reboot", self.prestige_count >= 1),
-            (
-                "compute_1m",
-                "Megahertz",
-                self.lifetime_compute >= 1_000_000.0,
-            ),
-            (
-                "compute_1b",
-                "Gigaflops",
-                self.lifetime_compute >= 1_000_000_000.0,
-            ),
-            (
-                "compute_1t",
-                "Teraflops",
-                self.lifetime_compute >= 1_000_000_000_000.0,
-            ),
-            ("task_10", "On Call", self.tasks_completed >= 10),
-            (
-                "task_50",
-                "Incident Commander",
-                self.tasks_completed >= 50,
-            ),
-            ("prestige_5", "Veteran", self.prestige_count >= 5),
-        ];
+        let ready: Vec<&AchievementDef> = progression::all_achievement_defs()
+            .iter()
+            .filter(|def| !self.has_achievement(&def.id))
+            .filter(|def| {
+                def.condition
+                    .as_ref()
+                    .is_some_and(|c| self.achievement_progress(c) >= c.target())
+            })
+            .collect();
 
         let mut newly_unlocked = Vec::new();
-        for &(id, name, condition) in checks {
-            if condition && !self.achievements.contains(&id.to_string()) {
-                self.achievements.push(id.to_string());
-                newly_unlocked.push(name.to_string());
+        for def in ready {
+            if self.unlock_achievement(&def.id) {
+                newly_unlocked.push(def.name.clone());
             }
         }
         newly_unlocked
     }
+
+    pub fn has_achievement(&self, id: &str) -> bool {
+        self.unlocked_achievement(id).is_some()
+    }
+
+    pub fn unlocked_achievement(&self, id: &str) -> Option<&UnlockedAchievement> {
+        self.achievements.iter().find(|a| a.id == id)
+    }
+
+    /// Current value of the stat an achievement condition tracks.
+    pub fn achievement_progress(&self, condition: &AchievementCondition) -> f64 {
+        match condition {
+            AchievementCondition::BuildingsOwned(_) => {
+                self.buildings.values().map(|b| b.count).sum::<u32>() as f64
+            }
+            AchievementCondition::Owns { kind, .. } => {
+                self.buildings.get(kind).map(|b| b.count).unwrap_or(0) as f64
+            }
+            AchievementCondition::UpgradesPurchased(_) => {
+                self.upgrades.iter().filter(|u| u.purchased).count() as f64
+            }
+            AchievementCondition::Prestiges(_) => self.prestige_count as f64,
+            AchievementCondition::LifetimeCompute(_) => self.lifetime_compute,
+            AchievementCondition::TasksCompleted(_) => self.tasks_completed as f64,
+            AchievementCondition::ChallengesCompleted(_) => self.completed_challenges.len() as f64,
+        }
+    }
+
+    /// The visible locked achievement closest to unlocking, with its
+    /// current progress.
+    pub fn next_achievement(&self) -> Option<(&'static AchievementDef, f64)> {
+        progression::all_achievement_defs()
+            .iter()
+            .filter(|def| !def.hidden && !self.has_achievement(&def.id))
+            .filter_map(|def| {
+                let condition = def.condition.as_ref()?;
+                Some((def, self.achievement_progress(condition)))
+            })
+            .max_by(|(a, a_progress), (b, b_progress)| {
+                let fraction = |def: &AchievementDef, progress: f64| {
+                    def.condition.as_ref().map_or(0.0, |c| progress / c.target())
+                };
+                fraction(a, *a_progress).total_cmp(&fraction(b, *b_progress))
+            })
+    }
+
+    fn unlocked_rewards(&self) -> impl Iterator<Item = &'static AchievementReward> + '_ {
+        progression::all_achievement_defs()
+            .iter()
+            .filter(|def| self.has_achievement(&def.id))
+            .filter_map(|def| def.reward.as_ref())
+    }
+
+    /// Production boost from unlocked achievements; kept across prestige.
+    pub fn achievement_multiplier(&self) -> f64 {
+        self.unlocked_rewards()
+            .map(|reward| match reward {
+                AchievementReward::ProductionMultiplier(mult) => *mult,
+                _ => 1.0,
+            })
+            .product()
+    }
+
+    /// Best title earned so far: the last one in content order.
+    pub fn title(&self) -> Option<&'static str> {
+        self.unlocked_rewards()
+            .filter_map(|reward| match reward {
+                AchievementReward::Title(title) => Some(title.as_str()),
+                _ => None,
+            })
+            .last()
+    }
 }
 
 impl Default for GameState {
@@ -430,6 +1634,15 @@ impl Default for GameState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::events::EffectKind;
+    use crate::game::market::BASE_PRICE;
+    use rand::Rng;
+
+    /// Queue an upgrade and run research until it is done.
+    fn research(state: &mut GameState, id: UpgradeId) {
+        state.purchase_upgrade(id).unwrap();
+        state.advance_research(u64::MAX);
+    }
 
     #[test]
     fn test_new_game_state() {
@@ -443,8 +1656,8 @@ mod tests {
         let mut state = GameState::new();
         state.resources.compute = 100.0;
 
-        let success = state.purchase_building(BuildingKind::RaspberryPi);
-        assert!(success);
+        let result = state.purchase_building(BuildingKind::RaspberryPi);
+        assert!(result.is_ok());
         assert_eq!(state.buildings[&BuildingKind::RaspberryPi].count, 1);
         assert!(state.resources.compute < 100.0);
         assert!(state.production_per_tick.compute > 0.0);
@@ -455,16 +1668,145 @@ mod tests {
         let mut state = GameState::new();
         state.resources.compute = 0.0;
 
-        let success = state.purchase_building(BuildingKind::RaspberryPi);
-        assert!(!success);
+        let result = state.purchase_building(BuildingKind::RaspberryPi);
+        assert_eq!(
+            result,
+            Err(PurchaseError::InsufficientFunds {
+                missing: Resources {
+                    compute: 10.0,
+                    ..Default::default()
+                }
+            })
+        );
         assert_eq!(state.buildings[&BuildingKind::RaspberryPi].count, 0);
     }
 
+    #[test]
+    fn test_locked_building_and_failure_logged() {
+        let mut state = GameState::new();
+        assert_eq!(
+            state.purchase_building(BuildingKind::Datacenter),
+            Err(PurchaseError::Locked)
+        );
+        assert_eq!(
+            state.upgrade_building(BuildingKind::RaspberryPi),
+            Err(PurchaseError::Locked)
+        );
+        let last = state.event_log.back().unwrap();
+        assert_eq!(
+            last.kind.description(),
+            "Can't buy Raspberry Pi Lv.1: locked"
+        );
+        assert_eq!(
+            state.purchase_upgrade(9_999),
+            Err(PurchaseError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_repeated_failures_are_coalesced() {
+        let mut state = GameState::new();
+        state.drain_notifications();
+        let logged = state.event_log.len();
+        for _ in 0..20 {
+            state.purchase_building(BuildingKind::Datacenter).ok();
+        }
+        assert_eq!(state.event_log.len(), logged + 1);
+        assert_eq!(state.drain_notifications().len(), 1);
+
+        // A different failure, or the same one later, is reported again
+        state.upgrade_building(BuildingKind::RaspberryPi).ok();
+        state.total_ticks += FAILURE_REPEAT_TICKS + 1;
+        state.purchase_building(BuildingKind::Datacenter).ok();
+        assert_eq!(state.event_log.len(), logged + 3);
+    }
+
+    #[test]
+    fn test_notifications_raised_and_drained() {
+        let mut state = GameState::new();
+        state.resources.compute = 0.0;
+        assert!(state.purchase_building(BuildingKind::RaspberryPi).is_err());
+        state.resources.compute = 100.0;
+        state.purchase_building(BuildingKind::RaspberryPi).unwrap();
+        state.check_achievements(); // Hello World
+
+        let kinds: Vec<_> = state.drain_notifications().iter().map(|n| n.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![NotificationKind::Warning, NotificationKind::Achievement]
+        );
+        assert!(state.drain_notifications().is_empty());
+    }
+
+    #[test]
+    fn test_research_status_and_path() {
+        let mut state = GameState::new();
+        // Automation Scripts (6) <- Containerization (3) <- Overclocking (0)
+        assert_eq!(state.research_status(0), Some(ResearchStatus::Available));
+        assert_eq!(state.research_status(3), Some(ResearchStatus::Locked(vec![0])));
+        assert_eq!(state.research_path(6), vec![0, 3]);
+
+        state.resources.compute = 100_000.0;
+        research(&mut state, 0);
+        assert_eq!(state.research_status(0), Some(ResearchStatus::Purchased));
+        assert_eq!(state.research_status(3), Some(ResearchStatus::Available));
+        assert_eq!(state.research_path(6), vec![3]);
+        assert_eq!(state.research_status(999), None);
+    }
+
+    #[test]
+    fn test_research_queue_waits_for_prerequisites() {
+        let mut state = GameState::new();
+        state.resources.compute = 100_000.0;
+        state.purchase_upgrade(0).unwrap();
+        state.purchase_upgrade(3).unwrap(); // queued behind Overclocking
+        assert_eq!(state.research_status(3), Some(ResearchStatus::Researching));
+        assert_eq!(state.active_research(), vec![0]);
+
+        let first = formulas::research_ticks(500.0);
+        state.advance_research(first);
+        assert!(state.is_purchased(0));
+        assert_eq!(state.active_research(), vec![3]);
+        assert_eq!(state.research.jobs[0].progress_ticks, 0);
+
+        state.advance_research(u64::MAX);
+        assert!(state.is_purchased(3));
+        assert!(state.research.jobs.is_empty());
+    }
+
+    #[test]
+    fn test_cancel_research_refunds_dependents() {
+        let mut state = GameState::new();
+        state.resources.compute = 100_000.0;
+        state.purchase_upgrade(0).unwrap();
+        state.purchase_upgrade(3).unwrap();
+
+        assert!(state.cancel_research(0));
+        assert!(state.research.jobs.is_empty()); // Containerization needed it
+        assert_eq!(state.resources.compute, 100_000.0);
+        assert!(!state.cancel_research(0));
+        // Nothing was spent: cancelled research never finished
+        assert_eq!(state.stats.run.total_spent.compute, 0.0);
+        assert_eq!(state.stats.lifetime.total_spent.compute, 0.0);
+    }
+
+    #[test]
+    fn test_research_queue_full() {
+        let mut state = GameState::new();
+        state.resources.compute = 1e9;
+        state.resources.bandwidth = 1e9;
+        state.resources.storage = 1e9;
+        for id in [0, 1, 2, 3] {
+            state.purchase_upgrade(id).unwrap();
+        }
+        assert_eq!(state.purchase_upgrade(4), Err(PurchaseError::QueueFull));
+    }
+
     #[test]
     fn test_tick_produces_resources() {
         let mut state = GameState::new();
         state.resources.compute = 100.0;
-        state.purchase_building(BuildingKind::RaspberryPi);
+        state.purchase_building(BuildingKind::RaspberryPi).unwrap();
 
         let compute_before = state.resources.compute;
         state.tick();
@@ -475,12 +1817,17 @@ mod tests {
     fn test_purchase_upgrade() {
         let mut state = GameState::new();
         state.resources.compute = 1000.0;
-        state.purchase_building(BuildingKind::RaspberryPi);
+        state.purchase_building(BuildingKind::RaspberryPi).unwrap();
 
         let prod_before = state.production_per_tick.compute;
-        let success = state.purchase_upgrade(0); // Overclocking: x2 RaspberryPi
-        assert!(success);
+        let spent_before = state.stats.run.total_spent.compute;
+        let result = state.purchase_upgrade(0); // Overclocking: x2 RaspberryPi
+        assert!(result.is_ok());
+        assert_eq!(state.production_per_tick.compute, prod_before); // still researching
+        assert_eq!(state.stats.run.total_spent.compute, spent_before);
+        state.advance_research(formulas::research_ticks(500.0));
         assert!(state.production_per_tick.compute > prod_before);
+        assert_eq!(state.stats.run.total_spent.compute, spent_before + 500.0);
         // Should be approximately 2x
         assert!((state.production_per_tick.compute / prod_before - 2.0).abs() < 0.01);
     }
@@ -491,18 +1838,21 @@ mod tests {
         state.resources.compute = 100_000.0;
 
         // Containerization (id=3) requires Overclocking (id=0)
-        let success = state.purchase_upgrade(3);
-        assert!(!success); // Should fail: missing prerequisite
-
-        state.purchase_upgrade(0); // Buy Overclocking first
-        let success = state.purchase_upgrade(3);
-        assert!(success); // Now should succeed
+        let result = state.purchase_upgrade(3);
+        assert_eq!(result, Err(PurchaseError::MissingPrerequisite(vec![0])));
+
+        research(&mut state, 0); // Buy Overclocking first
+        let result = state.purchase_upgrade(3);
+        assert!(result.is_ok()); // Now should succeed
+        assert_eq!(state.purchase_upgrade(3), Err(PurchaseError::InProgress));
+        state.advance_research(u64::MAX);
+        assert_eq!(state.purchase_upgrade(3), Err(PurchaseError::AlreadyPurchased));
     }
 
     #[test]
     fn test_prestige() {
         let mut state = GameState::new();
-        state.resources.compute = 4_000_000.0;
+        state.run_compute = 4_000_000.0;
 
         assert!(state.can_prestige());
         let rep = state.prestige();
@@ -511,6 +1861,440 @@ mod tests {
         assert_eq!(state.resources.compute, 50.0);
         assert_eq!(state.prestige_count, 1);
         assert!(state.global_multiplier > 1.0);
+        assert!(matches!(
+            state.event_log.back().map(|e| &e.kind),
+            Some(GameEventKind::Prestige { .. })
+        ));
+    }
+
+    #[test]
+    fn test_ipo_wipes_reputation_layer() {
+        let mut state = GameState::new();
+        state.resources.reputation = 100.0;
+        state.prestige_count = 7;
+        state.total_prestiges = 7;
+        state.unlock_achievement("first_build");
+        assert!(state.can_ipo());
+        let slots = state.research_slots();
+
+        assert_eq!(state.ipo(), 2.0); // sqrt(100 / 25) = 2
+        assert_eq!(state.equity, 2.0);
+        assert_eq!(state.ipo_count, 1);
+        assert_eq!(state.resources.reputation, 0.0);
+        assert_eq!(state.prestige_count, 0);
+        assert_eq!(state.total_prestiges, 7);
+        assert_eq!(state.research_slots(), slots);
+        // Journal runs keep counting across the IPO
+        state.log_event(GameEventKind::TaskCompleted { name: "x".into() });
+        assert!(state.drain_journal().iter().all(|entry| entry.run == 7));
+        assert!(state.has_achievement("first_build"));
+        // Seed Round and Always-On Ops, but not Research Dept
+        assert_eq!(state.resources.compute, 10_000.0);
+        assert_eq!(state.offline_efficiency, 0.5);
+        assert!(!state.has_perk(EquityPerk::ResearchDept));
+        assert!(state.global_multiplier >= progression::equity_multiplier(2.0));
+    }
+
+    #[test]
+    fn test_equity_tiers_need_equity() {
+        let mut state = GameState::new();
+        state.resources.compute = 1e15;
+        assert!(!state.unlocked_buildings().contains(&BuildingKind::HyperscaleCampus));
+        state.equity = 1.0;
+        let unlocked = state.unlocked_buildings();
+        assert!(unlocked.contains(&BuildingKind::HyperscaleCampus));
+        assert!(!unlocked.contains(&BuildingKind::OrbitalDatacenter));
+    }
+
+    #[test]
+    fn test_prestige_uses_run_compute() {
+        let mut state = GameState::new();
+        state.resources.compute = 5_000_000.0;
+        assert!(!state.can_prestige());
+
+        // Production counts towards the run total; spending doesn't undo it
+        state.production_per_tick.compute = 1_000_000.0;
+        state.apply_production(1.0);
+        state.resources.compute = 0.0;
+        assert!(state.can_prestige());
+        assert_eq!(state.prestige(), 1.0);
+        assert_eq!(state.run_compute, 0.0);
+    }
+
+    #[test]
+    fn test_prestige_resets_run_stats() {
+        let mut state = GameState::new();
+        state.resources.compute = 100.0;
+        state.purchase_building(BuildingKind::RaspberryPi).unwrap();
+        state.run_compute = 4_000_000.0;
+
+        state.prestige();
+        assert_eq!(state.stats.run.ticks, 0);
+        assert_eq!(state.stats.run.total_buildings_bought(), 0);
+        assert_eq!(state.stats.lifetime.total_buildings_bought(), 1);
+        assert_eq!(state.stats.lifetime.reputation_earned, 2.0);
+        assert_eq!(state.stats.fastest_prestige_ticks, Some(0));
+    }
+
+    #[test]
+    fn test_purchases_are_logged() {
+        let mut state = GameState::new();
+        state.resources.compute = 1000.0;
+        state.purchase_building(BuildingKind::RaspberryPi).unwrap();
+        research(&mut state, 0);
+
+        let kinds: Vec<_> = state.event_log.iter().map(|e| &e.kind).collect();
+        assert!(matches!(
+            kinds[0],
+            GameEventKind::BuildingPurchased(BuildingKind::RaspberryPi)
+        ));
+        assert!(matches!(kinds[1], GameEventKind::ResearchStarted { .. }));
+        assert!(matches!(kinds[2], GameEventKind::UpgradePurchased { .. }));
+    }
+
+    #[test]
+    fn test_undrained_journal_queue_is_capped() {
+        let mut state = GameState::new();
+        for _ in 0..MAX_JOURNAL_QUEUE + 10 {
+            state.log_event(GameEventKind::TaskExpired { name: "t".into() });
+        }
+        assert_eq!(state.journal_queue.len(), MAX_JOURNAL_QUEUE);
+        assert_eq!(state.drain_journal().len(), MAX_JOURNAL_QUEUE);
+        assert!(state.journal_queue.is_empty());
+    }
+
+    #[test]
+    fn test_production_breakdown_matches_total() {
+        let mut state = GameState::new();
+        state.resources.compute = 1000.0;
+        state.resources.bandwidth = 100.0;
+        state.purchase_building(BuildingKind::RaspberryPi).unwrap();
+        state.purchase_building(BuildingKind::HomeRouter).unwrap();
+        research(&mut state, 0);
+
+        let pi = state
+            .production_breakdown
+            .get(BuildingKind::RaspberryPi)
+            .unwrap();
+        assert_eq!(pi.upgrade_multiplier, 2.0);
+        assert!((pi.total() - state.production_per_tick.compute).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_capacity_shortfall_throttles_production() {
+        let mut state = GameState::new();
+        state.buildings.get_mut(&BuildingKind::VPS).unwrap().count = 20;
+        state.recalculate_production();
+
+        // 20 VPS draw 10 kW against the 5 kW base supply
+        let facility = state.production_breakdown.facility;
+        assert_eq!(facility.throttle(), 0.5);
+        let vps = state.production_breakdown.get(BuildingKind::VPS).unwrap();
+        assert_eq!(vps.facility_multiplier, 0.5);
+        assert!(state
+            .event_log
+            .iter()
+            .any(|e| matches!(e.kind, GameEventKind::CapacityExceeded { .. })));
+
+        state.buildings.get_mut(&BuildingKind::PDU).unwrap().count = 1;
+        state.buildings.get_mut(&BuildingKind::Chiller).unwrap().count = 1;
+        state.recalculate_production();
+        assert!(!state.production_breakdown.facility.is_throttled());
+        assert!(state.production_breakdown.get(BuildingKind::PDU).is_none());
+    }
+
+    #[test]
+    fn test_upkeep_deficit_degrades_output() {
+        let mut state = GameState::new();
+        state.buildings.get_mut(&BuildingKind::VPS).unwrap().count = 1;
+        state.recalculate_production();
+        let full = state.production_per_tick.compute;
+
+        // A stockpile pays the upkeep at full output
+        state.resources.bandwidth = 10.0;
+        state.resources.storage = 10.0;
+        let net = state.apply_production(1.0);
+        assert_eq!(state.upkeep_satisfaction, 1.0);
+        assert_eq!(net.compute, full);
+        assert!((state.resources.bandwidth - 9.6).abs() < 1e-9);
+        assert!((state.resources.storage - 9.8).abs() < 1e-9);
+
+        // Running dry throttles the VPS instead of going negative
+        state.resources.bandwidth = 0.1;
+        state.apply_production(1.0);
+        assert!((state.upkeep_satisfaction - 0.25).abs() < 1e-9);
+        assert!((state.production_per_tick.compute - full * 0.25).abs() < 1e-9);
+        assert_eq!(state.resources.bandwidth, 0.0);
+    }
+
+    #[test]
+    fn test_offline_production_in_chunks() {
+        let mut state = GameState::new();
+        state.buildings.get_mut(&BuildingKind::VPS).unwrap().count = 1;
+        state.recalculate_production();
+        let full = state.production_per_tick.compute;
+        state.resources.compute = 0.0;
+        state.resources.bandwidth = 1_000.0;
+        state.resources.storage = 1_000.0;
+
+        // 2 minutes at half efficiency, upkeep fully paid
+        state.apply_offline_production(480, 0.5);
+        assert!((state.resources.compute - full * 240.0).abs() < 1e-9);
+        assert!((state.resources.bandwidth - (1_000.0 - 0.4 * 240.0)).abs() < 1e-9);
+
+        // Out of bandwidth: output drops, nothing goes negative
+        state.resources.bandwidth = 0.0;
+        state.apply_offline_production(115_200, 0.5);
+        assert_eq!(state.upkeep_satisfaction, 0.0);
+        assert_eq!(state.resources.bandwidth, 0.0);
+    }
+
+    #[test]
+    fn test_net_flow_subtracts_upkeep() {
+        let mut state = GameState::new();
+        state.buildings.get_mut(&BuildingKind::VPS).unwrap().count = 2;
+        state.buildings.get_mut(&BuildingKind::HomeRouter).unwrap().count = 1;
+        state.recalculate_production();
+
+        let net = state.net_per_tick();
+        assert!((net.bandwidth - (state.production_per_tick.bandwidth - 0.8)).abs() < 1e-9);
+        assert!((net.storage + 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_advice_waits_on_net_flow() {
+        let mut state = GameState::new();
+        state.buildings.get_mut(&BuildingKind::HomeRouter).unwrap().count = 1;
+        state.recalculate_production();
+        state.resources.bandwidth = 0.0;
+        let advice = state
+            .purchase_advice(Purchase::Building(BuildingKind::FiberConnection))
+            .unwrap();
+        assert!(advice.time_to_afford.is_some());
+
+        // A VPS eats more bandwidth than the router makes
+        state.buildings.get_mut(&BuildingKind::VPS).unwrap().count = 1;
+        state.recalculate_production();
+        assert!(state.production_per_tick.bandwidth > 0.0);
+        let advice = state
+            .purchase_advice(Purchase::Building(BuildingKind::FiberConnection))
+            .unwrap();
+        assert_eq!(advice.time_to_afford, None);
+    }
+
+    #[test]
+    fn test_synergy_boosts_target() {
+        let mut state = GameState::new();
+        state.buildings.get_mut(&BuildingKind::DedicatedServer).unwrap().count = 1;
+        state.recalculate_production();
+        let alone = state.production_per_tick.compute;
+
+        state.buildings.get_mut(&BuildingKind::LoadBalancer).unwrap().count = 3;
+        state.recalculate_production();
+        let server = state
+            .production_breakdown
+            .get(BuildingKind::DedicatedServer)
+            .unwrap();
+        assert!(server.synergy_multiplier > 1.0);
+        assert!((state.production_per_tick.compute - alone * server.synergy_multiplier).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_trade_crypto() {
+        let mut state = GameState::new();
+        state.resources.compute = 2_000.0;
+        let price = state.market.quote(TradeSide::Buy, TradeAsset::Compute);
+
+        state.trade(TradeSide::Buy, TradeAsset::Compute, 1.0).unwrap();
+        assert_eq!(state.resources.crypto, 1.0);
+        assert!((state.resources.compute - (2_000.0 - price)).abs() < 1e-9);
+
+        assert!(matches!(
+            state.trade(TradeSide::Sell, TradeAsset::Storage, 2.0),
+            Err(PurchaseError::InsufficientFunds { .. })
+        ));
+        state.trade(TradeSide::Sell, TradeAsset::Storage, 1.0).unwrap();
+        assert_eq!(state.resources.crypto, 0.0);
+        assert!(state.resources.storage > 0.0);
+    }
+
+    #[test]
+    fn test_limit_order_escrow_and_fill() {
+        let mut state = GameState::new();
+        state.resources.compute = 1_000.0;
+        state
+            .place_order(Order::new(TradeSide::Buy, TradeAsset::Compute, 1.0, 900.0))
+            .unwrap();
+        assert_eq!(state.resources.compute, 100.0);
+
+        let id = state.market.orders[0].id;
+        assert!(state.cancel_order(id));
+        assert_eq!(state.resources.compute, 1_000.0);
+
+        state
+            .place_order(Order::new(TradeSide::Buy, TradeAsset::Compute, 1.0, 900.0))
+            .unwrap();
+        // Low enough that even a bubble this step stays under the limit
+        state.market.price = 100.0;
+        state.step_market();
+        assert!(state.market.orders.is_empty());
+        assert_eq!(state.resources.crypto, 1.0);
+    }
+
+    #[test]
+    fn test_open_orders_do_not_survive_prestige() {
+        let mut state = GameState::new();
+        state.run_compute = progression::PRESTIGE_COMPUTE;
+        state.resources.compute = 1_000_000.0;
+        state
+            .place_order(Order::new(TradeSide::Buy, TradeAsset::Compute, 1_000.0, 900.0))
+            .unwrap();
+        let id = state.market.orders[0].id;
+        state.market.price = 5_000.0;
+
+        state.prestige();
+        assert!(state.market.orders.is_empty());
+        assert_eq!(state.market.price, BASE_PRICE);
+        assert!(!state.cancel_order(id));
+        assert_eq!(state.resources.compute, 50.0);
+    }
+
+    #[test]
+    fn test_sync_content_adds_missing_and_keeps_purchases() {
+        let mut state = GameState::new();
+        state.buildings.remove(&BuildingKind::SolarArray);
+        state.upgrades[0].purchased = true;
+        state.upgrades[1].name = "Stale".into();
+        state.upgrades.pop();
+
+        state.sync_content();
+        assert_eq!(state.buildings[&BuildingKind::SolarArray].count, 0);
+        assert_eq!(state.upgrades.len(), all_upgrades().len());
+        assert!(state.upgrades[0].purchased);
+        assert_ne!(state.upgrades[1].name, "Stale");
+    }
+
+    #[test]
+    fn test_hardware_failure_takes_building_offline() {
+        let mut state = GameState::new();
+        state.resources.compute = 100.0;
+        state.purchase_building(BuildingKind::RaspberryPi).unwrap();
+
+        state.active_effects.push(
+            ActiveEffect::from_event(&GameEventKind::HardwareFailure(BuildingKind::RaspberryPi))
+                .unwrap(),
+        );
+        state.recalculate_production();
+        assert_eq!(state.production_per_tick.compute, 0.0);
+
+        state.active_effects[0].remaining_ticks = 1;
+        state.tick();
+        assert!(!state
+            .active_effects
+            .iter()
+            .any(|e| matches!(e.kind, EffectKind::Offline(_))));
+        assert!(state.production_per_tick.compute > 0.0);
+    }
+
+    #[test]
+    fn test_outages_end_with_the_run() {
+        let mut state = GameState::new();
+        state.active_effects.push(
+            ActiveEffect::from_event(&GameEventKind::HardwareFailure(BuildingKind::RaspberryPi))
+                .unwrap(),
+        );
+        state.run_compute = progression::PRESTIGE_COMPUTE;
+        state.prestige();
+        assert!(state.active_effects.is_empty());
+    }
+
+    #[test]
+    fn test_traffic_spike_is_in_the_breakdown() {
+        let mut state = GameState::new();
+        state.resources.compute = 100.0;
+        state.purchase_building(BuildingKind::RaspberryPi).unwrap();
+        state.modifiers.event_rate = 0.0;
+        let before = state.production_per_tick.compute;
+
+        state.traffic_spike_remaining = 1;
+        state.traffic_spike_multiplier = 3.0;
+        state.recalculate_production();
+        let pi = state
+            .production_breakdown
+            .get(BuildingKind::RaspberryPi)
+            .unwrap();
+        assert_eq!(pi.spike_multiplier, 3.0);
+        assert!((state.production_per_tick.compute - before * 3.0).abs() < 1e-9);
+
+        // The spike runs out after its last tick
+        state.tick();
+        assert!((state.production_per_tick.compute - before).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_upgrade_building() {
+        let mut state = GameState::new();
+        assert!(state.upgrade_building(BuildingKind::RaspberryPi).is_err()); // none owned
+
+        state.resources.compute = 1000.0;
+        state.purchase_building(BuildingKind::RaspberryPi).unwrap();
+        let before = state.production_per_tick.compute;
+        assert!(state.upgrade_building(BuildingKind::RaspberryPi).is_ok());
+        assert_eq!(state.buildings[&BuildingKind::RaspberryPi].level, 1);
+        assert!((state.resources.compute - (1000.0 - 10.0 - 100.0)).abs() < 1e-9);
+        assert!((state.production_per_tick.compute - before * 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_higher_levels_need_secondary_resource() {
+        let mut state = GameState::new();
+        state.resources.compute = 1e9;
+        state.purchase_building(BuildingKind::RaspberryPi).unwrap();
+        for _ in 0..4 {
+            assert!(state.upgrade_building(BuildingKind::RaspberryPi).is_ok());
+        }
+        // Level 5 also costs storage
+        let cost = state.building_level_cost(BuildingKind::RaspberryPi).unwrap();
+        assert!(cost.storage > 0.0);
+        assert!(state.upgrade_building(BuildingKind::RaspberryPi).is_err());
+
+        state.resources.storage = cost.storage;
+        assert!(state.upgrade_building(BuildingKind::RaspberryPi).is_ok());
+        assert_eq!(state.resources.storage, 0.0);
+    }
+
+    #[test]
+    fn test_marginal_production_does_not_mutate() {
+        let mut state = GameState::new();
+        state.resources.compute = 1000.0;
+        state.purchase_building(BuildingKind::RaspberryPi).unwrap();
+        let before = state.production_per_tick.compute;
+
+        let gain = state.marginal_production(Purchase::Building(BuildingKind::RaspberryPi));
+        assert!((gain.compute - before).abs() < 1e-9); // one more Pi doubles output
+        assert_eq!(state.buildings[&BuildingKind::RaspberryPi].count, 1);
+
+        let gain = state.marginal_production(Purchase::Upgrade(0));
+        assert!((gain.compute - before).abs() < 1e-9); // x2 Pi production
+
+        let gain = state.marginal_production(Purchase::BuildingLevel(BuildingKind::RaspberryPi));
+        assert!((gain.compute - before * 0.5).abs() < 1e-9); // +50% per level
+
+        assert_eq!(state.production_per_tick.compute, before);
+    }
+
+    #[test]
+    fn test_marginal_production_global_upgrade() {
+        let mut state = GameState::new();
+        state.resources.compute = 200_000.0;
+        state.purchase_building(BuildingKind::RaspberryPi).unwrap();
+        research(&mut state, 0);
+        research(&mut state, 3);
+
+        let before = state.production_per_tick.compute;
+        let gain = state.marginal_production(Purchase::Upgrade(6)); // x1.25 all
+        research(&mut state, 6);
+        assert!((before + gain.compute - state.production_per_tick.compute).abs() < 1e-9);
     }
 
     #[test]
@@ -523,7 +2307,7 @@ mod tests {
     fn test_check_achievements() {
         let mut state = GameState::new();
         state.resources.compute = 100.0;
-        state.purchase_building(BuildingKind::RaspberryPi);
+        state.purchase_building(BuildingKind::RaspberryPi).unwrap();
 
         let new = state.check_achievements();
         assert!(new.contains(&"Hello World".to_string()));
@@ -533,19 +2317,237 @@ mod tests {
         assert!(!new2.contains(&"Hello World".to_string()));
     }
 
+    #[test]
+    fn test_achievement_rewards_and_progress() {
+        let mut state = GameState::new();
+        state.tasks_completed = 12;
+        let (next, progress) = state.next_achievement().unwrap();
+        assert_eq!(next.id, "task_10");
+        assert_eq!(progress, 12.0);
+
+        state.buildings.get_mut(&BuildingKind::MonitoringStack).unwrap().count = 10;
+        let multiplier = state.global_multiplier;
+        let new = state.check_achievements();
+        assert!(new.contains(&"On Call".to_string()));
+        assert!(new.contains(&"Observability".to_string()));
+        assert!(new.contains(&"Sys Admin".to_string()));
+        assert!((state.global_multiplier - multiplier * 1.02).abs() < 1e-9);
+        assert_eq!(state.title(), Some("sysadmin"));
+
+        state.prestige_count = 5;
+        let reputation = state.resources.reputation;
+        state.check_achievements();
+        assert_eq!(state.resources.reputation, reputation + 5.0);
+
+        // Production rewards survive prestige
+        state.prestige();
+        assert!((state.achievement_multiplier() - 1.02).abs() < 1e-9);
+        let from_reputation = progression::reputation_multiplier(state.resources.reputation);
+        assert!(state.global_multiplier > from_reputation);
+    }
+
     #[test]
     fn test_global_multiplier_upgrade() {
         let mut state = GameState::new();
         state.resources.compute = 200_000.0;
-        state.purchase_building(BuildingKind::RaspberryPi);
+        state.purchase_building(BuildingKind::RaspberryPi).unwrap();
 
         let prod_before = state.production_per_tick.compute;
-        state.purchase_upgrade(0); // Overclocking (prereq for Automation Scripts)
-        state.purchase_upgrade(3); // Containerization (prereq for Automation Scripts)
-        state.purchase_upgrade(6); // Automation Scripts: x1.25 all
+        research(&mut state, 0); // Overclocking (prereq for Automation Scripts)
+        research(&mut state, 3); // Containerization (prereq for Automation Scripts)
+        research(&mut state, 6); // Automation Scripts: x1.25 all
         let prod_after = state.production_per_tick.compute;
 
         // Should be 2x (overclocking) * 1.25 (automation) = 2.5x
         assert!((prod_after / prod_before - 2.5).abs() < 0.1);
     }
+
+    #[test]
+    fn test_missing_packs_warn_on_load() {
+        let mut state = GameState::new();
+        let json = serde_json::to_string(&state).unwrap();
+        let mut loaded: GameState = serde_json::from_str(&json).unwrap();
+        loaded.packs = vec!["gone".into()];
+        let widget = BuildingKind::intern("gone:Widget");
+        loaded.buildings.insert(widget, BuildingInstance::new(widget));
+        let mut upgrade = loaded.upgrades[0].clone();
+        upgrade.id = crate::game::packs::pack_upgrade_id("gone:qec");
+        upgrade.purchased = true;
+        loaded.upgrades.push(upgrade.clone());
+        loaded.sync_content();
+        loaded.recalculate_production();
+
+        // Kept in the save so reinstalling the pack restores it
+        assert!(loaded.packs.contains(&"gone".to_string()));
+        assert_eq!(loaded.missing_upgrades, vec![upgrade.id]);
+        assert!(loaded.upgrades.iter().all(|u| u.id != upgrade.id));
+        let toasts = loaded.drain_notifications();
+        assert!(toasts.iter().any(|n| n.message.contains("gone")));
+        assert!(loaded.buildings.contains_key(&widget));
+        state.sync_content();
+        assert!(state.drain_notifications().is_empty());
+
+        // A missing purchase whose upgrade is back is purchased again
+        let id = state.upgrades[0].id;
+        state.missing_upgrades = vec![id];
+        state.sync_content();
+        assert!(state.upgrades[0].purchased);
+        assert!(state.missing_upgrades.is_empty());
+    }
+
+    #[test]
+    fn test_challenge_rules_and_completion() {
+        let mut state = GameState::new();
+        state.resources.compute = 5_000.0;
+        state.resources.reputation = 2.0;
+        state.stats.run.ticks = 40;
+        assert!(state.start_challenge("storage_only"));
+        assert_eq!(state.resources.compute, 0.0);
+        assert_eq!(state.resources.storage, 50.0);
+        assert_eq!(state.resources.reputation, 2.0);
+        assert_eq!(state.stats.fastest_prestige_ticks, None);
+
+        state.resources.compute = 100.0;
+        assert_eq!(
+            state.purchase_building(BuildingKind::RaspberryPi),
+            Err(PurchaseError::ChallengeRule)
+        );
+        state.purchase_building(BuildingKind::USBDrive).unwrap();
+
+        state.resources.storage = 50_000.0;
+        state.check_challenge();
+        assert!(state.challenge.is_none());
+        assert_eq!(state.modifiers, RunModifiers::default());
+        assert_eq!(state.resources.reputation, 5.0);
+        assert_eq!(state.completed_challenges, vec!["storage_only".to_string()]);
+
+        // Repeats don't pay the bonus again
+        assert!(state.start_challenge("storage_only"));
+        state.resources.storage = 50_000.0;
+        state.check_challenge();
+        assert!(state.challenge.is_none());
+        assert_eq!(state.resources.reputation, 5.0);
+        assert_eq!(state.completed_challenges.len(), 1);
+    }
+
+    #[test]
+    fn test_timed_challenge_fails() {
+        let mut state = GameState::new();
+        state.start_challenge("no_upgrades");
+        state.resources.compute = 1_000.0;
+        assert_eq!(state.purchase_upgrade(0), Err(PurchaseError::ChallengeRule));
+
+        state.start_challenge("speedrun");
+        state.total_ticks += state.challenge_remaining_ticks().unwrap();
+        state.check_challenge();
+        assert!(state.challenge.is_none());
+        assert!(state.completed_challenges.is_empty());
+        assert_eq!(state.event_log.back().unwrap().kind.slug(), "challenge_failed");
+    }
+
+    #[test]
+    fn test_daily_challenge_is_seeded_and_recorded() {
+        let id = daily::daily_id("2026-10-18".parse().unwrap());
+        let mut a = GameState::new();
+        let mut b = GameState::new();
+        a.resources.reputation = 10.0;
+        a.recalculate_production();
+        assert!(a.start_challenge(&id));
+        assert!(b.start_challenge(&id));
+        assert_eq!(a.global_multiplier, 1.0);
+        let rolls = |s: &mut GameState| (0..8).map(|_| s.rng.gen::<u64>()).collect::<Vec<_>>();
+        assert_eq!(rolls(&mut a), rolls(&mut b));
+
+        a.total_ticks += 400;
+        a.resources.compute = 1_000_000.0;
+        a.check_challenge();
+        assert!(a.completed_challenges.is_empty());
+        let results = a.drain_daily_results();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].ticks, 400);
+        assert_eq!(a.resources.reputation, 11.0);
+
+        // Replaying the same day ranks again but pays no more reputation
+        assert!(a.start_challenge(&id));
+        a.resources.compute = 1_000_000.0;
+        a.check_challenge();
+        assert_eq!(a.drain_daily_results().len(), 1);
+        assert_eq!(a.resources.reputation, 11.0);
+    }
+
+    #[test]
+    fn test_abandoned_daily_restores_bonuses() {
+        let id = daily::daily_id("2026-10-18".parse().unwrap());
+        let mut state = GameState::new();
+        state.resources.reputation = 10.0;
+        assert!(state.start_challenge(&id));
+        assert_eq!(state.global_multiplier, 1.0);
+
+        state.abandon_challenge();
+        let expected = progression::reputation_multiplier(10.0);
+        assert!(expected > 1.0);
+        assert!((state.global_multiplier - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_resumed_daily_is_not_ranked() {
+        let id = daily::daily_id("2026-10-18".parse().unwrap());
+        let mut state = GameState::new();
+        assert!(state.start_challenge(&id));
+        let json = serde_json::to_string(&state).unwrap();
+        let mut state: GameState = serde_json::from_str(&json).unwrap();
+        assert!(!state.challenge.as_ref().unwrap().seeded);
+
+        state.resources.compute = 1_000_000.0;
+        state.check_challenge();
+        assert!(state.challenge.is_none());
+        assert!(state.drain_daily_results().is_empty());
+    }
+
+    #[test]
+    fn test_incident_chain() {
+        let mut state = GameState::new();
+        state.modifiers.event_rate = 0.0; // no random events on top
+        state.resources.bandwidth = 1_000.0;
+        let event = GameEventKind::DDoSAttack { severity: 2 };
+        state
+            .incidents
+            .push(Incident::from_event(&event, 1, state.total_ticks, 10).unwrap());
+
+        // Detected straight away with plenty of monitoring; a prompt is queued
+        state.tick();
+        assert_eq!(state.incidents[0].state, IncidentState::Detected);
+        assert_eq!(state.task_queue.front().unwrap().incident, Some(1));
+        assert!(state.resources.bandwidth < 1_000.0);
+
+        state.acknowledge_incident(1);
+        state.mitigate_incident(1);
+        assert!(state.task_queue.is_empty());
+        let bandwidth = state.resources.bandwidth;
+        for _ in 0..crate::game::incidents::RECOVERY_TICKS {
+            state.tick();
+        }
+        assert!(state.incidents.is_empty());
+        assert!(state.resources.bandwidth >= bandwidth);
+        assert_eq!(state.stats.run.incidents_resolved, 1);
+        assert!(state.stats.run.mttr_seconds().unwrap() > 0.0);
+        assert_eq!(state.event_log.back().unwrap().kind.slug(), "incident_resolved");
+    }
+
+    #[test]
+    fn test_burned_out_incidents_stay_out_of_mttr() {
+        let mut state = GameState::new();
+        state.modifiers.event_rate = 0.0;
+        let event = GameEventKind::DDoSAttack { severity: 1 };
+        state
+            .incidents
+            .push(Incident::from_event(&event, 1, state.total_ticks, 10).unwrap());
+        for _ in 0..=crate::game::incidents::BURNOUT_TICKS {
+            state.tick();
+        }
+        assert!(state.incidents.is_empty());
+        assert_eq!(state.stats.run.incidents_burned_out, 1);
+        assert_eq!(state.stats.run.incidents_resolved, 0);
+        assert_eq!(state.stats.run.mttr_seconds(), None);
+    }
 }
@@ -0,0 +1,279 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::buildings::BuildingKind;
+use super::events::{EventCategory, GameEventKind};
+use super::incidents::Incident;
+use super::resources::Resources;
+
+/// Counters for a single run (or, when accumulated, for all runs).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunStats {
+    /// Online ticks played. Offline progress is not counted.
+    pub ticks: u64,
+    /// Highest production seen, per tick.
+    pub peak_production: Resources,
+    pub total_spent: Resources,
+    pub buildings_bought: HashMap<BuildingKind, u32>,
+    /// Random events seen, keyed by `GameEventKind::slug`.
+    pub events_by_type: HashMap<String, u32>,
+    pub tasks_succeeded: u32,
+    pub tasks_failed: u32,
+    pub tasks_expired: u32,
+    pub reputation_earned: f64,
+    /// Incidents mitigated and recovered from.
+    pub incidents_resolved: u32,
+    /// Incidents left unmitigated until they died down; not in MTTD/MTTR.
+    pub incidents_burned_out: u32,
+    /// Incidents a player acknowledged before they were resolved.
+    pub incidents_acknowledged: u32,
+    /// Summed ticks from start to detection, acknowledgement and resolution.
+    pub incident_detect_ticks: u64,
+    pub incident_acknowledge_ticks: u64,
+    pub incident_resolve_ticks: u64,
+}
+
+impl RunStats {
+    pub fn seconds_played(&self) -> f64 {
+        self.ticks as f64 / 4.0
+    }
+
+    pub fn reputation_per_hour(&self) -> f64 {
+        let hours = self.seconds_played() / 3600.0;
+        if hours > 0.0 {
+            self.reputation_earned / hours
+        } else {
+            0.0
+        }
+    }
+
+    /// Mean time to detect, in seconds.
+    pub fn mttd_seconds(&self) -> Option<f64> {
+        mean_seconds(self.incident_detect_ticks, self.incidents_resolved)
+    }
+
+    /// Mean time to acknowledge, in seconds.
+    pub fn mtta_seconds(&self) -> Option<f64> {
+        mean_seconds(self.incident_acknowledge_ticks, self.incidents_acknowledged)
+    }
+
+    /// Mean time to resolve, in seconds.
+    pub fn mttr_seconds(&self) -> Option<f64> {
+        mean_seconds(self.incident_resolve_ticks, self.incidents_resolved)
+    }
+
+    fn record_incident(&mut self, incident: &Incident) {
+        let since_start = |tick: u64| tick.saturating_sub(incident.started_tick);
+        self.incidents_resolved += 1;
+        self.incident_detect_ticks += since_start(incident.detected_tick);
+        if let Some(tick) = incident.acknowledged_tick {
+            self.incidents_acknowledged += 1;
+            self.incident_acknowledge_ticks += since_start(tick);
+        }
+        if let Some(tick) = incident.resolved_tick {
+            self.incident_resolve_ticks += since_start(tick);
+        }
+    }
+
+    fn record_burnout(&mut self) {
+        self.incidents_burned_out += 1;
+    }
+
+    pub fn total_buildings_bought(&self) -> u32 {
+        self.buildings_bought.values().sum()
+    }
+
+    fn record_tick(&mut self, production: &Resources) {
+        self.ticks += 1;
+        let peak = &mut self.peak_production;
+        peak.compute = peak.compute.max(production.compute);
+        peak.bandwidth = peak.bandwidth.max(production.bandwidth);
+        peak.storage = peak.storage.max(production.storage);
+        peak.reputation = peak.reputation.max(production.reputation);
+        peak.crypto = peak.crypto.max(production.crypto);
+    }
+
+    fn record_event(&mut self, kind: &GameEventKind) {
+        match kind {
+            GameEventKind::TaskCompleted { .. } => self.tasks_succeeded += 1,
+            GameEventKind::TaskFailed { .. } => self.tasks_failed += 1,
+            GameEventKind::TaskExpired { .. } => self.tasks_expired += 1,
+            GameEventKind::ViralRepo { bonus_reputation }
+            | GameEventKind::OpenSourceContribution { bonus_reputation } => {
+                self.reputation_earned += bonus_reputation;
+            }
+            GameEventKind::Prestige { reputation }
+            | GameEventKind::ChallengeCompleted { reputation, .. } => {
+                self.reputation_earned += reputation;
+            }
+            GameEventKind::Custom { effect, .. } => {
+                self.reputation_earned += effect.reputation.max(0.0);
+            }
+            _ => {}
+        }
+        if matches!(
+            kind.category(),
+            EventCategory::Incident | EventCategory::Bonus
+        ) {
+            *self.events_by_type.entry(kind.slug().to_string()).or_insert(0) += 1;
+        }
+    }
+}
+
+fn mean_seconds(total_ticks: u64, count: u32) -> Option<f64> {
+    (count > 0).then(|| total_ticks as f64 / count as f64 / 4.0)
+}
+
+/// Per-run and all-time statistics.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    pub run: RunStats,
+    pub lifetime: RunStats,
+    /// Shortest run, in ticks, that ended in a prestige.
+    pub fastest_prestige_ticks: Option<u64>,
+}
+
+impl Stats {
+    pub fn record_tick(&mut self, production: &Resources) {
+        self.run.record_tick(production);
+        self.lifetime.record_tick(production);
+    }
+
+    pub fn record_spent(&mut self, cost: &Resources) {
+        self.run.total_spent.add(cost);
+        self.lifetime.total_spent.add(cost);
+    }
+
+    pub fn record_building(&mut self, kind: BuildingKind) {
+        *self.run.buildings_bought.entry(kind).or_insert(0) += 1;
+        *self.lifetime.buildings_bought.entry(kind).or_insert(0) += 1;
+    }
+
+    pub fn record_event(&mut self, kind: &GameEventKind) {
+        self.run.record_event(kind);
+        self.lifetime.record_event(kind);
+    }
+
+    /// Count reputation gained outside of events, e.g. from achievement
+    /// rewards, task rewards or scripts.
+    pub fn record_reputation(&mut self, amount: f64) {
+        if amount > 0.0 {
+            self.run.reputation_earned += amount;
+            self.lifetime.reputation_earned += amount;
+        }
+    }
+
+    /// Count a resolved incident towards MTTD/MTTA/MTTR.
+    pub fn record_incident(&mut self, incident: &Incident) {
+        self.run.record_incident(incident);
+        self.lifetime.record_incident(incident);
+    }
+
+    /// Count an incident that burned out without being mitigated.
+    pub fn record_burnout(&mut self) {
+        self.run.record_burnout();
+        self.lifetime.record_burnout();
+    }
+
+    /// Count the current run's length towards the fastest prestige.
+    pub fn record_prestige(&mut self) {
+        let ticks = self.run.ticks;
+        self.fastest_prestige_ticks = Some(match self.fastest_prestige_ticks {
+            Some(best) => best.min(ticks),
+            None => ticks,
+        });
+    }
+
+    /// Start a fresh run, e.g. on prestige, IPO or a challenge start.
+    pub fn finish_run(&mut self) {
+        self.run = RunStats::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peak_production() {
+        let mut stats = Stats::default();
+        stats.record_tick(&Resources {
+            compute: 5.0,
+            ..Default::default()
+        });
+        stats.record_tick(&Resources {
+            compute: 2.0,
+            bandwidth: 1.0,
+            ..Default::default()
+        });
+        assert_eq!(stats.run.peak_production.compute, 5.0);
+        assert_eq!(stats.run.peak_production.bandwidth, 1.0);
+        assert_eq!(stats.run.ticks, 2);
+    }
+
+    #[test]
+    fn test_record_event_counts() {
+        let mut stats = Stats::default();
+        stats.record_event(&GameEventKind::DDoSAttack { severity: 2 });
+        stats.record_event(&GameEventKind::DDoSAttack { severity: 4 });
+        stats.record_event(&GameEventKind::TaskFailed { name: "x".into() });
+        stats.record_event(&GameEventKind::BuildingPurchased(BuildingKind::VPS));
+
+        assert_eq!(stats.run.events_by_type.get("ddos"), Some(&2));
+        assert_eq!(stats.run.events_by_type.len(), 1);
+        assert_eq!(stats.run.tasks_failed, 1);
+        assert_eq!(stats.lifetime.tasks_failed, 1);
+    }
+
+    #[test]
+    fn test_finish_run_keeps_fastest() {
+        let mut stats = Stats::default();
+        stats.run.ticks = 1000;
+        stats.record_prestige();
+        stats.finish_run();
+        assert_eq!(stats.fastest_prestige_ticks, Some(1000));
+        assert_eq!(stats.run.ticks, 0);
+
+        stats.run.ticks = 2000;
+        stats.record_prestige();
+        stats.finish_run();
+        assert_eq!(stats.fastest_prestige_ticks, Some(1000));
+
+        stats.run.ticks = 500;
+        stats.record_prestige();
+        stats.finish_run();
+        assert_eq!(stats.fastest_prestige_ticks, Some(500));
+
+        // Runs ended some other way don't count
+        stats.run.ticks = 40;
+        stats.finish_run();
+        assert_eq!(stats.fastest_prestige_ticks, Some(500));
+    }
+
+    #[test]
+    fn test_reputation_per_hour() {
+        let mut stats = RunStats {
+            ticks: 4 * 3600,
+            ..Default::default()
+        };
+        stats.record_event(&GameEventKind::Prestige { reputation: 3.0 });
+        assert!((stats.reputation_per_hour() - 3.0).abs() < 1e-9);
+
+        stats.record_event(&GameEventKind::ChallengeCompleted {
+            name: "x".into(),
+            reputation: 2.0,
+        });
+        assert!((stats.reputation_per_hour() - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_record_reputation() {
+        let mut stats = Stats::default();
+        stats.record_reputation(4.0);
+        stats.record_reputation(-1.0);
+        assert_eq!(stats.run.reputation_earned, 4.0);
+        assert_eq!(stats.lifetime.reputation_earned, 4.0);
+    }
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+use super::buildings::BuildingKind;
+use super::content;
+use super::formulas;
+
+/// What a synergy does for the buildings it targets.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SynergyEffect {
+    /// Multiplies production of one building.
+    Production(BuildingKind),
+    /// Lowers the odds of overloads and failures hitting a tier.
+    FailureOdds { tier: u8 },
+}
+
+/// A bonus one building gives others, growing with how many of the
+/// source are owned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Synergy {
+    pub source: BuildingKind,
+    pub effect: SynergyEffect,
+    /// Strength per source building.
+    pub strength: f64,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl Synergy {
+    /// Production multiplier or failure odds with `sources` owned.
+    pub fn value(&self, sources: u32) -> f64 {
+        match self.effect {
+            SynergyEffect::Production(_) => formulas::synergy_multiplier(self.strength, sources),
+            SynergyEffect::FailureOdds { .. } => formulas::failure_odds(self.strength, sources),
+        }
+    }
+
+    pub fn involves(&self, kind: BuildingKind) -> bool {
+        self.source == kind || self.effect == SynergyEffect::Production(kind)
+    }
+}
+
+/// The synergy table, from content data.
+pub fn all_synergies() -> &'static [Synergy] {
+    &content::registry().synergies
+}
+
+/// Product of every production synergy targeting `kind`.
+pub fn production_multiplier(kind: BuildingKind, count: impl Fn(BuildingKind) -> u32) -> f64 {
+    all_synergies()
+        .iter()
+        .filter(|s| s.effect == SynergyEffect::Production(kind))
+        .map(|s| s.value(count(s.source)))
+        .product()
+}
+
+/// Odds that an overload or failure rolled against `tier` goes ahead.
+pub fn failure_odds(tier: u8, count: impl Fn(BuildingKind) -> u32) -> f64 {
+    all_synergies()
+        .iter()
+        .filter(|s| s.effect == SynergyEffect::FailureOdds { tier })
+        .map(|s| s.value(count(s.source)))
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_production_multiplier() {
+        let none = production_multiplier(BuildingKind::DedicatedServer, |_| 0);
+        assert_eq!(none, 1.0);
+
+        let boosted = production_multiplier(BuildingKind::DedicatedServer, |k| {
+            if k == BuildingKind::LoadBalancer {
+                4
+            } else {
+                0
+            }
+        });
+        assert!(boosted > 1.0);
+    }
+
+    #[test]
+    fn test_failure_odds_only_for_watched_tier() {
+        let monitored = |k| if k == BuildingKind::MonitoringStack { 5 } else { 0 };
+        assert!(failure_odds(2, monitored) < 1.0);
+        assert_eq!(failure_odds(5, monitored), 1.0);
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use super::buildings::BuildingKind;
+use super::content;
 use super::resources::Resources;
 
 pub type UpgradeId = usize;
@@ -11,8 +13,10 @@ pub struct Upgrade {
     pub name: String,
     pub description: String,
     pub cost: Resources,
+    #[serde(default)]
     pub prerequisites: Vec<UpgradeId>,
     pub effect: UpgradeEffect,
+    #[serde(default)]
     pub purchased: bool,
 }
 
@@ -26,141 +30,62 @@ pub enum UpgradeEffect {
     IncreaseTaskReward(f64),
 }
 
+/// Where an upgrade stands in the research tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResearchStatus {
+    Purchased,
+    Available,
+    /// Paid for and in the research queue.
+    Researching,
+    /// Prerequisites neither purchased nor queued.
+    Locked(Vec<UpgradeId>),
+}
+
+/// Column of each upgrade in the research tree: 0 without prerequisites,
+/// otherwise one past its deepest prerequisite.
+pub fn research_depths(upgrades: &[Upgrade]) -> HashMap<UpgradeId, usize> {
+    fn depth(
+        id: UpgradeId,
+        upgrades: &[Upgrade],
+        depths: &mut HashMap<UpgradeId, usize>,
+    ) -> usize {
+        if let Some(d) = depths.get(&id) {
+            return *d;
+        }
+        let d = upgrades
+            .iter()
+            .find(|u| u.id == id)
+            .map(|u| {
+                u.prerequisites
+                    .iter()
+                    .map(|p| depth(*p, upgrades, depths) + 1)
+                    .max()
+                    .unwrap_or(0)
+            })
+            .unwrap_or(0);
+        depths.insert(id, d);
+        d
+    }
+
+    let mut depths = HashMap::new();
+    for upgrade in upgrades {
+        depth(upgrade.id, upgrades, &mut depths);
+    }
+    depths
+}
+
+/// Upgrades that list `id` as a prerequisite.
+pub fn dependents(upgrades: &[Upgrade], id: UpgradeId) -> Vec<UpgradeId> {
+    upgrades
+        .iter()
+        .filter(|u| u.prerequisites.contains(&id))
+        .map(|u| u.id)
+        .collect()
+}
+
+/// A fresh copy of every upgrade, from the content registry.
 pub fn all_upgrades() -> Vec<Upgrade> {
-    vec![
-        // Tier 1 upgrades
-        Upgrade {
-            id: 0,
-            name: "Overclocking".into(),
-            description: "x2 Raspberry Pi production".into(),
-            cost: Resources { compute: 500.0, ..Default::default() },
-            prerequisites: vec![],
-            effect: UpgradeEffect::MultiplyProduction(BuildingKind::RaspberryPi, 2.0),
-            purchased: false,
-        },
-        Upgrade {
-            id: 1,
-            name: "QoS Rules".into(),
-            description: "x2 Home Router production".into(),
-            cost: Resources { bandwidth: 300.0, ..Default::default() },
-            prerequisites: vec![],
-            effect: UpgradeEffect::MultiplyProduction(BuildingKind::HomeRouter, 2.0),
-            purchased: false,
-        },
-        Upgrade {
-            id: 2,
-            name: "USB 3.0".into(),
-            description: "x2 USB Drive production".into(),
-            cost: Resources { storage: 400.0, ..Default::default() },
-            prerequisites: vec![],
-            effect: UpgradeEffect::MultiplyProduction(BuildingKind::USBDrive, 2.0),
-            purchased: false,
-        },
-        // Tier 2 upgrades
-        Upgrade {
-            id: 3,
-            name: "Containerization".into(),
-            description: "x2 VPS production".into(),
-            cost: Resources { compute: 5_000.0, ..Default::default() },
-            prerequisites: vec![0],
-            effect: UpgradeEffect::MultiplyProduction(BuildingKind::VPS, 2.0),
-            purchased: false,
-        },
-        Upgrade {
-            id: 4,
-            name: "Fiber Optic Upgrade".into(),
-            description: "x2 Fiber Connection production".into(),
-            cost: Resources { bandwidth: 3_000.0, ..Default::default() },
-            prerequisites: vec![1],
-            effect: UpgradeEffect::MultiplyProduction(BuildingKind::FiberConnection, 2.0),
-            purchased: false,
-        },
-        Upgrade {
-            id: 5,
-            name: "RAID Configuration".into(),
-            description: "x2 NAS Box production".into(),
-            cost: Resources { storage: 4_000.0, ..Default::default() },
-            prerequisites: vec![2],
-            effect: UpgradeEffect::MultiplyProduction(BuildingKind::NASBox, 2.0),
-            purchased: false,
-        },
-        // Global upgrades
-        Upgrade {
-            id: 6,
-            name: "Automation Scripts".into(),
-            description: "x1.25 all production".into(),
-            cost: Resources { compute: 10_000.0, ..Default::default() },
-            prerequisites: vec![3],
-            effect: UpgradeEffect::MultiplyAllProduction(1.25),
-            purchased: false,
-        },
-        Upgrade {
-            id: 7,
-            name: "Kubernetes".into(),
-            description: "x1.5 all production".into(),
-            cost: Resources { compute: 100_000.0, bandwidth: 50_000.0, ..Default::default() },
-            prerequisites: vec![6],
-            effect: UpgradeEffect::MultiplyAllProduction(1.5),
-            purchased: false,
-        },
-        Upgrade {
-            id: 8,
-            name: "Terraform".into(),
-            description: "x1.5 all production".into(),
-            cost: Resources { compute: 1_000_000.0, ..Default::default() },
-            prerequisites: vec![7],
-            effect: UpgradeEffect::MultiplyAllProduction(1.5),
-            purchased: false,
-        },
-        // Tier 3 upgrades
-        Upgrade {
-            id: 9,
-            name: "Blade Servers".into(),
-            description: "x3 Dedicated Server production".into(),
-            cost: Resources { compute: 50_000.0, ..Default::default() },
-            prerequisites: vec![3],
-            effect: UpgradeEffect::MultiplyProduction(BuildingKind::DedicatedServer, 3.0),
-            purchased: false,
-        },
-        Upgrade {
-            id: 10,
-            name: "Anycast Routing".into(),
-            description: "x3 Load Balancer production".into(),
-            cost: Resources { bandwidth: 30_000.0, ..Default::default() },
-            prerequisites: vec![4],
-            effect: UpgradeEffect::MultiplyProduction(BuildingKind::LoadBalancer, 3.0),
-            purchased: false,
-        },
-        // Task reward upgrades
-        Upgrade {
-            id: 11,
-            name: "Incident Playbooks".into(),
-            description: "x2 task rewards".into(),
-            cost: Resources { compute: 20_000.0, ..Default::default() },
-            prerequisites: vec![],
-            effect: UpgradeEffect::IncreaseTaskReward(2.0),
-            purchased: false,
-        },
-        // Offline upgrades
-        Upgrade {
-            id: 12,
-            name: "Cron Jobs".into(),
-            description: "50% offline efficiency (up from 25%)".into(),
-            cost: Resources { compute: 50_000.0, ..Default::default() },
-            prerequisites: vec![6],
-            effect: UpgradeEffect::IncreaseOfflineEfficiency(0.50),
-            purchased: false,
-        },
-        Upgrade {
-            id: 13,
-            name: "Systemd Timers".into(),
-            description: "75% offline efficiency".into(),
-            cost: Resources { compute: 500_000.0, ..Default::default() },
-            prerequisites: vec![12],
-            effect: UpgradeEffect::IncreaseOfflineEfficiency(0.75),
-            purchased: false,
-        },
-    ]
+    content::registry().upgrades.clone()
 }
 
 #[cfg(test)]
@@ -176,6 +101,29 @@ mod tests {
         assert_eq!(ids.len(), upgrades.len());
     }
 
+    #[test]
+    fn test_research_depths() {
+        let upgrades = all_upgrades();
+        let depths = research_depths(&upgrades);
+        assert_eq!(depths.len(), upgrades.len());
+        assert_eq!(depths[&0], 0); // Overclocking
+        assert_eq!(depths[&3], 1); // Containerization <- Overclocking
+        for upgrade in &upgrades {
+            for prereq in &upgrade.prerequisites {
+                assert!(depths[prereq] < depths[&upgrade.id]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dependents() {
+        let upgrades = all_upgrades();
+        assert!(dependents(&upgrades, 0).contains(&3));
+        assert!(dependents(&upgrades, 3).iter().all(|id| {
+            upgrades[*id].prerequisites.contains(&3)
+        }));
+    }
+
     #[test]
     fn test_prerequisites_valid() {
         let upgrades = all_upgrades();
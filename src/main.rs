@@ -9,7 +9,7 @@ mod logging;
 mod theme;
 mod tui;
 
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{eyre, Result};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -23,14 +23,23 @@ async fn main() -> Result<()> {
     if args.iter().any(|a| a == "--help" || a == "-h") {
         println!("idle-terminal v{}", env!("CARGO_PKG_VERSION"));
         println!("A TUI-based idle game with an IT/DevOps theme\n");
-        println!("Usage: idle-terminal [OPTIONS]\n");
+        println!("Usage: idle-terminal [OPTIONS]");
+        println!("       idle-terminal log [--since <DURATION>] [--kind <KIND>] [--json]\n");
         println!("Options:");
         println!("  --reset    Delete save data and start fresh");
         println!("  --version  Print version information");
         println!("  --help     Print this help message");
+        println!("\nLog options:");
+        println!("  --since    Only show events newer than e.g. 30m, 1h, 2d");
+        println!("  --kind     Filter by event (ddos, prestige, ...) or category (incident, task, ...)");
+        println!("  --json     Print raw JSON lines");
         return Ok(());
     }
 
+    if args.get(1).map(String::as_str) == Some("log") {
+        return print_journal(&args[2..]);
+    }
+
     if args.iter().any(|a| a == "--reset") {
         game::save::delete_save()?;
         println!("Save data deleted. Starting fresh.");
@@ -44,3 +53,47 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+fn print_journal(args: &[String]) -> Result<()> {
+    let mut query = game::journal::JournalQuery::default();
+    let mut json = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--since" => {
+                let value = iter.next().map(String::as_str).unwrap_or_default();
+                let duration = game::journal::parse_duration(value)
+                    .ok_or_else(|| eyre!("Invalid duration '{value}' (try 30m, 1h, 2d)"))?;
+                let since = chrono::Utc::now()
+                    .checked_sub_signed(duration)
+                    .ok_or_else(|| eyre!("Duration '{value}' is too long"))?;
+                query.since = Some(since);
+            }
+            "--kind" => {
+                query.kind = iter.next().cloned();
+            }
+            "--json" => json = true,
+            other => return Err(eyre!("Unknown log option '{other}'")),
+        }
+    }
+
+    for entry in game::journal::query(&query)? {
+        if json {
+            println!("{}", serde_json::to_string(&entry)?);
+        } else {
+            println!(
+                "{}  run {:<3} {:<5}  {}",
+                entry
+                    .event
+                    .timestamp
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S"),
+                entry.run,
+                entry.event.kind.severity_color().label(),
+                entry.event.kind.description(),
+            );
+        }
+    }
+    Ok(())
+}
@@ -24,10 +24,6 @@ pub fn title() -> Style {
         .add_modifier(Modifier::BOLD)
 }
 
-pub fn text_primary() -> Style {
-    Style::default().fg(FG_PRIMARY)
-}
-
 pub fn text_dim() -> Style {
     Style::default().fg(FG_DIM)
 }